@@ -0,0 +1,306 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::{classify_object, shape_symmetries, Grid, LifeRule, ObjectKind, Shape};
+
+/// Chiffres du format de Wechsler : une colonne de 5 cellules vaut de `0` à `v`, et le
+/// caractère qui suit `y` compte de `0` à `z`.
+const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// Erreur renvoyée lorsqu'un apgcode ne peut pas être décodé.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ApgcodeError {
+    message: String,
+}
+
+impl ApgcodeError {
+    /// Crée une nouvelle erreur avec le message spécifié.
+    pub fn new(message: impl Into<String>) -> Self {
+        ApgcodeError {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ApgcodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid apgcode: {}", self.message)
+    }
+}
+
+impl Error for ApgcodeError {}
+
+/// Encode une forme dans le format de Wechsler étendu, sans la réorienter.
+///
+/// La forme est découpée en bandes de 5 lignes séparées par `z` ; chaque colonne d'une bande est
+/// écrite comme un chiffre dont le bit `i` est la cellule de la ligne `i`. Les suites de colonnes
+/// vides sont abrégées (`w` pour 2, `x` pour 3, `y` suivi d'un chiffre pour 4 à 39) et celles en
+/// fin de bande sont omises.
+///
+/// # Exemple
+///
+/// ```
+/// use crate::lifers::wechsler_encode;
+///
+/// // Planeur
+/// assert_eq!(wechsler_encode(&[(0, 0), (0, 1), (0, 2), (1, 2), (2, 1)]), "153");
+/// ```
+pub fn wechsler_encode(shape: &[(usize, usize)]) -> String {
+    let height = shape.iter().map(|&(row, _)| row + 1).max().unwrap_or(0);
+    let width = shape.iter().map(|&(_, col)| col + 1).max().unwrap_or(0);
+    let mut columns = vec![0u8; height.div_ceil(5) * width];
+    for &(row, col) in shape {
+        columns[(row / 5) * width + col] |= 1 << (row % 5);
+    }
+
+    let mut code = String::new();
+    for (strip, values) in columns.chunks(width.max(1)).enumerate() {
+        if strip > 0 {
+            code.push('z');
+        }
+        let mut zeros = 0;
+        for &value in values {
+            if value == 0 {
+                zeros += 1;
+                continue;
+            }
+            while zeros > 39 {
+                code.push_str("yz");
+                zeros -= 39;
+            }
+            match zeros {
+                0 => {}
+                1 => code.push('0'),
+                2 => code.push('w'),
+                3 => code.push('x'),
+                _ => {
+                    code.push('y');
+                    code.push(DIGITS[zeros - 4] as char);
+                }
+            }
+            zeros = 0;
+            code.push(DIGITS[value as usize] as char);
+        }
+    }
+    code
+}
+
+/// Décode une forme écrite dans le format de Wechsler étendu.
+///
+/// # Erreurs
+///
+/// Renvoie une erreur si le code contient un caractère inattendu.
+pub fn wechsler_decode(code: &str) -> Result<Shape, ApgcodeError> {
+    let digit = |c: char| DIGITS.iter().position(|&d| d as char == c);
+    let mut shape = Vec::new();
+    for (strip, text) in split_strips(code).into_iter().enumerate() {
+        let mut col = 0;
+        let mut chars = text.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                'w' => col += 2,
+                'x' => col += 3,
+                'y' => {
+                    let count = chars
+                        .next()
+                        .and_then(digit)
+                        .ok_or_else(|| ApgcodeError::new(format!("missing count after 'y' in {:?}", code)))?;
+                    col += count + 4;
+                }
+                _ => {
+                    let value = digit(c)
+                        .filter(|&value| value < 32)
+                        .ok_or_else(|| ApgcodeError::new(format!("unexpected character {:?} in {:?}", c, code)))?;
+                    for bit in 0..5 {
+                        if value & (1 << bit) != 0 {
+                            shape.push((strip * 5 + bit, col));
+                        }
+                    }
+                    col += 1;
+                }
+            }
+        }
+    }
+    shape.sort_unstable();
+    Ok(shape)
+}
+
+/// Sépare les bandes d'un code de Wechsler, en ignorant les `z` qui comptent des colonnes après `y`.
+fn split_strips(code: &str) -> Vec<String> {
+    let mut strips = vec![String::new()];
+    let mut after_y = false;
+    for c in code.chars() {
+        if c == 'z' && !after_y {
+            strips.push(String::new());
+        } else {
+            strips.last_mut().unwrap().push(c);
+        }
+        after_y = c == 'y' && !after_y;
+    }
+    strips
+}
+
+/// Compare deux codes de Wechsler : le plus court, puis le plus petit, est canonique.
+fn code_key(code: &str) -> (usize, &str) {
+    (code.len(), code)
+}
+
+/// Renvoie l'orientation canonique d'une forme et son code de Wechsler.
+///
+/// Parmi les 8 images de la forme par les rotations et réflexions du carré, l'orientation
+/// canonique est celle dont le code est le plus court, puis le plus petit dans l'ordre
+/// lexicographique, comme dans apgsearch.
+pub fn wechsler_canonical(shape: &[(usize, usize)]) -> (String, Shape) {
+    shape_symmetries(shape)
+        .into_iter()
+        .map(|image| (wechsler_encode(&image), image))
+        .min_by(|a, b| code_key(&a.0).cmp(&code_key(&b.0)))
+        .unwrap_or_default()
+}
+
+/// Choisit, parmi les phases d'un objet, celle dont le code canonique est le plus petit.
+pub fn wechsler_canonical_phases(phases: &[Shape]) -> (String, Shape) {
+    phases
+        .iter()
+        .map(|phase| wechsler_canonical(phase))
+        .min_by(|a, b| code_key(&a.0).cmp(&code_key(&b.0)))
+        .unwrap_or_default()
+}
+
+/// Renvoie l'apgcode d'un objet à partir de sa nature et de sa forme canonique.
+///
+/// Le préfixe est `xs` suivi de la population pour une nature morte, `xp` suivi de la période
+/// pour un oscillateur et `xq` suivi de la période pour un vaisseau. Les objets non classés
+/// reçoivent le préfixe `zz`, propre à cette bibliothèque.
+pub fn apgcode(kind: ObjectKind, shape: &[(usize, usize)]) -> String {
+    let prefix = match kind {
+        ObjectKind::StillLife => format!("xs{}", shape.len()),
+        ObjectKind::Oscillator { period } => format!("xp{}", period),
+        ObjectKind::Spaceship { period, .. } => format!("xq{}", period),
+        ObjectKind::Unclassified => String::from("zz"),
+    };
+    format!("{}_{}", prefix, wechsler_encode(shape))
+}
+
+/// Décode un apgcode de la forme `xs4_33`, `xp2_7`, `xq4_153` ou `zz_…` et renvoie la forme qu'il
+/// décrit.
+///
+/// # Exemple
+///
+/// ```
+/// use crate::lifers::apgcode_decode;
+///
+/// assert_eq!(apgcode_decode("xs4_33").unwrap(), vec![(0, 0), (0, 1), (1, 0), (1, 1)]);
+/// assert!(apgcode_decode("xs4").is_err());
+/// ```
+///
+/// # Erreurs
+///
+/// Renvoie une erreur si le préfixe n'est ni `xs`, `xp` ou `xq` suivi d'un nombre, ni `zz`, si le code
+/// de Wechsler est invalide, ou si la population d'une nature morte ne correspond pas.
+pub fn apgcode_decode(code: &str) -> Result<Shape, ApgcodeError> {
+    let (prefix, body) = code
+        .split_once('_')
+        .ok_or_else(|| ApgcodeError::new(format!("missing '_' in {:?}", code)))?;
+    let number: usize = match prefix.get(..2) {
+        // Les objets non classés n'ont pas de nombre (voir `apgcode`)
+        _ if prefix == "zz" => 0,
+        Some("xs" | "xp" | "xq") => prefix[2..]
+            .parse()
+            .map_err(|_| ApgcodeError::new(format!("invalid number in prefix {:?}", prefix)))?,
+        _ => return Err(ApgcodeError::new(format!("unknown prefix {:?}", prefix))),
+    };
+    let shape = wechsler_decode(body)?;
+    if prefix.starts_with("xs") && shape.len() != number {
+        return Err(ApgcodeError::new(format!(
+            "{:?} has {} cells but its prefix announces {}",
+            code,
+            shape.len(),
+            number
+        )));
+    }
+    Ok(shape)
+}
+
+/// Renvoie l'apgcode du motif formé par toutes les cellules vivantes d'une grille.
+///
+/// Le motif est extrait de la grille puis classé en le faisant évoluer isolément sur un plan
+/// (voir `classify_object`) pour choisir son préfixe.
+///
+/// # Arguments
+///
+/// * `grid` - La grille contenant le motif.
+/// * `rule` - La règle qui fait évoluer le motif.
+/// * `max_period` - La période maximale recherchée.
+///
+/// # Exemple
+///
+/// ```
+/// use crate::lifers::{grid_apgcode, ConwaysGrid, Grid, LifeRule};
+///
+/// let mut grid = ConwaysGrid::new(10, 10, false);
+/// for (row, col) in [(4, 3), (4, 4), (4, 5)] {
+///     grid.set_cell_state(row, col, 1);
+/// }
+/// assert_eq!(grid_apgcode(&grid, &LifeRule::conway(), 30), "xp2_7");
+/// ```
+pub fn grid_apgcode<G: Grid + ?Sized>(grid: &G, rule: &LifeRule, max_period: usize) -> String {
+    let cols = grid.cols();
+    let cells: Vec<(isize, isize)> = (0..grid.rows() * cols)
+        .filter(|&index| grid.current_cells()[index] >= 1)
+        .map(|index| ((index / cols) as isize, (index % cols) as isize))
+        .collect();
+    let (kind, shape) = classify_object(&cells, rule, max_period);
+    apgcode(kind, &shape)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConwaysGrid;
+
+    fn grid_with(cells: &[(usize, usize)]) -> ConwaysGrid {
+        let mut grid = ConwaysGrid::new(12, 12, false);
+        for &(row, col) in cells {
+            grid.set_cell_state(row + 4, col + 4, 1);
+        }
+        grid
+    }
+
+    #[test]
+    fn test_known_apgcodes() {
+        let rule = LifeRule::conway();
+        let block = grid_with(&[(0, 0), (0, 1), (1, 0), (1, 1)]);
+        let blinker = grid_with(&[(1, 0), (1, 1), (1, 2)]);
+        let glider = grid_with(&[(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]);
+        let beehive = grid_with(&[(0, 1), (0, 2), (1, 0), (1, 3), (2, 1), (2, 2)]);
+        assert_eq!(grid_apgcode(&block, &rule, 30), "xs4_33");
+        assert_eq!(grid_apgcode(&blinker, &rule, 30), "xp2_7");
+        assert_eq!(grid_apgcode(&glider, &rule, 30), "xq4_153");
+        assert_eq!(grid_apgcode(&beehive, &rule, 30), "xs6_696");
+    }
+
+    #[test]
+    fn test_decode_round_trip() {
+        // Pentadécathlon : deux bandes de 5 lignes
+        let shape = apgcode_decode("xp15_4r4z4r4").unwrap();
+        assert_eq!(shape.len(), 12);
+        assert_eq!(wechsler_encode(&shape), "4r4z4r4");
+
+        // Longues suites de colonnes vides
+        let sparse = vec![(0, 0), (0, 5), (0, 50), (6, 0)];
+        let code = wechsler_encode(&sparse);
+        assert_eq!(code, "1y01yzy11z2");
+        assert_eq!(wechsler_decode(&code).unwrap(), sparse);
+
+        // Les objets non classés se relisent eux aussi
+        let code = apgcode(ObjectKind::Unclassified, &sparse);
+        assert_eq!(code, "zz_1y01yzy11z2");
+        assert_eq!(apgcode_decode(&code).unwrap(), sparse);
+        assert!(apgcode_decode("zz1_33").is_err());
+
+        assert!(apgcode_decode("xs5_33").is_err());
+        assert!(apgcode_decode("yy4_33").is_err());
+        assert!(wechsler_decode("3!").is_err());
+    }
+}
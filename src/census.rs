@@ -0,0 +1,409 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+use crate::{apgcode, grid_index, grid_update_rule, wechsler_canonical_phases, LifeRule, Topology};
+
+/// Cellules vivantes d'un objet, en coordonnées `(ligne, colonne)` relatives à son coin supérieur gauche.
+pub type Shape = Vec<(usize, usize)>;
+
+/// Paramètres d'un recensement.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CensusOptions {
+    /// Distance de Tchebychev maximale entre deux cellules d'un même objet (1 pour la 8-connexité).
+    pub merge_distance: usize,
+    /// Période maximale recherchée lors de la classification.
+    pub max_period: usize,
+    /// Règle utilisée pour faire évoluer chaque objet isolément.
+    pub rule: LifeRule,
+}
+
+impl Default for CensusOptions {
+    fn default() -> Self {
+        CensusOptions {
+            merge_distance: 1,
+            max_period: 30,
+            rule: LifeRule::conway(),
+        }
+    }
+}
+
+/// Nature d'un objet recensé.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ObjectKind {
+    /// Objet stable, identique à chaque génération.
+    StillLife,
+    /// Objet qui revient à son état initial, au même endroit, après `period` générations.
+    Oscillator { period: usize },
+    /// Objet qui revient à son état initial après `period` générations, déplacé de `dx` colonnes et
+    /// `dy` lignes (en valeurs absolues, avec `dx >= dy`).
+    Spaceship { period: usize, dx: usize, dy: usize },
+    /// Objet qui ne revient pas à son état initial dans la période maximale, ou qui meurt.
+    Unclassified,
+}
+
+impl fmt::Display for ObjectKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ObjectKind::StillLife => write!(f, "still life"),
+            ObjectKind::Oscillator { period } => write!(f, "oscillator p{}", period),
+            ObjectKind::Spaceship { period, dx, dy } => write!(f, "spaceship ({},{})c/{}", dx, dy, period),
+            ObjectKind::Unclassified => write!(f, "unclassified"),
+        }
+    }
+}
+
+/// Ligne d'un recensement : un objet et son nombre d'occurrences.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CensusEntry {
+    /// Nature de l'objet.
+    pub kind: ObjectKind,
+    /// Apgcode de l'objet, qui l'identifie à une symétrie et une phase près.
+    pub apgcode: String,
+    /// Forme canonique de l'objet, décrite par son apgcode.
+    pub shape: Shape,
+    /// Nombre d'occurrences de l'objet sur la grille.
+    pub count: usize,
+}
+
+impl CensusEntry {
+    /// Renvoie le nombre de cellules vivantes de la forme canonique.
+    pub fn population(&self) -> usize {
+        self.shape.len()
+    }
+}
+
+/// Résultat d'un recensement, trié par nombre d'occurrences décroissant.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Census {
+    /// Objets recensés.
+    pub entries: Vec<CensusEntry>,
+}
+
+impl Census {
+    /// Renvoie le nombre total d'objets recensés.
+    pub fn total(&self) -> usize {
+        self.entries.iter().map(|entry| entry.count).sum()
+    }
+
+    /// Renvoie le nombre d'objets dont la nature satisfait le prédicat.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::{grid_census, CensusOptions, ObjectKind};
+    ///
+    /// // Un bloc et un clignotant
+    /// let mut cells = vec![0; 8 * 8];
+    /// for index in [9, 10, 17, 18, 45, 46, 47] {
+    ///     cells[index] = 1;
+    /// }
+    /// let census = grid_census(&cells, 8, 8, false, &CensusOptions::default());
+    /// assert_eq!(census.total(), 2);
+    /// assert_eq!(census.count(|kind| matches!(kind, ObjectKind::Oscillator { .. })), 1);
+    /// ```
+    pub fn count(&self, predicate: impl Fn(ObjectKind) -> bool) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| predicate(entry.kind))
+            .map(|entry| entry.count)
+            .sum()
+    }
+
+    /// Ajoute `count` occurrences d'un objet classé, regroupé avec les autres par apgcode.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - La nature de l'objet.
+    /// * `shape` - Sa forme canonique, telle que renvoyée par `classify_object`.
+    /// * `count` - Le nombre d'occurrences à ajouter.
+    pub fn record(&mut self, kind: ObjectKind, shape: Shape, count: usize) {
+        let code = apgcode(kind, &shape);
+        match self.entries.iter_mut().find(|entry| entry.apgcode == code) {
+            Some(entry) => entry.count += count,
+            None => self.entries.push(CensusEntry {
+                kind,
+                apgcode: code,
+                shape,
+                count,
+            }),
+        }
+        self.entries.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then(a.kind.cmp(&b.kind))
+                .then(a.apgcode.len().cmp(&b.apgcode.len()))
+                .then(a.apgcode.cmp(&b.apgcode))
+        });
+    }
+
+    /// Ajoute au recensement tous les objets d'un autre recensement.
+    pub fn merge(&mut self, other: &Census) {
+        for entry in &other.entries {
+            self.record(entry.kind, entry.shape.clone(), entry.count);
+        }
+    }
+}
+
+impl fmt::Display for Census {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            writeln!(f, "{:>6}  {:<24} {}", entry.count, entry.apgcode, entry.kind)?;
+        }
+        Ok(())
+    }
+}
+
+/// Sépare les cellules vivantes en objets connexes.
+///
+/// Deux cellules vivantes appartiennent au même objet si leur distance de Tchebychev est au plus
+/// `merge_distance`, en tenant compte de la topologie. Les coordonnées de chaque objet sont
+/// dépliées à partir de sa première cellule, si bien qu'un objet à cheval sur un bord recollé reste
+/// d'un seul tenant ; elles peuvent donc être négatives ou dépasser la grille.
+///
+/// # Arguments
+///
+/// * `current_cells` - Vecteur contenant l'état actuel de chaque cellule de la grille.
+/// * `rows` - Nombre de lignes de la grille.
+/// * `cols` - Nombre de colonnes de la grille.
+/// * `topology` - La topologie de la grille.
+/// * `merge_distance` - Distance maximale entre deux cellules d'un même objet.
+pub fn grid_components(
+    current_cells: &[u8],
+    rows: usize,
+    cols: usize,
+    topology: impl Into<Topology>,
+    merge_distance: usize,
+) -> Vec<Vec<(isize, isize)>> {
+    let topology = topology.into();
+    let d = merge_distance.max(1) as isize;
+    let mut visited = vec![false; rows * cols];
+    let mut components = Vec::new();
+
+    for start in 0..rows * cols {
+        if visited[start] || current_cells[start] == 0 {
+            continue;
+        }
+        visited[start] = true;
+        let mut component = Vec::new();
+        let mut queue = VecDeque::from([((start / cols) as isize, (start % cols) as isize)]);
+        while let Some((row, col)) = queue.pop_front() {
+            component.push((row, col));
+            for di in -d..=d {
+                for dj in -d..=d {
+                    let (i, j) = (row + di, col + dj);
+                    let Some((wrapped_row, wrapped_col)) = topology.wrap(i, j, rows, cols) else {
+                        continue;
+                    };
+                    let index = grid_index(wrapped_row, wrapped_col, cols);
+                    if !visited[index] && current_cells[index] >= 1 {
+                        visited[index] = true;
+                        // Les cellules atteintes en traversant un bord recollé gardent des
+                        // coordonnées dépliées, sauf sur les bords tordus où elles sont ramenées
+                        let unfolded = if matches!(topology, Topology::Torus | Topology::Cylinder) {
+                            (i, j)
+                        } else {
+                            (wrapped_row as isize, wrapped_col as isize)
+                        };
+                        queue.push_back(unfolded);
+                    }
+                }
+            }
+        }
+        components.push(component);
+    }
+    components
+}
+
+/// Translate des cellules pour que leur coin supérieur gauche soit en `(0, 0)`, puis les trie.
+fn normalize(cells: &[(isize, isize)]) -> Shape {
+    let min_row = cells.iter().map(|&(row, _)| row).min().unwrap_or(0);
+    let min_col = cells.iter().map(|&(_, col)| col).min().unwrap_or(0);
+    let mut shape: Shape = cells
+        .iter()
+        .map(|&(row, col)| ((row - min_row) as usize, (col - min_col) as usize))
+        .collect();
+    shape.sort_unstable();
+    shape
+}
+
+/// Symétrie du carré appliquée à une cellule `(ligne, colonne)`.
+type Transform = fn(isize, isize) -> (isize, isize);
+
+/// Renvoie les 8 images d'une forme par les rotations et réflexions du carré, normalisées.
+pub fn shape_symmetries(shape: &[(usize, usize)]) -> Vec<Shape> {
+    let transforms: [Transform; 8] = [
+        |r, c| (r, c),
+        |r, c| (c, -r),
+        |r, c| (-r, -c),
+        |r, c| (-c, r),
+        |r, c| (r, -c),
+        |r, c| (-r, c),
+        |r, c| (c, r),
+        |r, c| (-c, -r),
+    ];
+    transforms
+        .iter()
+        .map(|transform| {
+            let cells: Vec<(isize, isize)> = shape
+                .iter()
+                .map(|&(row, col)| transform(row as isize, col as isize))
+                .collect();
+            normalize(&cells)
+        })
+        .collect()
+}
+
+/// Renvoie la forme canonique d'un ensemble de cellules : la plus petite, dans l'ordre
+/// lexicographique, de ses images par les 8 symétries du carré.
+///
+/// # Exemple
+///
+/// ```
+/// use crate::lifers::canonical_shape;
+///
+/// // Un clignotant horizontal et un clignotant vertical ont la même forme canonique
+/// let horizontal = canonical_shape(&[(5, 1), (5, 2), (5, 3)]);
+/// let vertical = canonical_shape(&[(0, 7), (1, 7), (2, 7)]);
+/// assert_eq!(horizontal, vertical);
+/// ```
+pub fn canonical_shape(cells: &[(isize, isize)]) -> Shape {
+    shape_symmetries(&normalize(cells)).into_iter().min().unwrap_or_default()
+}
+
+/// Fait évoluer un objet isolé et détermine sa nature et sa forme canonique.
+///
+/// La forme canonique est l'orientation et la phase dont le code de Wechsler est le plus court,
+/// puis le plus petit (voir `wechsler_canonical`), si bien que `apgcode` donne l'apgcode de l'objet.
+///
+/// # Arguments
+///
+/// * `cells` - Les cellules vivantes de l'objet, dans des coordonnées quelconques.
+/// * `rule` - La règle qui fait évoluer l'objet.
+/// * `max_period` - La période maximale recherchée.
+pub fn classify_object(cells: &[(isize, isize)], rule: &LifeRule, max_period: usize) -> (ObjectKind, Shape) {
+    let initial = normalize(cells);
+    let height = initial.iter().map(|&(row, _)| row + 1).max().unwrap_or(0);
+    let width = initial.iter().map(|&(_, col)| col + 1).max().unwrap_or(0);
+    // Marge suffisante pour qu'un vaisseau ne touche pas les bords pendant `max_period` générations
+    let margin = max_period + 2;
+    let (rows, cols) = (height + 2 * margin, width + 2 * margin);
+    let mut current = vec![0u8; rows * cols];
+    let mut next = vec![0u8; rows * cols];
+    for &(row, col) in &initial {
+        current[grid_index(row + margin, col + margin, cols)] = 1;
+    }
+
+    let mut phases = vec![initial.clone()];
+    for period in 1..=max_period {
+        grid_update_rule(&mut current, &mut next, rows, cols, Topology::Plane, rule);
+        let live: Vec<(isize, isize)> = (0..rows * cols)
+            .filter(|&index| current[index] >= 1)
+            .map(|index| ((index / cols) as isize, (index % cols) as isize))
+            .collect();
+        if live.is_empty() {
+            break;
+        }
+        if normalize(&live) == initial {
+            let dy = (live.iter().map(|&(row, _)| row).min().unwrap() - margin as isize).unsigned_abs();
+            let dx = (live.iter().map(|&(_, col)| col).min().unwrap() - margin as isize).unsigned_abs();
+            let kind = match (period, dx.max(dy), dx.min(dy)) {
+                (_, 0, _) if period == 1 => ObjectKind::StillLife,
+                (_, 0, _) => ObjectKind::Oscillator { period },
+                (_, dx, dy) => ObjectKind::Spaceship { period, dx, dy },
+            };
+            return (kind, wechsler_canonical_phases(&phases).1);
+        }
+        phases.push(normalize(&live));
+    }
+    (ObjectKind::Unclassified, wechsler_canonical_phases(&[initial]).1)
+}
+
+/// Recense les objets d'une grille stabilisée, à la manière du recensement d'apgsearch.
+///
+/// Les cellules vivantes sont séparées en objets connexes (voir `grid_components`), chaque objet
+/// est classé en le faisant évoluer isolément (voir `classify_object`), puis les objets sont
+/// regroupés par apgcode, c'est-à-dire à une symétrie et une phase près.
+///
+/// # Arguments
+///
+/// * `current_cells` - Vecteur contenant l'état actuel de chaque cellule de la grille.
+/// * `rows` - Nombre de lignes de la grille.
+/// * `cols` - Nombre de colonnes de la grille.
+/// * `topology` - La topologie de la grille.
+/// * `options` - Les paramètres du recensement.
+pub fn grid_census(
+    current_cells: &[u8],
+    rows: usize,
+    cols: usize,
+    topology: impl Into<Topology>,
+    options: &CensusOptions,
+) -> Census {
+    let mut census = Census::default();
+    for component in grid_components(current_cells, rows, cols, topology, options.merge_distance) {
+        let (kind, shape) = classify_object(&component, &options.rule, options.max_period);
+        census.record(kind, shape, 1);
+    }
+    census
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn place(cells: &mut [u8], cols: usize, origin: (usize, usize), shape: &[(usize, usize)]) {
+        for &(row, col) in shape {
+            cells[grid_index(origin.0 + row, origin.1 + col, cols)] = 1;
+        }
+    }
+
+    #[test]
+    fn test_census_classifies_common_objects() {
+        let (rows, cols) = (30, 30);
+        let mut cells = vec![0; rows * cols];
+        let block = [(0, 0), (0, 1), (1, 0), (1, 1)];
+        place(&mut cells, cols, (2, 2), &block);
+        place(&mut cells, cols, (2, 20), &block);
+        place(&mut cells, cols, (10, 10), &[(0, 0), (1, 0), (2, 0)]);
+        place(&mut cells, cols, (20, 20), &[(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]);
+        let census = grid_census(&cells, rows, cols, false, &CensusOptions::default());
+
+        assert_eq!(census.total(), 4);
+        assert_eq!(census.entries[0].kind, ObjectKind::StillLife);
+        assert_eq!(census.entries[0].apgcode, "xs4_33");
+        assert_eq!(census.entries[0].count, 2);
+        assert_eq!(census.count(|kind| kind == ObjectKind::Oscillator { period: 2 }), 1);
+        assert_eq!(census.count(|kind| kind == ObjectKind::Spaceship { period: 4, dx: 1, dy: 1 }), 1);
+    }
+
+    #[test]
+    fn test_glider_phases_share_one_entry() {
+        // Deux planeurs dans des phases et des orientations différentes
+        let (rows, cols) = (20, 20);
+        let mut cells = vec![0; rows * cols];
+        place(&mut cells, cols, (2, 2), &[(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]);
+        place(&mut cells, cols, (12, 12), &[(0, 0), (0, 2), (1, 1), (1, 2), (2, 1)]);
+        let census = grid_census(&cells, rows, cols, false, &CensusOptions::default());
+        assert_eq!(census.entries.len(), 1);
+        assert_eq!(census.entries[0].apgcode, "xq4_153");
+        assert_eq!(census.entries[0].count, 2);
+    }
+
+    #[test]
+    fn test_merge_distance_and_toric_seam() {
+        // Deux blocs à deux cellules d'écart forment un seul objet avec une distance de fusion de 2
+        let (rows, cols) = (10, 10);
+        let mut cells = vec![0; rows * cols];
+        let block = [(0, 0), (0, 1), (1, 0), (1, 1)];
+        place(&mut cells, cols, (4, 1), &block);
+        place(&mut cells, cols, (4, 4), &block);
+        assert_eq!(grid_components(&cells, rows, cols, false, 1).len(), 2);
+        assert_eq!(grid_components(&cells, rows, cols, false, 2).len(), 1);
+
+        // Un bloc à cheval sur le bord d'un tore reste un seul bloc
+        let mut cells = vec![0; rows * cols];
+        place(&mut cells, cols, (0, 0), &[(0, 0), (1, 0), (0, 9), (1, 9)]);
+        let census = grid_census(&cells, rows, cols, true, &CensusOptions::default());
+        assert_eq!(census.entries.len(), 1);
+        assert_eq!(census.entries[0].shape, vec![(0, 0), (0, 1), (1, 0), (1, 1)]);
+    }
+}
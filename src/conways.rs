@@ -1,4 +1,8 @@
 use crate::life::*;
+use crate::{
+    Anchor, CellStyle, grid_apply_stochastic, grid_resize, grid_birth_color, ColorMode, grid_predecessor, grid_update_isotropic, grid_update_ltl, grid_update_table,
+    LifeRule, Neighborhood, PredecessorOptions, PredecessorResult, Rule, Stochastic, Topology, UpdateScheme,
+};
 use ggez::{graphics::Color, graphics::Canvas, Context, GameResult};
 use rand::prelude::*;
 use std::fmt;
@@ -14,10 +18,24 @@ pub struct ConwaysGrid {
     rows: usize,
     /// Nombre de colonnes de la grille.
     cols: usize,
-    /// Indique si les bords de la grille sont connectés, formant une grille torique. Si vrai, les bords gauche et droit ainsi que les bords supérieur et inférieur sont connectés.
-    toricgrid: bool,
+    /// Topologie de la grille, qui indique comment ses bords sont recollés.
+    topology: Topology,
+    /// Règle appliquée à chaque mise à jour de la grille.
+    rule: Rule,
+    /// Probabilités de naissance, de survie et de bruit appliquées après la règle.
+    stochastic: Stochastic,
+    /// Ordre de mise à jour des cellules.
+    scheme: UpdateScheme,
+    /// Mode de couleur des cellules vivantes.
+    color_mode: ColorMode,
+    /// Générateur aléatoire des mises à jour stochastiques et asynchrones.
+    rng: StdRng,
     color_alive: Option<Color>, 
     color_not_alive: Option<Color>,
+    /// Couleurs des états à partir de 2, données par `Grid::set_state_colors`.
+    state_colors: Vec<Option<Color>>,
+    /// Style des cellules dessinées.
+    cell_style: CellStyle,
 }
 
 // Implémentation d'une méthode pour afficher la grille
@@ -27,6 +45,252 @@ impl fmt::Display for ConwaysGrid {
     }
 }
 
+impl ConwaysGrid {
+    /// Renvoie la couleur d'affichage de chaque état de la grille.
+    ///
+    /// Les couleurs viennent de la section `@COLORS` d'une règle à table, les états sans couleur
+    /// reprenant celles des cellules vivantes et mortes, ou sinon du mode de couleur. Les couleurs
+    /// des états à partir de 2 données par `Grid::set_state_colors` les remplacent.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use ggez::graphics::Color;
+    /// use crate::lifers::{ColorMode, ConwaysGrid, Grid};
+    ///
+    /// let mut grid = ConwaysGrid::new(5, 5, true);
+    /// grid.set_color_mode(ColorMode::Immigration);
+    /// grid.set_state_colors(&[None, Some(Color::WHITE), Some(Color::RED)]);
+    /// assert_eq!(grid.state_palette(), vec![None, Some(Color::WHITE), Some(Color::RED)]);
+    /// ```
+    pub fn state_palette(&self) -> Vec<Option<Color>> {
+        let mut palette = match &self.rule {
+            // Les états sans couleur dans `@COLORS` reprennent les couleurs de la grille
+            Rule::Table(rule) => rule
+                .palette()
+                .into_iter()
+                .enumerate()
+                .map(|(state, color)| color.or(if state == 0 { self.color_not_alive } else { self.color_alive }))
+                .collect(),
+            _ => self.color_mode.palette(self.color_alive, self.color_not_alive),
+        };
+        for (color, &state_color) in palette.iter_mut().skip(2).zip(&self.state_colors) {
+            *color = state_color;
+        }
+        palette
+    }
+
+    /// Renvoie la règle appliquée par la grille.
+    pub fn rule(&self) -> &Rule {
+        &self.rule
+    }
+
+    /// Modifie la règle appliquée par la grille.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::{ConwaysGrid, Grid, LifeRule, LtlRule};
+    ///
+    /// let mut grid = ConwaysGrid::new(5, 5, true);
+    /// grid.set_rule(LifeRule::parse("B2/S34H").unwrap());
+    /// assert_eq!(grid.rule().to_string(), "B2/S34H");
+    ///
+    /// grid.set_rule(LtlRule::bosco());
+    /// assert_eq!(grid.rule().to_string(), "R5,C0,M1,S34..58,B34..45,NM");
+    /// ```
+    pub fn set_rule(&mut self, rule: impl Into<Rule>) {
+        self.rule = rule.into();
+    }
+
+    /// Renvoie la topologie de la grille.
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    /// Modifie la topologie de la grille.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::{ConwaysGrid, Grid, Topology};
+    ///
+    /// let mut grid = ConwaysGrid::new(4, 4, true);
+    /// grid.set_topology(Topology::KleinBottleVertical);
+    /// assert!(!grid.is_toricgrid());
+    /// ```
+    pub fn set_topology(&mut self, topology: Topology) {
+        self.topology = topology;
+    }
+
+    /// Renvoie les paramètres de la mise à jour stochastique.
+    pub fn stochastic(&self) -> Stochastic {
+        self.stochastic
+    }
+
+    /// Modifie les probabilités de naissance, de survie et de bruit appliquées après la règle.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::{ConwaysGrid, Grid, Stochastic};
+    ///
+    /// let mut grid = ConwaysGrid::new(3, 3, false);
+    /// grid.set_seed(42);
+    /// grid.set_stochastic(Stochastic { birth: 0.0, ..Stochastic::default() });
+    /// for col in 0..3 {
+    ///     grid.set_cell_state(1, col, 1);
+    /// }
+    /// // Aucune cellule ne peut naître : seule la cellule centrale du clignotant survit
+    /// grid.update();
+    /// assert_eq!(grid.current_cells(), &vec![0, 0, 0, 0, 1, 0, 0, 0, 0]);
+    /// ```
+    pub fn set_stochastic(&mut self, stochastic: Stochastic) {
+        self.stochastic = stochastic;
+    }
+
+    /// Réinitialise le générateur aléatoire avec une graine, pour des mises à jour stochastiques reproductibles.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    /// Renvoie l'ordre de mise à jour des cellules.
+    pub fn update_scheme(&self) -> UpdateScheme {
+        self.scheme
+    }
+
+    /// Modifie l'ordre de mise à jour des cellules ; les schémas aléatoires utilisent la graine
+    /// donnée à `set_seed`.
+    ///
+    /// La probabilité de `RandomIndependent` est ramenée entre 0 et 1, une probabilité invalide
+    /// (`NaN`) valant 0.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::{ConwaysGrid, Grid, UpdateScheme};
+    ///
+    /// let mut grid = ConwaysGrid::new(3, 3, false);
+    /// grid.set_update_scheme(UpdateScheme::FixedSweep);
+    /// for col in 0..3 {
+    ///     grid.set_cell_state(1, col, 1);
+    /// }
+    /// // Les cellules déjà mises à jour comptent : (0, 2) naît grâce à (0, 1), puis (1, 1) meurt étouffée
+    /// grid.update();
+    /// assert_eq!(grid.current_cells(), &vec![0, 1, 1, 1, 0, 1, 0, 0, 0]);
+    /// ```
+    pub fn set_update_scheme(&mut self, scheme: UpdateScheme) {
+        self.scheme = match scheme {
+            UpdateScheme::RandomIndependent(probability) if probability.is_nan() => UpdateScheme::RandomIndependent(0.0),
+            UpdateScheme::RandomIndependent(probability) => UpdateScheme::RandomIndependent(probability.clamp(0.0, 1.0)),
+            scheme => scheme,
+        };
+    }
+
+    /// Renvoie le mode de couleur des cellules vivantes.
+    pub fn color_mode(&self) -> ColorMode {
+        self.color_mode
+    }
+
+    /// Modifie le mode de couleur des cellules vivantes.
+    ///
+    /// Les couleurs ne s'appliquent qu'aux règles `B/S` : les autres familles de règles utilisent
+    /// la valeur des cellules pour leurs propres états.
+    pub fn set_color_mode(&mut self, color_mode: ColorMode) {
+        self.color_mode = color_mode;
+    }
+
+    /// Cherche une génération précédente de la région de `rows` × `cols` cellules dont le coin
+    /// supérieur gauche est en (`row`, `col`) (voir `grid_predecessor`).
+    ///
+    /// Renvoie `None` si la règle de la grille n'est pas une règle `B/S` ou si la région dépasse
+    /// de la grille.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::{ConwaysGrid, Grid, PredecessorOptions, PredecessorResult};
+    ///
+    /// let mut grid = ConwaysGrid::new(6, 6, false);
+    /// for col in 1..4 {
+    ///     grid.set_cell_state(2, col, 1);
+    /// }
+    /// let result = grid.find_predecessor(1, 1, 3, 3, &PredecessorOptions::default());
+    /// let Some(PredecessorResult::Found(mut predecessor)) = result else { panic!() };
+    /// predecessor.update();
+    /// // La région apparaît au centre de la boîte, décalée de la marge
+    /// assert!(predecessor.is_alive(2, 1) && predecessor.is_alive(2, 2) && predecessor.is_alive(2, 3));
+    /// ```
+    pub fn find_predecessor(
+        &self,
+        row: usize,
+        col: usize,
+        rows: usize,
+        cols: usize,
+        options: &PredecessorOptions,
+    ) -> Option<PredecessorResult> {
+        let Rule::Life(rule) = &self.rule else {
+            return None;
+        };
+        if row + rows > self.rows || col + cols > self.cols {
+            return None;
+        }
+        let target: Vec<u8> = (row..row + rows)
+            .flat_map(|i| (col..col + cols).map(move |j| (i, j)))
+            .map(|(i, j)| self.current_cells[grid_index(i, j, self.cols)])
+            .collect();
+        Some(grid_predecessor(&target, rows, cols, rule, options))
+    }
+
+    /// Indique si les naissances doivent recevoir une couleur.
+    fn colors_births(&self) -> bool {
+        self.color_mode != ColorMode::Single && matches!(self.rule, Rule::Life(_))
+    }
+
+    /// Renvoie la couleur d'une cellule qui naît d'après ses voisines dans `cells`.
+    fn birth_color(&self, row: usize, col: usize, cells: &[u8]) -> u8 {
+        grid_birth_color(row, col, cells, self.rows, self.cols, self.topology, &self.rule.neighborhood(), self.color_mode)
+    }
+
+    /// Calcule toutes les cellules à partir de la génération précédente, qui reste dans `next_cells`.
+    fn update_synchronous(&mut self) {
+        match &self.rule {
+            Rule::Life(rule) => grid_update_rule(
+                &mut self.current_cells,
+                &mut self.next_cells,
+                self.rows,
+                self.cols,
+                self.topology,
+                rule,
+            ),
+            Rule::LargerThanLife(rule) => grid_update_ltl(
+                &mut self.current_cells,
+                &mut self.next_cells,
+                self.rows,
+                self.cols,
+                self.topology,
+                rule,
+            ),
+            Rule::Isotropic(rule) => grid_update_isotropic(
+                &mut self.current_cells,
+                &mut self.next_cells,
+                self.rows,
+                self.cols,
+                self.topology,
+                rule,
+            ),
+            Rule::Table(rule) => grid_update_table(
+                &mut self.current_cells,
+                &mut self.next_cells,
+                self.rows,
+                self.cols,
+                self.topology,
+                rule,
+            ),
+        }
+    }
+}
+
 impl Grid for ConwaysGrid {
     fn display(&self) {
         println!("{}", self);
@@ -37,26 +301,38 @@ impl Grid for ConwaysGrid {
     ///
     /// * `rows` - Le nombre de lignes de la grille.
     /// * `cols` - Le nombre de colonnes de la grille.
-    /// * `toricgrid` - Indique si les bords de la grille sont connectés, formant une grille torique.
+    /// * `topology` - La topologie de la grille.
     ///
     /// # Exemple
     ///
     /// ```
     /// use crate::lifers::ConwaysGrid;
     /// use crate::lifers::Grid;
+    /// use crate::lifers::Topology;
     ///
     /// // Crée une nouvelle grille 3x3 avec des cellules mortes
     /// let grid = ConwaysGrid::new(3, 3, true);
+    ///
+    /// // Toute autre topologie peut être choisie dès la création
+    /// let grid = ConwaysGrid::new(3, 3, Topology::Mirror);
+    /// assert_eq!(grid.topology(), Topology::Mirror);
     /// ```
-    fn new(rows: usize, cols: usize, toricgrid: bool) -> ConwaysGrid {
+    fn new(rows: usize, cols: usize, topology: impl Into<Topology>) -> ConwaysGrid {
         ConwaysGrid {
             current_cells: vec![0; rows * cols],
             next_cells: vec![0; rows * cols],
             rows,
             cols,
-            toricgrid: toricgrid,
+            topology: topology.into(),
+            rule: Rule::default(),
+            stochastic: Stochastic::default(),
+            scheme: UpdateScheme::default(),
+            color_mode: ColorMode::default(),
+            rng: StdRng::from_entropy(),
             color_alive: Some(Color::from_rgb(0, 0, 0)),
             color_not_alive: Some(Color::from_rgb(204, 204, 204)),
+            state_colors: Vec::new(),
+            cell_style: CellStyle::default(),
         }
     }
 
@@ -66,7 +342,7 @@ impl Grid for ConwaysGrid {
     ///
     /// * `rows` - Le nombre de lignes de la grille.
     /// * `cols` - Le nombre de colonnes de la grille.
-    /// * `toricgrid` - Le nombre de colonnes de la grille.
+    /// * `topology` - La topologie de la grille.
     ///
     /// # Exemple
     ///
@@ -77,7 +353,7 @@ impl Grid for ConwaysGrid {
     /// // Crée une nouvelle grille 3x3 avec des cellules dont l'état est aléatoire
     /// let grid = ConwaysGrid::new_random(3, 3, true);
     /// ```
-    fn new_random(rows: usize, cols: usize, toricgrid: bool) -> ConwaysGrid {
+    fn new_random(rows: usize, cols: usize, topology: impl Into<Topology>) -> ConwaysGrid {
         let mut rng: ThreadRng = rand::thread_rng();
         let mut current_cells: Vec<u8> = Vec::with_capacity(rows * cols);
         for _ in 0..(rows * cols) {
@@ -90,20 +366,27 @@ impl Grid for ConwaysGrid {
             next_cells: vec![0; rows * cols],
             rows,
             cols,
-            toricgrid: toricgrid,
+            topology: topology.into(),
+            rule: Rule::default(),
+            stochastic: Stochastic::default(),
+            scheme: UpdateScheme::default(),
+            color_mode: ColorMode::default(),
+            rng: StdRng::from_entropy(),
             color_alive: Some(Color::from_rgb(0, 0, 0)),
             color_not_alive: Some(Color::from_rgb(204, 204, 204)),
+            state_colors: Vec::new(),
+            cell_style: CellStyle::default(),
         }
     }
 
-    /// Crée une nouvelle grille à partir d'un vecteur de cellules donné, avec les dimensions spécifiées et une option pour une grille torique.
+    /// Crée une nouvelle grille à partir d'un vecteur de cellules donné, avec les dimensions et la topologie spécifiées.
     ///
     /// # Arguments
     ///
     /// * `cels` - Le vecteur de cellules à utiliser pour initialiser la grille. Chaque valeur du vecteur représente l'état d'une cellule.
     /// * `rows` - Le nombre de lignes de la grille.
     /// * `cols` - Le nombre de colonnes de la grille.
-    /// * `toricgrid` - Un booléen indiquant si les bords de la grille sont connectés, formant une grille torique.
+    /// * `topology` - La topologie de la grille.
     ///
     /// # Panics
     ///
@@ -118,7 +401,7 @@ impl Grid for ConwaysGrid {
     /// // Crée une nouvelle grille à partir d'un vecteur de cellules avec des dimensions 3x3
     /// let grid = ConwaysGrid::from_vect(vec![0, 1, 0, 0, 0, 1, 1, 1, 0], 3, 3, true);
     /// ```
-    fn from_vect(cels: Vec<u8>, rows: usize, cols: usize, toricgrid: bool) -> Self {
+    fn from_vect(cels: Vec<u8>, rows: usize, cols: usize, topology: impl Into<Topology>) -> Self {
         // Vérifie si la longueur du vecteur correspond au nombre total de cellules dans la grille
         assert_eq!(cels.len(), rows * cols);
 
@@ -127,9 +410,16 @@ impl Grid for ConwaysGrid {
             next_cells: vec![0; rows * cols],
             rows,
             cols,
-            toricgrid,
+            topology: topology.into(),
+            rule: Rule::default(),
+            stochastic: Stochastic::default(),
+            scheme: UpdateScheme::default(),
+            color_mode: ColorMode::default(),
+            rng: StdRng::from_entropy(),
             color_alive: Some(Color::from_rgb(0, 0, 0)), // Noir pour les cellules vivantes par défaut
             color_not_alive: Some(Color::from_rgb(204, 204, 204)), // Gris clair pour les cellules mortes par défaut
+            state_colors: Vec::new(),
+            cell_style: CellStyle::default(),
         }
     }
 
@@ -142,6 +432,24 @@ impl Grid for ConwaysGrid {
     fn set_color_not_alive(&mut self, color: Option<Color>) {
         self.color_not_alive = color;
     }
+
+    fn set_state_colors(&mut self, palette: &[Option<Color>]) {
+        if let Some(&color) = palette.first() {
+            self.color_not_alive = color;
+        }
+        if let Some(&color) = palette.get(1) {
+            self.color_alive = color;
+        }
+        self.state_colors = palette.iter().skip(2).copied().collect();
+    }
+
+    fn cell_style(&self) -> CellStyle {
+        self.cell_style
+    }
+
+    fn set_cell_style(&mut self, style: CellStyle) {
+        self.cell_style = style;
+    }
     /// Renvoie le nombre de lignes de la grille.
     fn rows(&self) -> usize {
         self.rows
@@ -152,9 +460,27 @@ impl Grid for ConwaysGrid {
         self.cols
     }
 
+    /// Redimensionne la grille en conservant ses cellules.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::{Anchor, ConwaysGrid, Grid};
+    ///
+    /// let mut grid = ConwaysGrid::from_vect(vec![1, 0, 0, 1], 2, 2, true);
+    /// grid.resize(3, 3, Anchor::BottomRight);
+    /// assert_eq!(grid.current_cells(), &vec![0, 0, 0, 0, 1, 0, 0, 0, 1]);
+    /// ```
+    fn resize(&mut self, rows: usize, cols: usize, anchor: Anchor) {
+        self.current_cells = grid_resize(&self.current_cells, self.rows, self.cols, rows, cols, anchor);
+        self.next_cells = vec![0; rows * cols];
+        self.rows = rows;
+        self.cols = cols;
+    }
+
     /// Indique si les bords de la grille sont connectés, formant une grille torique.
     fn is_toricgrid(&self) -> bool {
-        self.toricgrid
+        self.topology == Topology::Torus
     }
 
     fn current_cells(&self) -> &Vec<u8> {
@@ -305,17 +631,18 @@ impl Grid for ConwaysGrid {
     ///
     /// ```
     fn count_neighbors(&self, row: usize, col: usize) -> usize {
-        grid_count_neighbors(
+        grid_count_neighbors_in(
             row,
             col,
             &self.current_cells,
             self.rows,
             self.cols,
-            self.toricgrid,
+            self.topology,
+            &self.rule.neighborhood(),
         )
     }
 
-    /// Met à jour l'état de la grille selon les règles du jeu de la vie.
+    /// Met à jour l'état de la grille selon sa règle (par défaut celle du jeu de la vie).
     ///
     /// Cette méthode parcourt chaque cellule de la grille, compte ses voisins vivants et applique les règles du jeu pour mettre à jour son état.
     ///
@@ -331,13 +658,71 @@ impl Grid for ConwaysGrid {
     /// grid.update();
     /// ```
     fn update(&mut self) {
-        grid_update(
-            &mut self.current_cells,
-            &mut self.next_cells,
-            self.rows,
-            self.cols,
-            self.toricgrid,
-        );
+        match self.scheme {
+            UpdateScheme::Synchronous => self.update_synchronous(),
+            UpdateScheme::RandomIndependent(probability) => {
+                self.update_synchronous();
+                // Les cellules non tirées reprennent leur état précédent
+                for (previous, current) in self.next_cells.iter().zip(self.current_cells.iter_mut()) {
+                    if !self.rng.gen_bool(probability) {
+                        *current = *previous;
+                    }
+                }
+            }
+            UpdateScheme::FixedSweep | UpdateScheme::RandomSequential => {
+                self.next_cells.copy_from_slice(&self.current_cells);
+                let mut order: Vec<usize> = (0..self.rows * self.cols).collect();
+                if self.scheme == UpdateScheme::RandomSequential {
+                    order.shuffle(&mut self.rng);
+                }
+                let colors_births = self.colors_births();
+                for index in order {
+                    let (row, col) = (index / self.cols, index % self.cols);
+                    let mut next =
+                        self.rule.next_cell_state(row, col, &self.current_cells, self.rows, self.cols, self.topology);
+                    if colors_births && next >= 1 && self.current_cells[index] == 0 {
+                        next = self.birth_color(row, col, &self.current_cells);
+                    }
+                    self.current_cells[index] = next;
+                }
+            }
+        }
+        if self.colors_births() && matches!(self.scheme, UpdateScheme::Synchronous | UpdateScheme::RandomIndependent(_)) {
+            // Les parentes des naissances sont dans la génération précédente, restée dans `next_cells`
+            for index in 0..self.current_cells.len() {
+                if self.current_cells[index] >= 1 && self.next_cells[index] == 0 {
+                    let (row, col) = (index / self.cols, index % self.cols);
+                    self.current_cells[index] = self.birth_color(row, col, &self.next_cells);
+                }
+            }
+        }
+        if !self.stochastic.is_deterministic() {
+            // Après l'échange, `next_cells` contient la génération précédente
+            grid_apply_stochastic(&self.next_cells, &mut self.current_cells, &self.stochastic, &mut self.rng);
+        }
+    }
+
+    fn noise(&self) -> f64 {
+        self.stochastic.noise
+    }
+
+    fn colors(&self) -> u8 {
+        self.color_mode.colors()
+    }
+
+    fn life_rule(&self) -> Option<LifeRule> {
+        match &self.rule {
+            Rule::Life(rule) => Some(rule.clone()),
+            _ => None,
+        }
+    }
+
+    fn is_hexagonal(&self) -> bool {
+        self.rule.neighborhood() == Neighborhood::Hexagonal
+    }
+
+    fn set_noise(&mut self, noise: f64) {
+        self.stochastic.noise = noise.clamp(0.0, 1.0);
     }
     /// Dessine la grille en utilisant le contexte `ctx` spécifié et la taille de cellule `cell_size`.
     ///
@@ -364,6 +749,17 @@ impl Grid for ConwaysGrid {
     ///
     /// Cette méthode peut être utilisée pour dessiner une grille de jeu dans une fenêtre `ggez`.
     fn draw(&self, ctx: &mut Context, canvas: &mut Canvas, cell_size: f32) -> GameResult {
+        let hexagonal = self.is_hexagonal();
+        if matches!(self.rule, Rule::Table(_)) || self.color_mode != ColorMode::Single {
+            let palette = self.state_palette();
+            if hexagonal {
+                return draw_hex_grid_palette(ctx, canvas, self, cell_size, &palette);
+            }
+            return draw_grid_palette(ctx, canvas, self, cell_size, &palette);
+        }
+        if hexagonal {
+            return draw_hex_grid(ctx, canvas, self, cell_size, self.color_alive, self.color_not_alive);
+        }
         draw_grid(ctx, canvas, self, cell_size,  self.color_alive, self.color_not_alive)
     }
 }
@@ -401,4 +797,27 @@ mod tests {
         // Vérification des voisins de la cellule en bas à droite
         assert_eq!(grid.count_neighbors(2, 2), 1);
     }
+
+    #[test]
+    fn test_update_blinker_oscillates() {
+        let mut grid: ConwaysGrid = ConwaysGrid::new(5, 5, false);
+        for col in 1..4 {
+            grid.set_cell_state(2, col, 1);
+        }
+        grid.update();
+        assert!(grid.is_alive(1, 2) && grid.is_alive(2, 2) && grid.is_alive(3, 2));
+        assert!(!grid.is_alive(2, 1) && !grid.is_alive(2, 3));
+        grid.update();
+        assert!(grid.is_alive(2, 1) && grid.is_alive(2, 2) && grid.is_alive(2, 3));
+    }
+
+    #[test]
+    fn test_update_uses_rule_neighborhood() {
+        let mut grid: ConwaysGrid = ConwaysGrid::new(3, 3, false);
+        grid.set_rule(Rule::parse("B1/S1V").unwrap());
+        grid.set_cell_state(1, 1, 1);
+        assert_eq!(grid.count_neighbors(0, 0), 0);
+        grid.update();
+        assert_eq!(grid.current_cells(), &vec![0, 1, 0, 1, 0, 1, 0, 1, 0]);
+    }
 }
@@ -0,0 +1,407 @@
+use crate::life::*;
+use crate::{grid_resize, Anchor, CellStyle, Topology};
+use ggez::{graphics::Color, graphics::Canvas, Context, GameResult};
+use rand::prelude::*;
+use std::fmt;
+
+/// Règle d'un automate cellulaire unidimensionnel de rayon 1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ElementaryRule {
+    /// Automate élémentaire de Wolfram à deux états, numéroté de 0 à 255.
+    Wolfram(u8),
+    /// Automate totalistique à `colors` états : l'état suivant est le chiffre de rang
+    /// `gauche + centre + droite` du code écrit en base `colors`.
+    Totalistic { colors: u8, code: u64 },
+}
+
+impl Default for ElementaryRule {
+    fn default() -> Self {
+        ElementaryRule::Wolfram(30)
+    }
+}
+
+impl ElementaryRule {
+    /// Nombre maximal d'états d'une règle totalistique, pour que son code tienne dans un `u64`.
+    pub const MAX_COLORS: u8 = 7;
+
+    /// Crée une règle totalistique à `colors` états.
+    ///
+    /// Renvoie `None` si le nombre d'états n'est pas compris entre 2 et `MAX_COLORS` ou si le
+    /// code dépasse `colors^(3 * colors - 2) - 1`.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::ElementaryRule;
+    ///
+    /// let rule = ElementaryRule::totalistic(3, 777).unwrap();
+    /// assert_eq!(rule.colors(), 3);
+    /// assert!(ElementaryRule::totalistic(3, 3u64.pow(7)).is_none());
+    /// ```
+    pub fn totalistic(colors: u8, code: u64) -> Option<Self> {
+        if !(2..=ElementaryRule::MAX_COLORS).contains(&colors) {
+            return None;
+        }
+        let limit = (colors as u64).pow(3 * colors as u32 - 2);
+        (code < limit).then_some(ElementaryRule::Totalistic { colors, code })
+    }
+
+    /// Renvoie le nombre d'états de la règle.
+    pub fn colors(&self) -> u8 {
+        match self {
+            ElementaryRule::Wolfram(_) => 2,
+            ElementaryRule::Totalistic { colors, .. } => *colors,
+        }
+    }
+
+    /// Calcule l'état suivant d'une cellule à partir de son état et de ceux de ses deux voisines.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::ElementaryRule;
+    ///
+    /// // La règle 90 calcule le ou exclusif des deux voisines
+    /// let rule = ElementaryRule::Wolfram(90);
+    /// assert_eq!(rule.next_state(1, 1, 0), 1);
+    /// assert_eq!(rule.next_state(1, 0, 1), 0);
+    /// ```
+    pub fn next_state(&self, left: u8, center: u8, right: u8) -> u8 {
+        match *self {
+            ElementaryRule::Wolfram(number) => {
+                let pattern = ((left >= 1) as u8) << 2 | ((center >= 1) as u8) << 1 | (right >= 1) as u8;
+                (number >> pattern) & 1
+            }
+            ElementaryRule::Totalistic { colors, code } => {
+                let max = colors - 1;
+                let sum = left.min(max) as u32 + center.min(max) as u32 + right.min(max) as u32;
+                ((code / (colors as u64).pow(sum)) % colors as u64) as u8
+            }
+        }
+    }
+}
+
+impl fmt::Display for ElementaryRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ElementaryRule::Wolfram(number) => write!(f, "W{}", number),
+            ElementaryRule::Totalistic { colors, code } => write!(f, "k{}/T{}", colors, code),
+        }
+    }
+}
+
+/// Structure représentant le diagramme espace-temps d'un automate cellulaire unidimensionnel.
+///
+/// Chaque ligne de la grille est une génération de l'automate, la plus ancienne en haut. À chaque
+/// mise à jour, la génération suivante est calculée à partir de la dernière ligne remplie et
+/// écrite sur la ligne en dessous ; une fois la grille pleine, le diagramme défile vers le haut.
+#[derive(Clone)]
+pub struct ElementaryGrid {
+    /// Vecteur contenant l'état de chaque cellule du diagramme.
+    current_cells: Vec<u8>,
+    /// Nombre de lignes de la grille, c'est-à-dire de générations affichées.
+    rows: usize,
+    /// Nombre de colonnes de la grille, c'est-à-dire de cellules de l'automate.
+    cols: usize,
+    /// Topologie de l'automate, appliquée à ses bords gauche et droit comme à une grille d'une
+    /// seule ligne.
+    topology: Topology,
+    /// Nombre de lignes déjà remplies ; la génération courante est la ligne `filled - 1`.
+    filled: usize,
+    /// Règle appliquée à chaque mise à jour.
+    rule: ElementaryRule,
+    color_alive: Option<Color>,
+    color_not_alive: Option<Color>,
+    /// Style des cellules dessinées.
+    cell_style: CellStyle,
+}
+
+impl ElementaryGrid {
+    /// Renvoie la règle appliquée par l'automate.
+    pub fn rule(&self) -> ElementaryRule {
+        self.rule
+    }
+
+    /// Modifie la règle appliquée par l'automate.
+    pub fn set_rule(&mut self, rule: ElementaryRule) {
+        self.rule = rule;
+    }
+
+    /// Efface le diagramme et ne garde qu'une cellule vivante au milieu de la première ligne.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::{ElementaryGrid, ElementaryRule, Grid};
+    ///
+    /// let mut grid = ElementaryGrid::new(3, 5, false);
+    /// grid.set_rule(ElementaryRule::Wolfram(90));
+    /// grid.seed_center();
+    /// grid.update();
+    /// grid.update();
+    /// assert_eq!(grid.generation(), &[1, 0, 0, 0, 1]);
+    /// ```
+    pub fn seed_center(&mut self) {
+        self.current_cells.fill(0);
+        self.filled = 1;
+        if self.rows > 0 && self.cols > 0 {
+            self.current_cells[self.cols / 2] = 1;
+        }
+    }
+
+    /// Renvoie l'état des cellules de la génération courante, vide si le diagramme n'a aucune ligne.
+    pub fn generation(&self) -> &[u8] {
+        if self.rows == 0 {
+            return &[];
+        }
+        let start = (self.filled - 1) * self.cols;
+        &self.current_cells[start..start + self.cols]
+    }
+
+    /// Renvoie la cellule de la génération courante située à `offset` colonnes de `col`, hors de
+    /// l'automate selon sa topologie.
+    fn neighbor(&self, col: usize, offset: isize) -> u8 {
+        self.topology.state_at(self.generation(), 0, col as isize + offset, 1, self.cols)
+    }
+
+    /// Calcule la palette des états, dégradée de la couleur des cellules mortes à celle des vivantes.
+    fn palette(&self) -> Vec<Option<Color>> {
+        let (Some(from), Some(to)) = (self.color_not_alive, self.color_alive) else {
+            return vec![self.color_not_alive, self.color_alive];
+        };
+        let max = (self.rule.colors() - 1) as f32;
+        (0..self.rule.colors())
+            .map(|state| {
+                let t = state as f32 / max;
+                Some(Color::new(
+                    from.r + (to.r - from.r) * t,
+                    from.g + (to.g - from.g) * t,
+                    from.b + (to.b - from.b) * t,
+                    from.a + (to.a - from.a) * t,
+                ))
+            })
+            .collect()
+    }
+}
+
+// Implémentation d'une méthode pour afficher la grille
+impl fmt::Display for ElementaryGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        grid_fmt(f, self.rows, self.cols, &self.current_cells)
+    }
+}
+
+impl Grid for ElementaryGrid {
+    fn display(&self) {
+        println!("{}", self);
+    }
+
+    /// Initialise un diagramme vide dont la première ligne est la génération courante.
+    fn new(rows: usize, cols: usize, topology: impl Into<Topology>) -> ElementaryGrid {
+        ElementaryGrid {
+            current_cells: vec![0; rows * cols],
+            rows,
+            cols,
+            topology: topology.into(),
+            filled: 1,
+            rule: ElementaryRule::default(),
+            color_alive: Some(Color::from_rgb(0, 0, 0)),
+            color_not_alive: Some(Color::from_rgb(204, 204, 204)),
+            cell_style: CellStyle::default(),
+        }
+    }
+
+    /// Initialise un diagramme dont la première ligne est aléatoire et les suivantes vides.
+    fn new_random(rows: usize, cols: usize, topology: impl Into<Topology>) -> ElementaryGrid {
+        let mut rng: ThreadRng = rand::thread_rng();
+        let mut grid = ElementaryGrid::new(rows, cols, topology);
+        for cell in grid.current_cells.iter_mut().take(cols) {
+            *cell = rng.gen_range(0..2);
+        }
+        grid
+    }
+
+    /// Crée un diagramme à partir d'un vecteur de cellules.
+    ///
+    /// La génération courante est la dernière ligne contenant une cellule vivante, ou la première
+    /// ligne si toutes les cellules sont mortes.
+    ///
+    /// # Panics
+    ///
+    /// Cette méthode panique si la longueur du vecteur `cels` ne correspond pas à `rows * cols`.
+    fn from_vect(cels: Vec<u8>, rows: usize, cols: usize, topology: impl Into<Topology>) -> Self {
+        assert_eq!(cels.len(), rows * cols);
+        let filled = (0..rows)
+            .rev()
+            .find(|&row| cels[row * cols..(row + 1) * cols].iter().any(|&cell| cell >= 1))
+            .map_or(1, |row| row + 1);
+        ElementaryGrid {
+            current_cells: cels,
+            filled,
+            ..ElementaryGrid::new(rows, cols, topology)
+        }
+    }
+
+    /// Setter pour la couleur des cellules vivantes
+    fn set_color_alive(&mut self, color: Option<Color>) {
+        self.color_alive = color;
+    }
+
+    /// Setter pour la couleur des cellules mortes
+    fn set_color_not_alive(&mut self, color: Option<Color>) {
+        self.color_not_alive = color;
+    }
+
+    fn cell_style(&self) -> CellStyle {
+        self.cell_style
+    }
+
+    fn set_cell_style(&mut self, style: CellStyle) {
+        self.cell_style = style;
+    }
+
+    fn rows(&self) -> usize {
+        self.rows
+    }
+
+    fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Redimensionne le diagramme en conservant ses cellules.
+    ///
+    /// La génération courante suit le déplacement des lignes ; si elle sort du diagramme, elle
+    /// devient la ligne la plus proche.
+    fn resize(&mut self, rows: usize, cols: usize, anchor: Anchor) {
+        let (row_offset, _) = anchor.offset(self.rows, self.cols, rows, cols);
+        self.current_cells = grid_resize(&self.current_cells, self.rows, self.cols, rows, cols, anchor);
+        self.filled = (self.filled as isize + row_offset).clamp(1, rows.max(1) as isize) as usize;
+        self.rows = rows;
+        self.cols = cols;
+    }
+
+    fn is_toricgrid(&self) -> bool {
+        self.topology == Topology::Torus
+    }
+
+    fn current_cells(&self) -> &Vec<u8> {
+        &self.current_cells
+    }
+
+    fn set_cell_state(&mut self, row: usize, col: usize, alive: u8) {
+        let state = alive.min(self.rule.colors() - 1);
+        grid_set_cell_state(row, col, state, &mut self.current_cells, self.cols)
+    }
+
+    fn toggle_cell_state(&mut self, row: usize, col: usize) {
+        grid_toggle_cell_state(row, col, &mut self.current_cells, self.cols)
+    }
+
+    /// Fait passer une cellule à l'état suivant parmi les états de la règle.
+    fn cycle_cell_state(&mut self, row: usize, col: usize) {
+        let index = grid_index(row, col, self.cols);
+        self.current_cells[index] = (self.current_cells[index] + 1) % self.rule.colors();
+    }
+
+    fn is_alive(&self, row: usize, col: usize) -> bool {
+        grid_is_alive(row, col, &self.current_cells, self.cols)
+    }
+
+    fn index(&self, row: usize, col: usize) -> usize {
+        grid_index(row, col, self.cols)
+    }
+
+    /// Compte les voisines vivantes d'une cellule sur sa ligne (à gauche et à droite).
+    fn count_neighbors(&self, row: usize, col: usize) -> usize {
+        [-1isize, 1]
+            .iter()
+            .filter(|&&offset| {
+                let line = &self.current_cells[row * self.cols..(row + 1) * self.cols];
+                self.topology.state_at(line, 0, col as isize + offset, 1, self.cols) >= 1
+            })
+            .count()
+    }
+
+    /// Calcule la génération suivante et l'ajoute sous la génération courante, en faisant défiler
+    /// le diagramme vers le haut lorsque la grille est pleine.
+    fn update(&mut self) {
+        if self.rows == 0 || self.cols == 0 {
+            return;
+        }
+        let next: Vec<u8> = (0..self.cols)
+            .map(|col| {
+                let center = self.generation()[col];
+                self.rule.next_state(self.neighbor(col, -1), center, self.neighbor(col, 1))
+            })
+            .collect();
+        if self.filled < self.rows {
+            self.filled += 1;
+        } else {
+            self.current_cells.copy_within(self.cols.., 0);
+        }
+        let start = (self.filled - 1) * self.cols;
+        self.current_cells[start..start + self.cols].copy_from_slice(&next);
+    }
+
+    /// Dessine le diagramme, les états intermédiaires des règles totalistiques étant dégradés
+    /// entre la couleur des cellules mortes et celle des cellules vivantes.
+    fn draw(&self, ctx: &mut Context, canvas: &mut Canvas, cell_size: f32) -> GameResult {
+        if self.rule.colors() == 2 {
+            return draw_grid(ctx, canvas, self, cell_size, self.color_alive, self.color_not_alive);
+        }
+        draw_grid_palette(ctx, canvas, self, cell_size, &self.palette())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_30_from_single_cell() {
+        let mut grid = ElementaryGrid::new(4, 7, false);
+        grid.seed_center();
+        for _ in 0..3 {
+            grid.update();
+        }
+        #[rustfmt::skip]
+        let expected = vec![
+            0, 0, 0, 1, 0, 0, 0,
+            0, 0, 1, 1, 1, 0, 0,
+            0, 1, 1, 0, 0, 1, 0,
+            1, 1, 0, 1, 1, 1, 1,
+        ];
+        assert_eq!(grid.current_cells(), &expected);
+    }
+
+    #[test]
+    fn test_diagram_scrolls_when_full() {
+        let mut grid = ElementaryGrid::new(2, 5, true);
+        grid.set_rule(ElementaryRule::Wolfram(90));
+        grid.seed_center();
+        grid.update();
+        grid.update();
+        assert_eq!(grid.current_cells(), &vec![0, 1, 0, 1, 0, 1, 0, 0, 0, 1]);
+
+        let reloaded = ElementaryGrid::from_vect(grid.current_cells().clone(), 2, 5, true);
+        assert_eq!(reloaded.generation(), grid.generation());
+    }
+
+    #[test]
+    fn test_totalistic_digits() {
+        // Code 777 en base 3 : 1001210, soit les états 0,1,2,1,0,0,1 pour les sommes 0 à 6
+        let rule = ElementaryRule::totalistic(3, 777).unwrap();
+        let next: Vec<u8> = (0..=6).map(|sum: u8| rule.next_state(sum.min(2), sum.saturating_sub(2).min(2), sum.saturating_sub(4))).collect();
+        assert_eq!(next, vec![0, 1, 2, 1, 0, 0, 1]);
+        assert_eq!(rule.to_string(), "k3/T777");
+    }
+
+    #[test]
+    fn test_empty_diagram() {
+        let mut grid = ElementaryGrid::new(0, 5, false);
+        grid.seed_center();
+        grid.update();
+        assert!(grid.generation().is_empty());
+        assert!(ElementaryGrid::new(3, 0, true).generation().is_empty());
+    }
+}
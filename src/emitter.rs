@@ -0,0 +1,279 @@
+use std::fmt;
+
+use crate::{apgcode, classify_object, grid_components, grid_index, grid_update_rule, LifeRule, ObjectKind, Topology};
+
+/// Paramètres de l'observation d'un émetteur.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EmitterOptions {
+    /// Nombre de générations observées.
+    pub generations: usize,
+    /// Distance au cadre initial du motif au-delà de laquelle un objet est considéré comme sorti.
+    pub margin: usize,
+    /// Période maximale recherchée lors de l'identification des objets sortis.
+    pub max_period: usize,
+}
+
+impl Default for EmitterOptions {
+    fn default() -> Self {
+        EmitterOptions {
+            generations: 500,
+            margin: 4,
+            max_period: 30,
+        }
+    }
+}
+
+/// Vaisseau sorti du cadre d'un motif.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Emission {
+    /// Génération à laquelle le vaisseau a été détecté hors du cadre.
+    pub generation: usize,
+    /// Apgcode du vaisseau.
+    pub apgcode: String,
+    /// Déplacement horizontal du vaisseau après une période, positif vers la droite.
+    pub dx: isize,
+    /// Déplacement vertical du vaisseau après une période, positif vers le bas.
+    pub dy: isize,
+}
+
+impl Emission {
+    /// Renvoie la direction du vaisseau sous forme de point cardinal (`N`, `NE`, `E`…).
+    pub fn direction(&self) -> &'static str {
+        match (self.dy.signum(), self.dx.signum()) {
+            (-1, 0) => "N",
+            (-1, 1) => "NE",
+            (0, 1) => "E",
+            (1, 1) => "SE",
+            (1, 0) => "S",
+            (1, -1) => "SW",
+            (0, -1) => "W",
+            (-1, -1) => "NW",
+            _ => "-",
+        }
+    }
+}
+
+/// Flux de vaisseaux identiques partant dans la même direction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EmissionStream {
+    /// Apgcode des vaisseaux du flux.
+    pub apgcode: String,
+    /// Direction des vaisseaux (voir `Emission::direction`).
+    pub direction: &'static str,
+    /// Nombre de vaisseaux détectés.
+    pub count: usize,
+    /// Nombre de générations entre deux vaisseaux, s'il est constant.
+    pub period: Option<usize>,
+    /// Génération de détection des vaisseaux modulo la période.
+    pub phase: Option<usize>,
+}
+
+/// Résultat de l'observation d'un émetteur.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EmitterReport {
+    /// Vaisseaux sortis, dans l'ordre de détection.
+    pub emissions: Vec<Emission>,
+    /// Flux de vaisseaux, regroupés par apgcode et direction.
+    pub streams: Vec<EmissionStream>,
+}
+
+impl fmt::Display for EmitterReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.streams.is_empty() {
+            return writeln!(f, "no spaceship escaped");
+        }
+        for stream in &self.streams {
+            write!(f, "{} x{} {}", stream.apgcode, stream.count, stream.direction)?;
+            match (stream.period, stream.phase) {
+                (Some(period), Some(phase)) => writeln!(f, " period {} phase {}", period, phase)?,
+                _ => writeln!(f, " irregular")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Regroupe les vaisseaux par apgcode et direction, et calcule la période de chaque flux.
+///
+/// La période est l'écart entre deux détections successives lorsqu'il est toujours le même,
+/// éventuellement après la première détection, qui peut suivre une phase de démarrage.
+fn group_streams(emissions: &[Emission]) -> Vec<EmissionStream> {
+    let mut streams: Vec<(EmissionStream, Vec<usize>)> = Vec::new();
+    for emission in emissions {
+        let key = (emission.apgcode.as_str(), emission.direction());
+        match streams
+            .iter_mut()
+            .find(|(stream, _)| (stream.apgcode.as_str(), stream.direction) == key)
+        {
+            Some((stream, generations)) => {
+                stream.count += 1;
+                generations.push(emission.generation);
+            }
+            None => streams.push((
+                EmissionStream {
+                    apgcode: emission.apgcode.clone(),
+                    direction: emission.direction(),
+                    count: 1,
+                    period: None,
+                    phase: None,
+                },
+                vec![emission.generation],
+            )),
+        }
+    }
+    streams
+        .into_iter()
+        .map(|(mut stream, generations)| {
+            let intervals: Vec<usize> = generations.windows(2).map(|pair| pair[1] - pair[0]).collect();
+            let steady = if intervals.len() > 1 { &intervals[1..] } else { &intervals[..] };
+            if let Some(&period) = steady.first() {
+                if period > 0 && steady.iter().all(|&interval| interval == period) {
+                    stream.period = Some(period);
+                    stream.phase = Some(generations[generations.len() - 1] % period);
+                }
+            }
+            stream
+        })
+        .collect()
+}
+
+/// Fait évoluer un objet isolé pendant une période et renvoie son déplacement `(dx, dy)`.
+fn displacement(cells: &[(isize, isize)], rule: &LifeRule, period: usize) -> (isize, isize) {
+    let min_row = cells.iter().map(|&(row, _)| row).min().unwrap_or(0);
+    let min_col = cells.iter().map(|&(_, col)| col).min().unwrap_or(0);
+    let height = cells.iter().map(|&(row, _)| row - min_row + 1).max().unwrap_or(0) as usize;
+    let width = cells.iter().map(|&(_, col)| col - min_col + 1).max().unwrap_or(0) as usize;
+    let margin = period + 1;
+    let (rows, cols) = (height + 2 * margin, width + 2 * margin);
+    let mut current = vec![0u8; rows * cols];
+    let mut next = vec![0u8; rows * cols];
+    for &(row, col) in cells {
+        current[grid_index((row - min_row) as usize + margin, (col - min_col) as usize + margin, cols)] = 1;
+    }
+    for _ in 0..period {
+        grid_update_rule(&mut current, &mut next, rows, cols, Topology::Plane, rule);
+    }
+    let live = (0..rows * cols).filter(|&index| current[index] >= 1);
+    let top = live.clone().map(|index| index / cols).min().unwrap_or(margin) as isize;
+    let left = live.map(|index| index % cols).min().unwrap_or(margin) as isize;
+    (left - margin as isize, top - margin as isize)
+}
+
+/// Observe un motif et détecte les vaisseaux qui quittent son cadre.
+///
+/// Le motif évolue sur un plan assez grand pour que les vaisseaux soient détectés avant d'en
+/// atteindre le bord. À chaque génération, les objets situés entièrement à plus de `margin`
+/// cellules du cadre initial du motif sont identifiés ; les vaisseaux sont enregistrés avec leur
+/// apgcode et leur direction, puis retirés. Les flux obtenus donnent la période et la phase
+/// d'émission d'un canon.
+///
+/// # Arguments
+///
+/// * `current_cells` - Vecteur contenant l'état de chaque cellule du motif.
+/// * `rows` - Nombre de lignes du motif.
+/// * `cols` - Nombre de colonnes du motif.
+/// * `rule` - La règle qui fait évoluer le motif.
+/// * `options` - La durée d'observation, la marge autour du cadre et la période maximale.
+///
+/// # Exemple
+///
+/// ```
+/// use crate::lifers::{grid_emissions, EmitterOptions, LifeRule};
+///
+/// // Un planeur seul sort du cadre vers le sud-est
+/// let cells = vec![0, 1, 0, 0, 0, 1, 1, 1, 1];
+/// let options = EmitterOptions { generations: 40, ..EmitterOptions::default() };
+/// let report = grid_emissions(&cells, 3, 3, &LifeRule::conway(), &options);
+/// assert_eq!(report.emissions.len(), 1);
+/// assert_eq!(report.emissions[0].apgcode, "xq4_153");
+/// assert_eq!(report.emissions[0].direction(), "SE");
+/// ```
+pub fn grid_emissions(
+    current_cells: &[u8],
+    rows: usize,
+    cols: usize,
+    rule: &LifeRule,
+    options: &EmitterOptions,
+) -> EmitterReport {
+    let live: Vec<usize> = (0..rows * cols).filter(|&index| current_cells[index] >= 1).collect();
+    let Some(top) = live.iter().map(|&index| index / cols).min() else {
+        return EmitterReport::default();
+    };
+    let bottom = live.iter().map(|&index| index / cols).max().unwrap_or(top);
+    let left = live.iter().map(|&index| index % cols).min().unwrap_or(0);
+    let right = live.iter().map(|&index| index % cols).max().unwrap_or(left);
+
+    // Plan entourant le cadre, avec la marge de détection et de quoi laisser passer les vaisseaux
+    let pad = options.margin + options.max_period + 4;
+    let (board_rows, board_cols) = (bottom - top + 1 + 2 * pad, right - left + 1 + 2 * pad);
+    let mut current = vec![0u8; board_rows * board_cols];
+    let mut next = vec![0u8; board_rows * board_cols];
+    for &index in &live {
+        current[grid_index(index / cols - top + pad, index % cols - left + pad, board_cols)] = 1;
+    }
+    let (frame_rows, frame_cols) = (
+        (pad - options.margin) as isize..(board_rows - pad + options.margin) as isize,
+        (pad - options.margin) as isize..(board_cols - pad + options.margin) as isize,
+    );
+    let in_frame = |&(row, col): &(isize, isize)| frame_rows.contains(&row) && frame_cols.contains(&col);
+
+    let mut emissions = Vec::new();
+    for generation in 1..=options.generations {
+        grid_update_rule(&mut current, &mut next, board_rows, board_cols, Topology::Plane, rule);
+        for component in grid_components(&current, board_rows, board_cols, Topology::Plane, 1) {
+            if component.iter().any(in_frame) {
+                continue;
+            }
+            let (kind, shape) = classify_object(&component, rule, options.max_period);
+            if let ObjectKind::Spaceship { period, .. } = kind {
+                let (dx, dy) = displacement(&component, rule, period);
+                emissions.push(Emission {
+                    generation,
+                    apgcode: apgcode(kind, &shape),
+                    dx,
+                    dy,
+                });
+                for (row, col) in component {
+                    current[grid_index(row as usize, col as usize, board_cols)] = 0;
+                }
+            }
+        }
+    }
+    EmitterReport {
+        streams: group_streams(&emissions),
+        emissions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rle_decode;
+
+    const GOSPER_GLIDER_GUN: &str = "x = 36, y = 9, rule = B3/S23
+24bo$22bobo$12b2o6b2o12b2o$11bo3bo4b2o12b2o$2o8bo5bo3b2o$2o8bo3bob2o4b
+obo$10bo5bo7bo$11bo3bo$12b2o!";
+
+    #[test]
+    fn test_gosper_gun_emits_period_30_glider_stream() {
+        let gun = rle_decode(GOSPER_GLIDER_GUN).unwrap();
+        let options = EmitterOptions { generations: 200, ..EmitterOptions::default() };
+        let report = grid_emissions(&gun.cells, gun.rows, gun.cols, &LifeRule::conway(), &options);
+        assert_eq!(report.streams.len(), 1);
+        let stream = &report.streams[0];
+        assert_eq!(stream.apgcode, "xq4_153");
+        assert_eq!(stream.direction, "SE");
+        assert_eq!(stream.period, Some(30));
+        assert!(stream.count >= 4);
+        assert!(report.to_string().contains("period 30"));
+    }
+
+    #[test]
+    fn test_still_life_and_oscillator_emit_nothing() {
+        // Un bloc et un clignotant assez éloignés pour ne pas interagir
+        let cells = vec![1, 1, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1];
+        let report = grid_emissions(&cells, 6, 3, &LifeRule::conway(), &EmitterOptions::default());
+        assert!(report.emissions.is_empty());
+        assert_eq!(report.to_string(), "no spaceship escaped\n");
+    }
+}
@@ -1,10 +1,41 @@
-use ggez::graphics::{Color,Canvas, PxScale, DrawParam, Text, TextFragment};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+use ggez::graphics::{Color,Canvas, PxScale, DrawParam, Rect, Text, TextFragment};
 use ggez::{Context, input::mouse::MouseButton, input::keyboard::{KeyCode, KeyInput}, GameResult};  
 use ggez::event::EventHandler;
 use ggez::timer;
   
 
-use crate::Grid;
+use crate::{
+    draw_gridlines, draw_heatmap, draw_minimap, grid_color_counts, grid_emissions, Anchor, CellStyle, EmitterOptions, Grid,
+    GridLines, Heatmap, HeatmapMode, Minimap, Theme, Viewport,
+};
+
+/// Pas d'ajustement du taux de bruit au clavier.
+const NOISE_STEP: f64 = 0.001;
+
+/// Nombre de générations prises en compte par la carte de chaleur activée au clavier.
+const HEATMAP_WINDOW: usize = 100;
+
+/// Facteur de zoom appliqué à chaque cran de la molette.
+const ZOOM_STEP: f32 = 1.25;
+
+/// Tailles de cellule minimale et maximale atteintes par le zoom, en pixels.
+const ZOOM_RANGE: (f32, f32) = (1.0, 64.0);
+
+/// Réaction de l'interface graphique au redimensionnement de la fenêtre.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ResizeMode {
+    /// La grille et la taille des cellules ne changent pas.
+    #[default]
+    Fixed,
+    /// La taille des cellules est ajustée pour que toute la grille tienne dans la fenêtre.
+    FitCells,
+    /// La grille est redimensionnée autour de l'ancre pour remplir la fenêtre, la taille des
+    /// cellules ne changeant pas.
+    ExpandGrid(Anchor),
+}
 
 pub struct LifeGui<G> {
     grid: G,
@@ -12,25 +43,270 @@ pub struct LifeGui<G> {
     is_paused: bool,
     fps: u32,
     days: u32, 
+    /// Couleur posée par un clic sur une grille multicolore.
+    paint_color: u8,
+    /// Résultat de la dernière analyse des vaisseaux émis, affiché sous les compteurs.
+    emitter_report: Option<String>,
+    /// Paramètres de l'analyse des vaisseaux émis.
+    emitter_options: EmitterOptions,
+    /// Résultat attendu de l'analyse en cours, calculée sur un autre fil d'exécution.
+    emitter_job: Option<Receiver<String>>,
+    /// Carte de chaleur dessinée par-dessus la grille, si elle est activée.
+    heatmap: Option<Heatmap>,
+    /// Lignes de séparation dessinées entre les cellules, si elles sont affichées.
+    gridlines: Option<GridLines>,
+    /// Couleur de fond de la fenêtre, visible entre les cellules espacées ou transparentes.
+    background: Color,
+    /// Thèmes parcourus au clavier.
+    themes: Vec<Theme>,
+    /// Indice du thème appliqué dans `themes`, `None` tant que la grille garde ses propres couleurs.
+    theme: Option<usize>,
+    /// Réaction au redimensionnement de la fenêtre.
+    resize_mode: ResizeMode,
+    /// Cellule `(ligne, colonne)` montrée dans le coin supérieur gauche de la fenêtre.
+    origin: (f32, f32),
+    /// Vue d'ensemble de la grille, si elle est affichée.
+    minimap: Option<Minimap>,
 }
 
 impl<G: Grid> LifeGui<G> {
     /// Crée une nouvelle instance de `LifeGui` avec la référence à la grille spécifiée.
     pub fn new(grid: G, cell_size: f32) -> Self {  
-        LifeGui { grid, cell_size , is_paused:false, fps: 60, days: 0,}
+        LifeGui { grid, cell_size , is_paused:false, fps: 60, days: 0, paint_color: 1, emitter_report: None, emitter_options: EmitterOptions::default(), emitter_job: None, heatmap: None, gridlines: None, background: Color::BLACK, themes: Theme::presets(), theme: None, resize_mode: ResizeMode::Fixed, origin: (0.0, 0.0), minimap: None,}
     }
     /// Méthode pour modifier le FPS
     pub fn set_fps(&mut self, fps: u32) {
         self.fps = fps;
     }
 
+    /// Affiche les lignes de séparation spécifiées, ou les masque si `None`.
+    pub fn set_gridlines(&mut self, gridlines: Option<GridLines>) {
+        self.gridlines = gridlines;
+    }
+
+    /// Affiche ou masque les lignes de séparation ; elles sont réaffichées avec le style par défaut.
+    pub fn toggle_gridlines(&mut self) {
+        self.gridlines = match self.gridlines {
+            Some(_) => None,
+            None => {
+                let color = self.current_theme().map_or(GridLines::default().color, |theme| theme.gridlines);
+                Some(GridLines { color, ..GridLines::default() })
+            }
+        };
+    }
+
+    /// Modifie l'espacement et l'arrondi des cellules dessinées (voir `Grid::set_cell_style`).
+    pub fn set_cell_style(&mut self, style: CellStyle) {
+        self.grid.set_cell_style(style);
+    }
+
+    /// Modifie la couleur de fond de la fenêtre.
+    pub fn set_background(&mut self, color: Color) {
+        self.background = color;
+    }
+
+    /// Choisit la réaction de l'interface au redimensionnement de la fenêtre.
+    pub fn set_resize_mode(&mut self, resize_mode: ResizeMode) {
+        self.resize_mode = resize_mode;
+    }
+
+    /// Adapte la taille des cellules ou celle de la grille à une fenêtre de `width` × `height`
+    /// pixels, selon le mode de redimensionnement.
+    pub fn fit_to_window(&mut self, width: f32, height: f32) {
+        match self.resize_mode {
+            ResizeMode::Fixed => {}
+            ResizeMode::FitCells => {
+                let (rows, cols) = (self.grid.rows().max(1) as f32, self.grid.cols().max(1) as f32);
+                self.cell_size = (width / cols).min(height / rows).max(1.0);
+            }
+            ResizeMode::ExpandGrid(anchor) => {
+                let rows = ((height / self.cell_size) as usize).max(1);
+                let cols = ((width / self.cell_size) as usize).max(1);
+                if (rows, cols) != (self.grid.rows(), self.grid.cols()) {
+                    self.grid.resize(rows, cols, anchor);
+                }
+            }
+        }
+    }
+
+    /// Renvoie la partie de la grille visible dans une fenêtre de `width` × `height` pixels.
+    pub fn viewport(&self, width: f32, height: f32) -> Viewport {
+        Viewport::new(self.origin.0, self.origin.1, width, height, self.cell_size)
+            .clamped(self.grid.rows(), self.grid.cols())
+    }
+
+    /// Centre la partie visible sur la cellule (`row`, `col`), sans sortir de la grille.
+    pub fn jump_to(&mut self, row: f32, col: f32, width: f32, height: f32) {
+        let viewport = self.viewport(width, height).centered_on(row, col, self.grid.rows(), self.grid.cols());
+        self.origin = (viewport.row, viewport.col);
+    }
+
+    /// Déplace la partie visible de (`rows`, `cols`) cellules.
+    pub fn pan(&mut self, rows: f32, cols: f32, width: f32, height: f32) {
+        let viewport = self.viewport(width, height);
+        self.origin = (viewport.row + rows, viewport.col + cols);
+        self.clamp_origin(width, height);
+    }
+
+    /// Multiplie la taille des cellules par `factor`, la cellule sous le point (`x`, `y`) de la
+    /// fenêtre restant en place.
+    pub fn zoom(&mut self, factor: f32, x: f32, y: f32, width: f32, height: f32) {
+        let viewport = self.viewport(width, height);
+        let (row, col) = (viewport.row + y / self.cell_size, viewport.col + x / self.cell_size);
+        self.cell_size = (self.cell_size * factor).clamp(ZOOM_RANGE.0, ZOOM_RANGE.1);
+        self.origin = (row - y / self.cell_size, col - x / self.cell_size);
+        self.clamp_origin(width, height);
+    }
+
+    /// Ramène la partie visible dans la grille.
+    fn clamp_origin(&mut self, width: f32, height: f32) {
+        let viewport = self.viewport(width, height);
+        self.origin = (viewport.row, viewport.col);
+    }
+
+    /// Affiche la vue d'ensemble spécifiée, ou la masque si `None`.
+    pub fn set_minimap(&mut self, minimap: Option<Minimap>) {
+        self.minimap = minimap;
+    }
+
+    /// Affiche ou masque la vue d'ensemble de la grille.
+    pub fn toggle_minimap(&mut self) {
+        self.minimap = match self.minimap {
+            Some(_) => None,
+            None => Some(Minimap::default()),
+        };
+    }
+
+    /// Remplace les thèmes parcourus au clavier, par exemple par ceux lus avec `Theme::load`.
+    ///
+    /// Le thème actuel reste appliqué jusqu'au prochain changement de thème.
+    pub fn set_themes(&mut self, themes: Vec<Theme>) {
+        self.themes = themes;
+        self.theme = None;
+    }
+
+    /// Renvoie le thème appliqué, s'il y en a un.
+    pub fn current_theme(&self) -> Option<&Theme> {
+        self.theme.and_then(|index| self.themes.get(index))
+    }
+
+    /// Applique le thème d'indice `index` parmi les thèmes parcourus au clavier.
+    ///
+    /// Le thème donne la couleur de fond, celle des lignes de séparation et la palette de la grille.
+    pub fn set_theme(&mut self, index: usize) {
+        let Some(theme) = self.themes.get(index) else {
+            return;
+        };
+        theme.apply(&mut self.grid);
+        self.background = theme.background;
+        if let Some(gridlines) = &mut self.gridlines {
+            gridlines.color = theme.gridlines;
+        }
+        self.theme = Some(index);
+    }
+
+    /// Applique le thème suivant, en revenant au premier après le dernier.
+    pub fn cycle_theme(&mut self) {
+        if !self.themes.is_empty() {
+            self.set_theme(self.theme.map_or(0, |index| (index + 1) % self.themes.len()));
+        }
+    }
+
+    /// Choisit la couleur posée par un clic, si la grille possède cette couleur.
+    pub fn set_paint_color(&mut self, color: u8) {
+        if (1..=self.grid.colors()).contains(&color) {
+            self.paint_color = color;
+        }
+    }
+
+    /// Active une carte de chaleur de l'activité sur les `window` dernières générations.
+    ///
+    /// La carte commence à la génération actuelle et est dessinée par-dessus la grille.
+    pub fn set_heatmap(&mut self, window: usize, mode: HeatmapMode) {
+        let mut heatmap = Heatmap::new(self.grid.rows(), self.grid.cols(), window, mode);
+        heatmap.record_grid(&self.grid);
+        self.heatmap = Some(heatmap);
+    }
+
+    /// Active ou désactive la carte de chaleur ; une fois activée, un nouvel appel passe du
+    /// décompte des changements à celui des cellules vivantes avant de la désactiver.
+    pub fn toggle_heatmap(&mut self) {
+        match self.heatmap.as_ref().map(Heatmap::mode) {
+            None => self.set_heatmap(HEATMAP_WINDOW, HeatmapMode::Changes),
+            Some(HeatmapMode::Changes) => self.set_heatmap(HEATMAP_WINDOW, HeatmapMode::Alive),
+            Some(HeatmapMode::Alive) => self.heatmap = None,
+        }
+    }
+
+    /// Modifie les paramètres de l'analyse des vaisseaux émis, dont dépend sa durée.
+    pub fn set_emitter_options(&mut self, options: EmitterOptions) {
+        self.emitter_options = options;
+    }
+
+    /// Lance l'analyse des vaisseaux émis par le motif actuel de la grille (voir `grid_emissions`).
+    ///
+    /// L'analyse est calculée sur un autre fil d'exécution pour ne pas bloquer la fenêtre ; son
+    /// résultat est affiché sous les compteurs dès qu'il est prêt. Les grilles dont la règle n'est
+    /// pas une règle `B/S` ne peuvent pas être analysées.
+    pub fn analyze_emissions(&mut self) {
+        let Some(rule) = self.grid.life_rule() else {
+            self.emitter_report = Some(String::from("emitter analysis needs a B/S rule\n"));
+            return;
+        };
+        let cells = self.grid.current_cells().clone();
+        let (rows, cols, options) = (self.grid.rows(), self.grid.cols(), self.emitter_options);
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = sender.send(grid_emissions(&cells, rows, cols, &rule, &options).to_string());
+        });
+        // Une nouvelle analyse remplace celle en cours, dont le résultat est ignoré
+        self.emitter_job = Some(receiver);
+        self.emitter_report = Some(String::from("analysing...\n"));
+    }
+
+    /// Récupère le résultat de l'analyse des vaisseaux émis, s'il est prêt.
+    fn poll_emissions(&mut self) {
+        let Some(job) = &self.emitter_job else {
+            return;
+        };
+        match job.try_recv() {
+            Ok(report) => {
+                self.emitter_report = Some(report);
+                self.emitter_job = None;
+            }
+            Err(mpsc::TryRecvError::Disconnected) => self.emitter_job = None,
+            Err(mpsc::TryRecvError::Empty) => {}
+        }
+    }
+
     /// Méthode pour afficher le nombre d'fps et les jours qui se sont écoulés
     pub fn draw_fps_days(&self, ctx: &mut Context, canvas: &mut Canvas) -> GameResult {
         // Récupère le nombre d'images par seconde (FPS) depuis le compteur
         let fps = ctx.time.fps();
     
         // Crée une chaîne de caractères formatée pour afficher le FPS
-        let fps_string = format!("FPS: {} \nDays: {}", fps, self.days);
+        let mut fps_string = format!("FPS: {} \nDays: {}", fps, self.days);
+        if self.grid.noise() > 0.0 {
+            fps_string.push_str(&format!("\nNoise: {:.3}", self.grid.noise()));
+        }
+        let colors = self.grid.colors();
+        if colors > 1 {
+            // Population de chaque couleur, la couleur posée par un clic étant marquée d'une étoile
+            let counts = grid_color_counts(self.grid.current_cells(), colors);
+            for (color, count) in counts.iter().enumerate().skip(1) {
+                let marker = if color as u8 == self.paint_color { "*" } else { "" };
+                fps_string.push_str(&format!("\nColor {}{}: {}", color, marker, count));
+            }
+        }
+        if let Some(theme) = self.current_theme() {
+            fps_string.push_str(&format!("\nTheme: {}", theme.name));
+        }
+        if let Some(heatmap) = &self.heatmap {
+            fps_string.push_str(&format!("\nHeatmap: {} ({}/{})", heatmap.mode(), heatmap.generations(), heatmap.window()));
+        }
+        if let Some(report) = &self.emitter_report {
+            fps_string.push_str(&format!("\nEmitters:\n{}", report.trim_end()));
+        }
     
         // Crée un objet TextFragment pour le texte
         let text_fragment = TextFragment::new(fps_string)
@@ -48,12 +324,16 @@ impl<G: Grid> LifeGui<G> {
 
 impl<G: Grid> EventHandler for LifeGui<G> {
     fn update(&mut self, ctx: &mut Context) -> GameResult {
+        self.poll_emissions();
         // Régule la mise à jour en fonction des FPS cibles (par exemple, 60 FPS)
         while ctx.time.check_update_time(self.fps) {
             // Vérifie si le jeu est en pause avant de mettre à jour la grille
             if !self.is_paused {
                 self.days +=1;
                 self.grid.update();
+                if let Some(heatmap) = &mut self.heatmap {
+                    heatmap.record_grid(&self.grid);
+                }
             }
         }
         timer::yield_now(); // Facultatif : permet de libérer le CPU pour d'autres tâches
@@ -61,33 +341,107 @@ impl<G: Grid> EventHandler for LifeGui<G> {
     }
 
     fn draw(&mut self, ctx: &mut Context) -> GameResult { 
-        let mut canvas = Canvas::from_frame(ctx, Color::BLACK);
+        let mut canvas = Canvas::from_frame(ctx, self.background);
+        // La grille est dessinée dans ses propres coordonnées, décalées jusqu'à la partie visible
+        let (width, height) = ctx.gfx.drawable_size();
+        let viewport = self.viewport(width, height);
+        canvas.set_screen_coordinates(Rect::new(viewport.col * self.cell_size, viewport.row * self.cell_size, width, height));
         let _ = self.grid.draw(ctx, &mut canvas, self.cell_size);
+        if let Some(heatmap) = &self.heatmap {
+            draw_heatmap(ctx, &mut canvas, heatmap, self.cell_size)?;
+        }
+        if let Some(gridlines) = &self.gridlines {
+            draw_gridlines(ctx, &mut canvas, self.grid.rows(), self.grid.cols(), self.cell_size, gridlines)?;
+        }
+        // Les superpositions sont dessinées dans les coordonnées de la fenêtre
+        canvas.set_screen_coordinates(Rect::new(0.0, 0.0, width, height));
+        if let Some(minimap) = &self.minimap {
+            draw_minimap(ctx, &mut canvas, self.grid.current_cells(), self.grid.rows(), self.grid.cols(), &viewport, minimap)?;
+        }
         self.draw_fps_days(ctx, &mut canvas)?;
         canvas.finish(ctx)?;
         Ok(())
     }
 
-    fn mouse_button_down_event(&mut self, _ctx: &mut Context, button: MouseButton, x: f32, y: f32) -> GameResult { 
+    fn mouse_button_down_event(&mut self, ctx: &mut Context, button: MouseButton, x: f32, y: f32) -> GameResult { 
         if button == MouseButton::Left { 
-            // Convertir les coordonnées de la souris en indices de cellule
-            let row = (y / self.cell_size) as usize;
-            let col = (x / self.cell_size) as usize;
-
-            // Vérifier que les indices sont valides
-            if row < self.grid.rows() && col < self.grid.cols() {
-                // Inverser l'état de la cellule (de morte à vivante ou de vivante à morte)
-                self.grid.toggle_cell_state(row, col);
+            let (width, height) = ctx.gfx.drawable_size();
+            // Un clic sur la vue d'ensemble centre la partie visible sur la cellule désignée
+            if let Some(minimap) = &self.minimap {
+                if let Some((row, col)) = minimap.cell_at(self.grid.rows(), self.grid.cols(), width, x, y) {
+                    self.jump_to(row, col, width, height);
+                    return Ok(());
+                }
+            }
+            // Convertir les coordonnées de la souris en indices de cellule, hors de la grille sinon
+            let viewport = self.viewport(width, height);
+            let (x, y) = (x + viewport.col * self.cell_size, y + viewport.row * self.cell_size);
+            if let Some((row, col)) = self.grid.cell_at(x, y, self.cell_size) {
+                if self.grid.colors() > 1 {
+                    // Pose la couleur choisie, ou efface la cellule si elle a déjà cette couleur
+                    let index = self.grid.index(row, col);
+                    let state = if self.grid.current_cells()[index] == self.paint_color { 0 } else { self.paint_color };
+                    self.grid.set_cell_state(row, col, state);
+                } else {
+                    // Passe la cellule à l'état suivant (de morte à vivante ou de vivante à morte pour une grille à deux états)
+                    self.grid.cycle_cell_state(row, col);
+                }
             }
         }
         Ok(())
     }
 
-    fn key_down_event(&mut self, _ctx: &mut Context, input: KeyInput, _repeat: bool) -> GameResult  {
+    fn mouse_wheel_event(&mut self, ctx: &mut Context, _x: f32, y: f32) -> GameResult {
+        // Zoom autour du pointeur de la souris
+        let (width, height) = ctx.gfx.drawable_size();
+        let position = ctx.mouse.position();
+        self.zoom(ZOOM_STEP.powf(y), position.x, position.y, width, height);
+        Ok(())
+    }
+
+    fn resize_event(&mut self, _ctx: &mut Context, width: f32, height: f32) -> GameResult {
+        self.fit_to_window(width, height);
+        Ok(())
+    }
+
+    fn key_down_event(&mut self, ctx: &mut Context, input: KeyInput, _repeat: bool) -> GameResult  {
         // Inverse l'état de la pause lorsque la touche "Espace" est enfoncée 
         if input.keycode == Some(KeyCode::Space) { 
             self.is_paused = !self.is_paused;
         }
+        // Ajuste le taux de bruit avec les touches "[" et "]"
+        match input.keycode {
+            Some(KeyCode::LBracket) => self.grid.set_noise((self.grid.noise() - NOISE_STEP).max(0.0)),
+            Some(KeyCode::RBracket) => self.grid.set_noise(self.grid.noise() + NOISE_STEP),
+            // Choisit la couleur posée par un clic sur une grille multicolore
+            Some(KeyCode::Key1) => self.set_paint_color(1),
+            Some(KeyCode::Key2) => self.set_paint_color(2),
+            Some(KeyCode::Key3) => self.set_paint_color(3),
+            Some(KeyCode::Key4) => self.set_paint_color(4),
+            // Analyse les vaisseaux émis par le motif actuel avec la touche "E"
+            Some(KeyCode::E) => self.analyze_emissions(),
+            // Parcourt les cartes de chaleur (changements, cellules vivantes, aucune) avec la touche "H"
+            Some(KeyCode::H) => self.toggle_heatmap(),
+            // Affiche ou masque les lignes de séparation avec la touche "G"
+            Some(KeyCode::G) => self.toggle_gridlines(),
+            // Passe au thème suivant avec la touche "T"
+            Some(KeyCode::T) => self.cycle_theme(),
+            // Affiche ou masque la vue d'ensemble avec la touche "M"
+            Some(KeyCode::M) => self.toggle_minimap(),
+            // Déplace la partie visible d'un quart de fenêtre avec les flèches
+            Some(key @ (KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right)) => {
+                let (width, height) = ctx.gfx.drawable_size();
+                let viewport = self.viewport(width, height);
+                let (rows, cols) = match key {
+                    KeyCode::Up => (-viewport.rows / 4.0, 0.0),
+                    KeyCode::Down => (viewport.rows / 4.0, 0.0),
+                    KeyCode::Left => (0.0, -viewport.cols / 4.0),
+                    _ => (0.0, viewport.cols / 4.0),
+                };
+                self.pan(rows, cols, width, height);
+            }
+            _ => {}
+        }
         Ok(())
     }
 }
\ No newline at end of file
@@ -0,0 +1,262 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+use ggez::{graphics::{self, Canvas, Color}, Context, GameResult};
+
+use crate::{grid_index, Grid};
+
+/// Activité comptée par une carte de chaleur.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HeatmapMode {
+    /// Nombre de générations où la cellule a changé d'état.
+    Changes,
+    /// Nombre de générations où la cellule était vivante.
+    Alive,
+}
+
+impl fmt::Display for HeatmapMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HeatmapMode::Changes => write!(f, "changes"),
+            HeatmapMode::Alive => write!(f, "alive"),
+        }
+    }
+}
+
+/// Carte de chaleur : activité de chaque cellule sur les dernières générations d'une grille.
+///
+/// Chaque génération enregistrée ajoute 1 au compteur des cellules actives ; au-delà de `window`
+/// générations, l'activité de la plus ancienne est retirée, si bien que les compteurs ne portent
+/// que sur les `window` dernières générations.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Heatmap {
+    rows: usize,
+    cols: usize,
+    window: usize,
+    mode: HeatmapMode,
+    /// Nombre de générations actives de chaque cellule dans la fenêtre.
+    counts: Vec<u32>,
+    /// Indices des cellules actives de chaque génération de la fenêtre, de la plus ancienne à la
+    /// plus récente.
+    history: VecDeque<Vec<usize>>,
+    /// États de la dernière génération enregistrée, pour détecter les changements.
+    previous: Option<Vec<u8>>,
+}
+
+impl Heatmap {
+    /// Crée une carte de chaleur vide pour une grille de `rows` × `cols` cellules.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - Nombre de lignes de la grille.
+    /// * `cols` - Nombre de colonnes de la grille.
+    /// * `window` - Nombre de générations prises en compte, au moins 1.
+    /// * `mode` - L'activité comptée.
+    pub fn new(rows: usize, cols: usize, window: usize, mode: HeatmapMode) -> Self {
+        Heatmap {
+            rows,
+            cols,
+            window: window.max(1),
+            mode,
+            counts: vec![0; rows * cols],
+            history: VecDeque::new(),
+            previous: None,
+        }
+    }
+
+    /// Nombre de lignes de la grille observée.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Nombre de colonnes de la grille observée.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Nombre de générations prises en compte.
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Activité comptée par la carte.
+    pub fn mode(&self) -> HeatmapMode {
+        self.mode
+    }
+
+    /// Nombre de générations actuellement dans la fenêtre.
+    pub fn generations(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Nombre de générations actives de chaque cellule dans la fenêtre, ligne par ligne.
+    pub fn counts(&self) -> &[u32] {
+        &self.counts
+    }
+
+    /// Efface l'activité enregistrée.
+    pub fn clear(&mut self) {
+        self.counts.fill(0);
+        self.history.clear();
+        self.previous = None;
+    }
+
+    /// Enregistre une génération de la grille.
+    ///
+    /// En mode `Changes`, la première génération enregistrée sert de référence et ne compte
+    /// aucun changement. Si les dimensions diffèrent de celles de la carte, la carte est vidée et
+    /// adopte les nouvelles dimensions.
+    ///
+    /// # Arguments
+    ///
+    /// * `current_cells` - Vecteur contenant l'état de chaque cellule de la grille.
+    /// * `rows` - Nombre de lignes de la grille.
+    /// * `cols` - Nombre de colonnes de la grille.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::{Heatmap, HeatmapMode};
+    ///
+    /// let mut heatmap = Heatmap::new(1, 3, 2, HeatmapMode::Alive);
+    /// heatmap.record(&[1, 1, 0], 1, 3);
+    /// heatmap.record(&[0, 1, 0], 1, 3);
+    /// heatmap.record(&[0, 1, 1], 1, 3);
+    /// // Seules les deux dernières générations comptent
+    /// assert_eq!(heatmap.counts(), &[0, 2, 1]);
+    /// assert_eq!(heatmap.intensity(0, 1), 1.0);
+    /// ```
+    pub fn record(&mut self, current_cells: &[u8], rows: usize, cols: usize) {
+        if (rows, cols) != (self.rows, self.cols) {
+            *self = Heatmap::new(rows, cols, self.window, self.mode);
+        }
+        let active: Vec<usize> = match (self.mode, &self.previous) {
+            (HeatmapMode::Alive, _) => (0..rows * cols).filter(|&index| current_cells[index] >= 1).collect(),
+            (HeatmapMode::Changes, Some(previous)) => {
+                (0..rows * cols).filter(|&index| current_cells[index] != previous[index]).collect()
+            }
+            (HeatmapMode::Changes, None) => Vec::new(),
+        };
+        for &index in &active {
+            self.counts[index] += 1;
+        }
+        self.history.push_back(active);
+        while self.history.len() > self.window {
+            for index in self.history.pop_front().unwrap_or_default() {
+                self.counts[index] -= 1;
+            }
+        }
+        if self.mode == HeatmapMode::Changes {
+            match &mut self.previous {
+                Some(previous) => previous.copy_from_slice(current_cells),
+                None => self.previous = Some(current_cells.to_vec()),
+            }
+        }
+    }
+
+    /// Enregistre la génération actuelle d'une grille (voir `Heatmap::record`).
+    pub fn record_grid<G: Grid + ?Sized>(&mut self, grid: &G) {
+        self.record(grid.current_cells(), grid.rows(), grid.cols());
+    }
+
+    /// Renvoie l'activité d'une cellule entre 0 et 1, rapportée au nombre de générations de la
+    /// fenêtre.
+    pub fn intensity(&self, row: usize, col: usize) -> f32 {
+        if self.history.is_empty() {
+            return 0.0;
+        }
+        self.counts[grid_index(row, col, self.cols)] as f32 / self.history.len() as f32
+    }
+}
+
+/// Renvoie la couleur d'une intensité entre 0 et 1 sur une rampe bleu, rouge, jaune, blanc.
+///
+/// L'opacité croît avec l'intensité pour que la grille reste visible sous les zones calmes.
+///
+/// # Exemple
+///
+/// ```
+/// use crate::lifers::heat_color;
+///
+/// assert_eq!(heat_color(0.0).a, 0.0);
+/// assert_eq!((heat_color(1.0).r, heat_color(1.0).g, heat_color(1.0).b), (1.0, 1.0, 1.0));
+/// ```
+pub fn heat_color(intensity: f32) -> Color {
+    const RAMP: [(f32, f32, f32); 4] = [(0.0, 0.0, 1.0), (1.0, 0.0, 0.0), (1.0, 1.0, 0.0), (1.0, 1.0, 1.0)];
+    let intensity = intensity.clamp(0.0, 1.0);
+    let position = intensity * (RAMP.len() - 1) as f32;
+    let step = (position as usize).min(RAMP.len() - 2);
+    let t = position - step as f32;
+    let (from, to) = (RAMP[step], RAMP[step + 1]);
+    let mix = |a: f32, b: f32| a + (b - a) * t;
+    let alpha = if intensity > 0.0 { 0.25 + 0.6 * intensity } else { 0.0 };
+    Color::new(mix(from.0, to.0), mix(from.1, to.1), mix(from.2, to.2), alpha)
+}
+
+/// Dessine une carte de chaleur par-dessus une grille déjà dessinée.
+///
+/// Les cellules sans activité ne sont pas dessinées.
+///
+/// # Arguments
+///
+/// * `ctx` - Le contexte du jeu.
+/// * `canvas` - Le canva sur lequel dessiner.
+/// * `heatmap` - La carte de chaleur à dessiner.
+/// * `cell_size` - La taille de chaque cellule de la grille.
+///
+/// # Erreurs
+///
+/// Cette fonction peut retourner une erreur de type `GameError` si une erreur survient lors du dessin.
+///
+pub fn draw_heatmap(ctx: &mut Context, canvas: &mut Canvas, heatmap: &Heatmap, cell_size: f32) -> GameResult {
+    for row in 0..heatmap.rows() {
+        for col in 0..heatmap.cols() {
+            let intensity = heatmap.intensity(row, col);
+            if intensity <= 0.0 {
+                continue;
+            }
+            let rect = graphics::Rect::new(col as f32 * cell_size, row as f32 * cell_size, cell_size, cell_size);
+            let mesh = graphics::Mesh::new_rectangle(ctx, graphics::DrawMode::fill(), rect, heat_color(intensity))?;
+            canvas.draw(&mesh, graphics::DrawParam::default());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConwaysGrid;
+
+    #[test]
+    fn test_blinker_changes_over_window() {
+        // Clignotant horizontal au centre d'une grille de 5x5
+        let mut cells = vec![0; 25];
+        cells[11..14].fill(1);
+        let mut grid = ConwaysGrid::from_vect(cells, 5, 5, false);
+        let mut heatmap = Heatmap::new(5, 5, 4, HeatmapMode::Changes);
+        heatmap.record_grid(&grid);
+        for _ in 0..6 {
+            grid.update();
+            heatmap.record_grid(&grid);
+        }
+        assert_eq!(heatmap.generations(), 4);
+        // Le centre ne change jamais, les extrémités changent à chaque génération
+        assert_eq!(heatmap.intensity(2, 2), 0.0);
+        assert_eq!(heatmap.intensity(2, 1), 1.0);
+        assert_eq!(heatmap.intensity(1, 2), 1.0);
+        assert_eq!(heatmap.intensity(0, 0), 0.0);
+    }
+
+    #[test]
+    fn test_resize_and_clear_reset_counts() {
+        let mut heatmap = Heatmap::new(1, 2, 10, HeatmapMode::Alive);
+        heatmap.record(&[1, 0], 1, 2);
+        heatmap.record(&[1, 1, 1], 1, 3);
+        assert_eq!((heatmap.cols(), heatmap.generations()), (3, 1));
+        assert_eq!(heatmap.counts(), &[1, 1, 1]);
+        heatmap.clear();
+        assert_eq!(heatmap.counts(), &[0, 0, 0]);
+        assert_eq!(heatmap.intensity(0, 0), 0.0);
+    }
+}
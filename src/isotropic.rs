@@ -0,0 +1,471 @@
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use crate::{grid_index, RuleParseError, Topology};
+
+/// Nombre de classes de configurations isotropes du voisinage de Moore.
+pub const ISOTROPIC_CLASS_COUNT: usize = 51;
+
+/// Lettres de la notation de Hensel disponibles pour chaque nombre de voisins (0 à 8).
+const LETTERS: [&str; 9] = [
+    "",
+    "ce",
+    "cekain",
+    "cekainyqjr",
+    "cekainyqjrtwz",
+    "cekainyqjr",
+    "cekain",
+    "ce",
+    "",
+];
+
+/// Configuration représentative de chaque classe pour 1 à 4 voisins, sous forme de positions
+/// sur la couronne des voisins dans le sens horaire : N, NE, E, SE, S, SW, W, NW.
+/// Les classes à 5, 6 et 7 voisins sont les compléments de celles à 3, 2 et 1 voisins.
+const REPRESENTATIVES: [&[(char, &[usize])]; 5] = [
+    &[],
+    &[('c', &[1]), ('e', &[0])],
+    &[
+        ('c', &[1, 3]),
+        ('e', &[0, 2]),
+        ('k', &[0, 3]),
+        ('a', &[0, 1]),
+        ('i', &[0, 4]),
+        ('n', &[1, 5]),
+    ],
+    &[
+        ('c', &[1, 3, 5]),
+        ('e', &[0, 2, 4]),
+        ('k', &[0, 2, 5]),
+        ('a', &[0, 1, 2]),
+        ('i', &[0, 1, 7]),
+        ('n', &[0, 1, 3]),
+        ('y', &[0, 3, 5]),
+        ('q', &[0, 1, 5]),
+        ('j', &[0, 1, 6]),
+        ('r', &[0, 1, 4]),
+    ],
+    &[
+        ('c', &[1, 3, 5, 7]),
+        ('e', &[0, 2, 4, 6]),
+        ('k', &[0, 1, 3, 6]),
+        ('a', &[0, 1, 2, 3]),
+        ('i', &[0, 1, 3, 4]),
+        ('n', &[0, 1, 3, 7]),
+        ('y', &[0, 1, 3, 5]),
+        ('q', &[0, 1, 2, 5]),
+        ('j', &[0, 1, 4, 6]),
+        ('r', &[0, 1, 2, 4]),
+        ('t', &[0, 1, 4, 7]),
+        ('w', &[0, 1, 5, 6]),
+        ('z', &[0, 1, 4, 5]),
+    ],
+];
+
+/// Position dans le voisinage 3x3 (bit de l'index à 9 bits) de chaque case de la couronne.
+const RING_BITS: [usize; 8] = [1, 2, 5, 8, 7, 6, 3, 0];
+
+/// Bit de la cellule centrale dans l'index à 9 bits.
+const CENTER_BIT: usize = 4;
+
+/// Renvoie l'identifiant de la classe `letter` à `count` voisins, les classes étant numérotées
+/// dans l'ordre `0, 1c, 1e, 2c, 2e, ...`.
+fn class_id(count: usize, letter: Option<char>) -> Option<usize> {
+    let offset: usize = (0..count).map(|n| LETTERS[n].len().max(1)).sum();
+    match letter {
+        None if LETTERS[count].is_empty() => Some(offset),
+        None => None,
+        Some(letter) => LETTERS[count].find(letter).map(|position| offset + position),
+    }
+}
+
+/// Renvoie la table qui associe à chaque configuration 3x3 (index à 9 bits, ligne par ligne,
+/// le bit 4 étant la cellule centrale) l'identifiant de sa classe isotrope.
+///
+/// La table est calculée une seule fois en appliquant les 8 symétries du carré aux
+/// configurations représentatives de chaque classe.
+///
+/// # Exemple
+///
+/// ```
+/// use crate::lifers::isotropic_class_table;
+///
+/// let table = isotropic_class_table();
+/// // Les deux voisins opposés N-S et E-W appartiennent à la même classe (2i)
+/// assert_eq!(table[0b000_000_010 | 0b010_000_000], table[0b000_101_000]);
+/// ```
+pub fn isotropic_class_table() -> &'static [u8; 512] {
+    static TABLE: OnceLock<[u8; 512]> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut table = [0u8; 512];
+        for (count, count_letters) in LETTERS.iter().enumerate() {
+            let letters: Vec<Option<char>> = if count_letters.is_empty() {
+                vec![None]
+            } else {
+                count_letters.chars().map(Some).collect()
+            };
+            for letter in letters {
+                let ring = representative_ring(count, letter);
+                let id = class_id(count, letter).expect("letter belongs to the count") as u8;
+                for symmetry in 0..8 {
+                    let index = ring_to_index(transform_ring(ring, symmetry));
+                    table[index] = id;
+                    table[index | 1 << CENTER_BIT] = id;
+                }
+            }
+        }
+        table
+    })
+}
+
+/// Renvoie la couronne (8 bits, N en bit 0) représentative d'une classe.
+fn representative_ring(count: usize, letter: Option<char>) -> u8 {
+    if count > 4 {
+        // Les classes au-delà de 4 voisins sont les compléments des classes à 8 - count voisins
+        return !representative_ring(8 - count, letter);
+    }
+    match letter {
+        None if count == 0 => 0,
+        None => 0xff,
+        Some(letter) => REPRESENTATIVES[count]
+            .iter()
+            .find(|(l, _)| *l == letter)
+            .map(|(_, positions)| positions.iter().fold(0, |ring, p| ring | 1 << p))
+            .expect("representative exists"),
+    }
+}
+
+/// Applique l'une des 8 symétries du carré à une couronne : rotations d'un quart de tour,
+/// suivies d'un miroir pour les symétries 4 à 7.
+fn transform_ring(ring: u8, symmetry: usize) -> u8 {
+    let rotated = ring.rotate_left(2 * (symmetry % 4) as u32);
+    if symmetry < 4 {
+        return rotated;
+    }
+    (0..8)
+        .filter(|&p| rotated & 1 << p != 0)
+        .fold(0, |mirrored, p| mirrored | 1 << ((8 - p) % 8))
+}
+
+/// Convertit une couronne en index à 9 bits avec une cellule centrale morte.
+fn ring_to_index(ring: u8) -> usize {
+    (0..8)
+        .filter(|&p| ring & 1 << p != 0)
+        .fold(0, |index, p| index | 1 << RING_BITS[p])
+}
+
+/// Calcule l'index à 9 bits du voisinage 3x3 d'une cellule (ligne par ligne, cellule centrale en bit 4).
+///
+/// # Arguments
+///
+/// * `row` - L'indice de la ligne de la cellule dans la grille.
+/// * `col` - L'indice de la colonne de la cellule dans la grille.
+/// * `current_cells` - Vecteur contenant l'état actuel de chaque cellule de la grille.
+/// * `rows` - Nombre de lignes de la grille.
+/// * `cols` - Nombre de colonnes de la grille.
+/// * `topology` - La topologie de la grille.
+///
+/// # Exemple
+///
+/// ```
+/// use crate::lifers::grid_moore_index;
+///
+/// let current_cells = vec![
+///     1, 0, 0,
+///     0, 1, 0,
+///     0, 0, 1,
+/// ];
+/// assert_eq!(grid_moore_index(1, 1, &current_cells, 3, 3, false), 0b100_010_001);
+/// ```
+pub fn grid_moore_index(
+    row: usize,
+    col: usize,
+    current_cells: &[u8],
+    rows: usize,
+    cols: usize,
+    topology: impl Into<Topology>,
+) -> usize {
+    let topology = topology.into();
+    let mut index = 0;
+    for (bit, (di, dj)) in (-1..=1isize)
+        .flat_map(|di| (-1..=1isize).map(move |dj| (di, dj)))
+        .enumerate()
+    {
+        let i = row as isize + di;
+        let j = col as isize + dj;
+        let alive = topology.state_at(current_cells, i, j, rows, cols) >= 1;
+        if alive {
+            index |= 1 << bit;
+        }
+    }
+    index
+}
+
+/// Règle isotrope non totalistique en notation de Hensel, par exemple `B2n3/S23-q`.
+///
+/// Chaque chiffre peut être suivi de lettres désignant les classes de configurations retenues,
+/// ou d'un `-` suivi des classes exclues. Un chiffre seul désigne toutes les classes de ce
+/// nombre de voisins.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IsotropicRule {
+    birth: Vec<bool>,
+    survival: Vec<bool>,
+}
+
+impl IsotropicRule {
+    /// Interprète une règle en notation de Hensel.
+    ///
+    /// # Erreurs
+    ///
+    /// Renvoie une `RuleParseError` si la chaîne est mal formée ou si une lettre n'existe pas
+    /// pour le nombre de voisins qui la précède.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::IsotropicRule;
+    ///
+    /// let rule = IsotropicRule::parse("B2n3/S23-q").unwrap();
+    /// assert!(rule.is_birth(2, 'n'));
+    /// assert!(!rule.is_birth(2, 'a'));
+    /// assert!(rule.is_survival(3, 'a'));
+    /// assert!(!rule.is_survival(3, 'q'));
+    /// ```
+    pub fn parse(rulestring: &str) -> Result<Self, RuleParseError> {
+        let lower = rulestring.trim().to_ascii_lowercase();
+        let (birth, survival) = match (lower.find('b'), lower.find('s')) {
+            (Some(b), Some(s)) if b < s => (&lower[b + 1..s], &lower[s + 1..]),
+            (Some(b), Some(s)) => (&lower[b + 1..], &lower[s + 1..b]),
+            _ => {
+                return Err(RuleParseError::new(format!(
+                    "`{}` is not in B/S notation",
+                    rulestring
+                )))
+            }
+        };
+        Ok(IsotropicRule {
+            birth: parse_classes(birth.trim_end_matches('/'))?,
+            survival: parse_classes(survival.trim_end_matches('/'))?,
+        })
+    }
+
+    /// Indique si une cellule morte dont le voisinage appartient à la classe `count` + `letter` naît.
+    pub fn is_birth(&self, count: usize, letter: char) -> bool {
+        class_id(count, Some(letter)).is_some_and(|id| self.birth[id])
+    }
+
+    /// Indique si une cellule vivante dont le voisinage appartient à la classe `count` + `letter` survit.
+    pub fn is_survival(&self, count: usize, letter: char) -> bool {
+        class_id(count, Some(letter)).is_some_and(|id| self.survival[id])
+    }
+
+    /// Calcule l'état suivant d'une cellule à partir de l'index à 9 bits de son voisinage.
+    ///
+    /// Une cellule qui survit conserve sa valeur, une cellule qui naît prend la valeur 1.
+    pub fn next_state(&self, cell: u8, index: usize) -> u8 {
+        let class = isotropic_class_table()[index] as usize;
+        if cell >= 1 {
+            if self.survival[class] {
+                cell
+            } else {
+                0
+            }
+        } else if self.birth[class] {
+            1
+        } else {
+            0
+        }
+    }
+}
+
+impl FromStr for IsotropicRule {
+    type Err = RuleParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        IsotropicRule::parse(s)
+    }
+}
+
+impl fmt::Display for IsotropicRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "B{}/S{}", format_classes(&self.birth), format_classes(&self.survival))
+    }
+}
+
+/// Interprète la partie naissance ou survie d'une règle en notation de Hensel.
+fn parse_classes(part: &str) -> Result<Vec<bool>, RuleParseError> {
+    let mut classes = vec![false; ISOTROPIC_CLASS_COUNT];
+    let mut chars = part.chars().peekable();
+    while let Some(c) = chars.next() {
+        let count = match c.to_digit(10) {
+            Some(count) if count <= 8 => count as usize,
+            _ => return Err(RuleParseError::new(format!("unexpected character `{}`", c))),
+        };
+        let negate = chars.next_if_eq(&'-').is_some();
+        let mut letters = String::new();
+        while let Some(letter) = chars.next_if(|c| c.is_ascii_alphabetic()) {
+            if !LETTERS[count].contains(letter) {
+                return Err(RuleParseError::new(format!(
+                    "no class `{}{}` in Hensel notation",
+                    count, letter
+                )));
+            }
+            letters.push(letter);
+        }
+        if negate && letters.is_empty() {
+            return Err(RuleParseError::new(format!("missing letters after `{}-`", count)));
+        }
+        if LETTERS[count].is_empty() {
+            classes[class_id(count, None).expect("class without letters")] = true;
+            continue;
+        }
+        for letter in LETTERS[count].chars() {
+            let listed = letters.contains(letter);
+            if letters.is_empty() || listed != negate {
+                classes[class_id(count, Some(letter)).expect("letter belongs to the count")] = true;
+            }
+        }
+    }
+    Ok(classes)
+}
+
+/// Écrit les classes retenues sous la forme la plus courte de la notation de Hensel.
+fn format_classes(classes: &[bool]) -> String {
+    let mut result = String::new();
+    for (count, letters) in LETTERS.iter().enumerate() {
+        if letters.is_empty() {
+            if classes[class_id(count, None).expect("class without letters")] {
+                result.push_str(&count.to_string());
+            }
+            continue;
+        }
+        let (included, excluded): (String, String) = (
+            letters.chars().filter(|&l| classes[class_id(count, Some(l)).unwrap()]).collect(),
+            letters.chars().filter(|&l| !classes[class_id(count, Some(l)).unwrap()]).collect(),
+        );
+        if included.is_empty() {
+            continue;
+        }
+        result.push_str(&count.to_string());
+        if excluded.is_empty() {
+            continue;
+        }
+        if excluded.len() < included.len() {
+            result.push('-');
+            result.push_str(&excluded);
+        } else {
+            result.push_str(&included);
+        }
+    }
+    result
+}
+
+/// Indique si une chaîne de règle utilise les lettres de la notation de Hensel.
+pub(crate) fn is_hensel_rulestring(rulestring: &str) -> bool {
+    rulestring
+        .to_ascii_lowercase()
+        .chars()
+        .any(|c| "cekainyqjrtwz-".contains(c))
+}
+
+/// Met à jour l'état de la grille selon une règle isotrope non totalistique.
+///
+/// # Arguments
+///
+/// * `current_cells` - Vecteur contenant l'état actuel de chaque cellule de la grille.
+/// * `next_cells` - Vecteur contenant l'état suivant de chaque cellule de la grille.
+/// * `rows` - Nombre de lignes de la grille.
+/// * `cols` - Nombre de colonnes de la grille.
+/// * `topology` - La topologie de la grille.
+/// * `rule` - La règle à appliquer.
+pub fn grid_update_isotropic(
+    current_cells: &mut Vec<u8>,
+    next_cells: &mut Vec<u8>,
+    rows: usize,
+    cols: usize,
+    topology: impl Into<Topology>,
+    rule: &IsotropicRule,
+) {
+    let topology = topology.into();
+    for row in 0..rows {
+        for col in 0..cols {
+            let current_index = grid_index(row, col, cols);
+            let index = grid_moore_index(row, col, current_cells, rows, cols, topology);
+            next_cells[current_index] = rule.next_state(current_cells[current_index], index);
+        }
+    }
+    std::mem::swap(current_cells, next_cells);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{grid_update_rule, LifeRule};
+    use rand::prelude::*;
+
+    #[test]
+    fn test_class_table_covers_51_classes() {
+        let table = isotropic_class_table();
+        let mut sizes = [0usize; ISOTROPIC_CLASS_COUNT];
+        for index in 0..256usize {
+            // Index sans la cellule centrale : les 8 voisins occupent les bits 0-3 et 5-8
+            let index = (index & 0b1111) | (index & !0b1111) << 1;
+            sizes[table[index] as usize] += 1;
+            assert_eq!(table[index], table[index | 1 << CENTER_BIT]);
+            let count = (index as u32).count_ones() as usize;
+            let first = class_id(count, LETTERS[count].chars().next()).unwrap();
+            let classes = LETTERS[count].len().max(1);
+            assert!((first..first + classes).contains(&(table[index] as usize)));
+        }
+        assert!(sizes.iter().all(|&size| size > 0));
+        assert_eq!(sizes.iter().sum::<usize>(), 256);
+        // Quelques tailles de classes connues
+        assert_eq!(sizes[class_id(2, Some('i')).unwrap()], 2);
+        assert_eq!(sizes[class_id(3, Some('j')).unwrap()], 8);
+        assert_eq!(sizes[class_id(4, Some('c')).unwrap()], 1);
+        assert_eq!(sizes[class_id(4, Some('t')).unwrap()], 4);
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let rule = IsotropicRule::parse("B2n3/S23-q").unwrap();
+        assert_eq!(rule.to_string(), "B2n3/S23-q");
+        assert!(rule.is_survival(2, 'k'));
+        assert!(!rule.is_birth(4, 'a'));
+        assert_eq!(IsotropicRule::parse("B2-ak3/S0").unwrap().to_string(), "B2-ka3/S0");
+        assert!(IsotropicRule::parse("B2z/S23").is_err());
+        assert!(IsotropicRule::parse("B2-/S23").is_err());
+    }
+
+    #[test]
+    fn test_totalistic_rule_matches_life_rule() {
+        let (rows, cols) = (16, 16);
+        let mut rng = StdRng::seed_from_u64(3);
+        let cells: Vec<u8> = (0..rows * cols).map(|_| rng.gen_range(0..2)).collect();
+
+        let isotropic = IsotropicRule::parse("B3cekainyqjr/S2cekain3").unwrap();
+        let (mut current, mut next) = (cells.clone(), vec![0; rows * cols]);
+        let (mut expected, mut expected_next) = (cells, vec![0; rows * cols]);
+        for _ in 0..10 {
+            grid_update_isotropic(&mut current, &mut next, rows, cols, true, &isotropic);
+            grid_update_rule(&mut expected, &mut expected_next, rows, cols, true, &LifeRule::conway());
+        }
+        assert_eq!(current, expected);
+    }
+
+    #[test]
+    fn test_excluded_class_changes_update() {
+        // Une cellule morte avec trois voisins en ligne (classe 3i) ne naît pas en B3-i
+        #[rustfmt::skip]
+        let mut current_cells = vec![
+            1, 1, 1,
+            0, 0, 0,
+            0, 0, 0,
+        ];
+        let mut next_cells = vec![0; 9];
+        let rule = IsotropicRule::parse("B3-i/S23").unwrap();
+        grid_update_isotropic(&mut current_cells, &mut next_cells, 3, 3, false, &rule);
+        assert_eq!(current_cells[4], 0);
+    }
+}
@@ -0,0 +1,481 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use crate::{grid_index, Neighborhood, RuleParseError, Topology};
+
+/// Forme du voisinage d'une règle Larger than Life.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LtlNeighborhood {
+    /// Carré de côté `2r + 1` (`NM`).
+    Moore,
+    /// Losange des cellules à une distance de Manhattan au plus `r` (`NN`).
+    VonNeumann,
+}
+
+/// Règle Larger than Life (voisinage de rayon `r`), écrite `R5,C0,M1,S34..58,B34..45,NM`.
+///
+/// * `R` - Le rayon du voisinage.
+/// * `C` - Le nombre d'états (0 ou 2 pour une règle à deux états, plus pour des états de déclin).
+/// * `M` - Indique si la cellule centrale est comptée (`1`) ou non (`0`).
+/// * `S` - L'intervalle de voisins vivants permettant la survie.
+/// * `B` - L'intervalle de voisins vivants provoquant une naissance.
+/// * `N` - Le voisinage : `M` pour Moore, `N` pour von Neumann.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LtlRule {
+    range: usize,
+    states: u8,
+    middle: bool,
+    survival: RangeInclusive<usize>,
+    birth: RangeInclusive<usize>,
+    neighborhood: LtlNeighborhood,
+}
+
+impl LtlRule {
+    /// Crée une règle Larger than Life.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - Le rayon du voisinage.
+    /// * `states` - Le nombre d'états, 0 et 2 désignant une règle à deux états.
+    /// * `middle` - Indique si la cellule centrale est comptée parmi les voisins.
+    /// * `survival` - L'intervalle de voisins vivants permettant la survie.
+    /// * `birth` - L'intervalle de voisins vivants provoquant une naissance.
+    /// * `neighborhood` - La forme du voisinage.
+    pub fn new(
+        range: usize,
+        states: u8,
+        middle: bool,
+        survival: RangeInclusive<usize>,
+        birth: RangeInclusive<usize>,
+        neighborhood: LtlNeighborhood,
+    ) -> Self {
+        LtlRule {
+            range,
+            states: states.max(2),
+            middle,
+            survival,
+            birth,
+            neighborhood,
+        }
+    }
+
+    /// Renvoie la règle de Bosco (`R5,C0,M1,S34..58,B34..45,NM`).
+    pub fn bosco() -> Self {
+        LtlRule::new(5, 2, true, 34..=58, 34..=45, LtlNeighborhood::Moore)
+    }
+
+    /// Interprète une règle Larger than Life au format `R5,C0,M1,S34..58,B34..45,NM`.
+    ///
+    /// # Erreurs
+    ///
+    /// Renvoie une `RuleParseError` si un champ est manquant ou mal formé.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::LtlRule;
+    ///
+    /// let rule = LtlRule::parse("R5,C0,M1,S34..58,B34..45,NM").unwrap();
+    /// assert_eq!(rule, LtlRule::bosco());
+    /// assert_eq!(rule.range(), 5);
+    /// ```
+    pub fn parse(rulestring: &str) -> Result<Self, RuleParseError> {
+        let mut range = None;
+        let mut states = 2;
+        let mut middle = false;
+        let mut survival = None;
+        let mut birth = None;
+        let mut neighborhood = LtlNeighborhood::Moore;
+
+        for field in rulestring.trim().split(',') {
+            let field = field.trim();
+            let mut chars = field.chars();
+            let key = chars.next().map(|c| c.to_ascii_uppercase());
+            let value = chars.as_str();
+            match key {
+                Some('R') => range = Some(parse_number(field, value)?),
+                Some('C') => states = parse_number(field, value)?,
+                Some('M') => middle = parse_number::<u8>(field, value)? != 0,
+                Some('S') => survival = Some(parse_range(field, value)?),
+                Some('B') => birth = Some(parse_range(field, value)?),
+                Some('N') => {
+                    neighborhood = match value.to_ascii_uppercase().as_str() {
+                        "M" => LtlNeighborhood::Moore,
+                        "N" => LtlNeighborhood::VonNeumann,
+                        _ => return Err(RuleParseError::new(format!("unknown neighbourhood `{}`", field))),
+                    }
+                }
+                _ => return Err(RuleParseError::new(format!("unexpected field `{}`", field))),
+            }
+        }
+
+        let missing = |name: &str| RuleParseError::new(format!("missing `{}` field", name));
+        Ok(LtlRule::new(
+            range.ok_or_else(|| missing("R"))?,
+            states,
+            middle,
+            survival.ok_or_else(|| missing("S"))?,
+            birth.ok_or_else(|| missing("B"))?,
+            neighborhood,
+        ))
+    }
+
+    /// Renvoie le rayon du voisinage.
+    pub fn range(&self) -> usize {
+        self.range
+    }
+
+    /// Renvoie le nombre d'états de la règle.
+    pub fn states(&self) -> u8 {
+        self.states
+    }
+
+    /// Renvoie la forme du voisinage.
+    pub fn ltl_neighborhood(&self) -> LtlNeighborhood {
+        self.neighborhood
+    }
+
+    /// Renvoie le voisinage équivalent sous forme de masque, sans la cellule centrale.
+    pub fn neighborhood(&self) -> Neighborhood {
+        let side = 2 * self.range + 1;
+        let r = self.range as isize;
+        let mask = (0..side * side)
+            .map(|index| {
+                let di = (index / side) as isize - r;
+                let dj = (index % side) as isize - r;
+                match self.neighborhood {
+                    LtlNeighborhood::Moore => true,
+                    LtlNeighborhood::VonNeumann => di.abs() + dj.abs() <= r,
+                }
+            })
+            .collect();
+        Neighborhood::custom(self.range, mask)
+    }
+
+    /// Indique si une cellule est comptée comme vivante par la règle.
+    ///
+    /// Avec plus de deux états, seules les cellules dans l'état 1 sont vivantes, les autres déclinent.
+    pub fn is_alive(&self, cell: u8) -> bool {
+        if self.states > 2 {
+            cell == 1
+        } else {
+            cell >= 1
+        }
+    }
+
+    /// Calcule l'état suivant d'une cellule à partir de son état et du nombre de cellules vivantes
+    /// dans son voisinage (la cellule centrale étant incluse si `M1`).
+    pub fn next_state(&self, cell: u8, count: usize) -> u8 {
+        if self.is_alive(cell) {
+            if self.survival.contains(&count) {
+                cell
+            } else if self.states > 2 {
+                2
+            } else {
+                0
+            }
+        } else if cell >= 2 {
+            // Cellule en déclin : elle passe à l'état suivant puis meurt
+            (cell + 1) % self.states
+        } else if self.birth.contains(&count) {
+            1
+        } else {
+            0
+        }
+    }
+}
+
+impl FromStr for LtlRule {
+    type Err = RuleParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        LtlRule::parse(s)
+    }
+}
+
+impl fmt::Display for LtlRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "R{},C{},M{},S{}..{},B{}..{},N{}",
+            self.range,
+            if self.states == 2 { 0 } else { self.states },
+            self.middle as u8,
+            self.survival.start(),
+            self.survival.end(),
+            self.birth.start(),
+            self.birth.end(),
+            match self.neighborhood {
+                LtlNeighborhood::Moore => 'M',
+                LtlNeighborhood::VonNeumann => 'N',
+            }
+        )
+    }
+}
+
+/// Interprète la valeur numérique d'un champ de règle.
+fn parse_number<T: FromStr>(field: &str, value: &str) -> Result<T, RuleParseError> {
+    value
+        .parse()
+        .map_err(|_| RuleParseError::new(format!("invalid number in `{}`", field)))
+}
+
+/// Interprète un intervalle `a..b` ou une valeur unique `a`.
+fn parse_range(field: &str, value: &str) -> Result<RangeInclusive<usize>, RuleParseError> {
+    match value.split_once("..") {
+        Some((start, end)) => Ok(parse_number(field, start)?..=parse_number(field, end)?),
+        None => {
+            let count = parse_number(field, value)?;
+            Ok(count..=count)
+        }
+    }
+}
+
+/// Table des sommes cumulées (summed-area table) des cellules vivantes d'une grille.
+///
+/// La table couvre la grille étendue d'une marge de `margin` cellules de chaque côté : sur une
+/// grille torique, la marge reprend les cellules du bord opposé, sinon elle est morte. La somme
+/// de n'importe quel rectangle s'obtient alors en temps constant.
+pub struct SummedAreaTable {
+    sums: Vec<u32>,
+    width: usize,
+    margin: usize,
+}
+
+impl SummedAreaTable {
+    /// Construit la table des sommes cumulées d'une grille.
+    ///
+    /// # Arguments
+    ///
+    /// * `current_cells` - Vecteur contenant l'état actuel de chaque cellule de la grille.
+    /// * `rows` - Nombre de lignes de la grille.
+    /// * `cols` - Nombre de colonnes de la grille.
+    /// * `topology` - La topologie de la grille.
+    /// * `margin` - La marge autour de la grille, au moins égale au rayon du voisinage.
+    /// * `is_alive` - Indique si une valeur de cellule est comptée comme vivante.
+    pub fn new(
+        current_cells: &[u8],
+        rows: usize,
+        cols: usize,
+        topology: impl Into<Topology>,
+        margin: usize,
+        is_alive: impl Fn(u8) -> bool,
+    ) -> Self {
+        let topology = topology.into();
+        let height = rows + 2 * margin;
+        let width = cols + 2 * margin;
+        // La table a une ligne et une colonne supplémentaires de zéros en tête
+        let mut sums = vec![0u32; (height + 1) * (width + 1)];
+        for i in 0..height {
+            let mut row_sum = 0;
+            for j in 0..width {
+                let row = i as isize - margin as isize;
+                let col = j as isize - margin as isize;
+                let alive = is_alive(topology.state_at(current_cells, row, col, rows, cols));
+                row_sum += alive as u32;
+                sums[(i + 1) * (width + 1) + j + 1] = sums[i * (width + 1) + j + 1] + row_sum;
+            }
+        }
+        SummedAreaTable {
+            sums,
+            width,
+            margin,
+        }
+    }
+
+    /// Renvoie le nombre de cellules vivantes dans le rectangle `[row0, row1] x [col0, col1]`.
+    ///
+    /// Les coordonnées sont celles de la grille et peuvent en dépasser les bords d'au plus `margin` cellules.
+    pub fn rect_sum(&self, row0: isize, col0: isize, row1: isize, col1: isize) -> usize {
+        let at = |i: isize, j: isize| {
+            let i = (i + self.margin as isize) as usize;
+            let j = (j + self.margin as isize) as usize;
+            self.sums[i * (self.width + 1) + j]
+        };
+        (at(row1 + 1, col1 + 1) + at(row0, col0) - at(row0, col1 + 1) - at(row1 + 1, col0)) as usize
+    }
+}
+
+/// Calcule l'état suivant d'une cellule en comptant directement ses voisins, sans table des
+/// sommes cumulées, pour les mises à jour cellule par cellule.
+///
+/// # Arguments
+///
+/// * `row` - L'indice de la ligne de la cellule dans la grille.
+/// * `col` - L'indice de la colonne de la cellule dans la grille.
+/// * `current_cells` - Vecteur contenant l'état actuel de chaque cellule de la grille.
+/// * `rows` - Nombre de lignes de la grille.
+/// * `cols` - Nombre de colonnes de la grille.
+/// * `topology` - La topologie de la grille.
+/// * `rule` - La règle à appliquer.
+pub fn ltl_next_cell_state(
+    row: usize,
+    col: usize,
+    current_cells: &[u8],
+    rows: usize,
+    cols: usize,
+    topology: impl Into<Topology>,
+    rule: &LtlRule,
+) -> u8 {
+    let topology = topology.into();
+    let r = rule.range as isize;
+    let cell = current_cells[grid_index(row, col, cols)];
+    let mut count = 0;
+    for di in -r..=r {
+        for dj in -r..=r {
+            let inside = match rule.neighborhood {
+                LtlNeighborhood::Moore => true,
+                LtlNeighborhood::VonNeumann => di.abs() + dj.abs() <= r,
+            };
+            if !inside || (di == 0 && dj == 0 && !rule.middle) {
+                continue;
+            }
+            let state = topology.state_at(current_cells, row as isize + di, col as isize + dj, rows, cols);
+            if rule.is_alive(state) {
+                count += 1;
+            }
+        }
+    }
+    rule.next_state(cell, count)
+}
+
+/// Compte les cellules vivantes du voisinage de rayon `r` d'une cellule à l'aide d'une table des sommes cumulées.
+///
+/// Le voisinage de Moore est un unique rectangle ; le voisinage de von Neumann est sommé ligne par
+/// ligne, soit `O(r)` opérations au lieu de `O(r²)`.
+pub fn ltl_count(table: &SummedAreaTable, row: usize, col: usize, rule: &LtlRule) -> usize {
+    let (row, col, r) = (row as isize, col as isize, rule.range as isize);
+    match rule.neighborhood {
+        LtlNeighborhood::Moore => table.rect_sum(row - r, col - r, row + r, col + r),
+        LtlNeighborhood::VonNeumann => (-r..=r)
+            .map(|di| {
+                let width = r - di.abs();
+                table.rect_sum(row + di, col - width, row + di, col + width)
+            })
+            .sum(),
+    }
+}
+
+/// Met à jour l'état de la grille selon une règle Larger than Life.
+///
+/// # Arguments
+///
+/// * `current_cells` - Vecteur contenant l'état actuel de chaque cellule de la grille.
+/// * `next_cells` - Vecteur contenant l'état suivant de chaque cellule de la grille.
+/// * `rows` - Nombre de lignes de la grille.
+/// * `cols` - Nombre de colonnes de la grille.
+/// * `topology` - La topologie de la grille.
+/// * `rule` - La règle à appliquer.
+///
+/// # Exemple
+///
+/// ```
+/// use crate::lifers::{grid_update_ltl, LtlRule};
+///
+/// let mut current_cells = vec![1; 25];
+/// let mut next_cells = vec![0; 25];
+///
+/// // Avec R2, toutes les cellules d'une grille torique 5x5 pleine ont 24 voisins (25 avec M1)
+/// let rule = LtlRule::parse("R2,C0,M1,S25..25,B1..1,NM").unwrap();
+/// grid_update_ltl(&mut current_cells, &mut next_cells, 5, 5, true, &rule);
+/// assert_eq!(current_cells, vec![1; 25]);
+/// ```
+pub fn grid_update_ltl(
+    current_cells: &mut Vec<u8>,
+    next_cells: &mut Vec<u8>,
+    rows: usize,
+    cols: usize,
+    topology: impl Into<Topology>,
+    rule: &LtlRule,
+) {
+    let table = SummedAreaTable::new(current_cells, rows, cols, topology, rule.range, |cell| {
+        rule.is_alive(cell)
+    });
+    for row in 0..rows {
+        for col in 0..cols {
+            let index = grid_index(row, col, cols);
+            let cell = current_cells[index];
+            let mut count = ltl_count(&table, row, col, rule);
+            if !rule.middle && rule.is_alive(cell) {
+                count -= 1;
+            }
+            next_cells[index] = rule.next_state(cell, count);
+        }
+    }
+    std::mem::swap(current_cells, next_cells);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid_count_neighbors_in;
+    use rand::prelude::*;
+
+    fn random_cells(rows: usize, cols: usize, seed: u64) -> Vec<u8> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..rows * cols).map(|_| rng.gen_range(0..2)).collect()
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let rule = LtlRule::parse("R5,C0,M1,S34..58,B34..45,NM").unwrap();
+        assert_eq!(rule.to_string(), "R5,C0,M1,S34..58,B34..45,NM");
+        let rule = LtlRule::parse("R3,C4,M0,S2..5,B3,NN").unwrap();
+        assert_eq!(rule.states(), 4);
+        assert_eq!(rule.ltl_neighborhood(), LtlNeighborhood::VonNeumann);
+        assert_eq!(rule.to_string(), "R3,C4,M0,S2..5,B3..3,NN");
+        assert!(LtlRule::parse("R5,C0,M1,S34..58").is_err());
+        assert!(LtlRule::parse("R5,C0,M1,S34..x,B34..45,NM").is_err());
+    }
+
+    #[test]
+    fn test_summed_area_count_matches_naive_count() {
+        let (rows, cols) = (17, 13);
+        let cells = random_cells(rows, cols, 7);
+        for rulestring in ["R5,C0,M0,S1..2,B3..4,NM", "R4,C0,M0,S1..2,B3..4,NN"] {
+            let rule = LtlRule::parse(rulestring).unwrap();
+            let neighborhood = rule.neighborhood();
+            for toricgrid in [true, false] {
+                let table = SummedAreaTable::new(&cells, rows, cols, toricgrid, rule.range(), |c| c >= 1);
+                for row in 0..rows {
+                    for col in 0..cols {
+                        let center = cells[grid_index(row, col, cols)] as usize;
+                        let naive = grid_count_neighbors_in(row, col, &cells, rows, cols, toricgrid, &neighborhood);
+                        assert_eq!(ltl_count(&table, row, col, &rule), naive + center);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_bosco_update_matches_naive_update() {
+        let (rows, cols) = (24, 20);
+        let rule = LtlRule::bosco();
+        let neighborhood = rule.neighborhood();
+        for toricgrid in [true, false] {
+            let mut current_cells = random_cells(rows, cols, 11);
+            let mut next_cells = vec![0; rows * cols];
+            let expected: Vec<u8> = (0..rows * cols)
+                .map(|index| {
+                    let (row, col) = (index / cols, index % cols);
+                    let cell = current_cells[index];
+                    let count = grid_count_neighbors_in(row, col, &current_cells, rows, cols, toricgrid, &neighborhood);
+                    rule.next_state(cell, count + cell as usize)
+                })
+                .collect();
+            grid_update_ltl(&mut current_cells, &mut next_cells, rows, cols, toricgrid, &rule);
+            assert_eq!(current_cells, expected);
+        }
+    }
+
+    #[test]
+    fn test_decaying_states() {
+        let rule = LtlRule::parse("R1,C4,M0,S2..3,B3..3,NM").unwrap();
+        assert_eq!(rule.next_state(1, 1), 2);
+        assert_eq!(rule.next_state(2, 3), 3);
+        assert_eq!(rule.next_state(3, 3), 0);
+        assert_eq!(rule.next_state(0, 3), 1);
+    }
+}
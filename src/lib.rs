@@ -5,4 +5,82 @@ mod life;
 pub use life::*;
 
 mod gui;
-pub use gui::*;
\ No newline at end of file
+pub use gui::*;
+
+mod tui;
+pub use tui::*;
+
+mod neighborhood;
+pub use neighborhood::*;
+
+mod rule;
+pub use rule::*;
+
+mod larger_than_life;
+pub use larger_than_life::*;
+
+mod isotropic;
+pub use isotropic::*;
+
+mod wireworld;
+pub use wireworld::*;
+
+mod ruletable;
+pub use ruletable::*;
+
+mod elementary;
+pub use elementary::*;
+
+mod margolus;
+pub use margolus::*;
+
+mod topology;
+pub use topology::*;
+
+mod stochastic;
+pub use stochastic::*;
+
+mod update_scheme;
+pub use update_scheme::*;
+
+mod multicolor;
+pub use multicolor::*;
+
+mod census;
+pub use census::*;
+
+mod apgcode;
+pub use apgcode::*;
+
+mod search;
+pub use search::*;
+
+mod predecessor;
+pub use predecessor::*;
+
+mod rle;
+pub use rle::*;
+
+mod ship_search;
+pub use ship_search::*;
+
+mod emitter;
+pub use emitter::*;
+
+mod synthesis;
+pub use synthesis::*;
+
+mod heatmap;
+pub use heatmap::*;
+
+mod render;
+pub use render::*;
+
+mod theme;
+pub use theme::*;
+
+mod resize;
+pub use resize::*;
+
+mod minimap;
+pub use minimap::*;
\ No newline at end of file
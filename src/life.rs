@@ -2,6 +2,8 @@ use std::fmt;
 
 use ggez::{graphics::{self, Color, Canvas}, Context, GameResult};
 
+use crate::{cell_mesh, Anchor, CellStyle, LifeRule, Neighborhood, Topology};
+
 pub trait Grid {
     // Méthode pour afficher la grille
     fn display(&self);
@@ -12,9 +14,9 @@ pub trait Grid {
     ///
     /// * `rows` - Le nombre de lignes de la grille.
     /// * `cols` - Le nombre de colonnes de la grille.
-    /// * `toricgrid` - Indique si les bords de la grille sont connectés, formant une grille torique.
+    /// * `topology` - La topologie de la grille.
     ///
-    fn new(rows: usize, cols: usize, toricgrid: bool) -> Self;
+    fn new(rows: usize, cols: usize, topology: impl Into<Topology>) -> Self;
 
     /// Initialise une nouvelle grille du jeu de la vie avec des cellules dont l'état est aléatoire.
     ///
@@ -22,12 +24,12 @@ pub trait Grid {
     ///
     /// * `rows` - Le nombre de lignes de la grille.
     /// * `cols` - Le nombre de colonnes de la grille.
-    /// * `toricgrid` - Le nombre de colonnes de la grille.
+    /// * `topology` - La topologie de la grille.
     ///
-    fn new_random(rows: usize, cols: usize, toricgrid: bool) -> Self;
+    fn new_random(rows: usize, cols: usize, topology: impl Into<Topology>) -> Self;
     
     /// Méthode pour créer une grille à partir d'un vecteur de cellules
-    fn from_vect(cels: Vec<u8>, rows: usize, cols: usize, toricgrid: bool) -> Self ;
+    fn from_vect(cels: Vec<u8>, rows: usize, cols: usize, topology: impl Into<Topology>) -> Self ;
 
     /// Setter pour la couleur des cellules vivantes
     fn set_color_alive(&mut self, color: Option<Color>) ;
@@ -41,6 +43,17 @@ pub trait Grid {
     /// Méthode pour renvoyer le nombre de colonnes de la grille
     fn cols(&self) -> usize;
 
+    /// Redimensionne la grille en conservant ses cellules (voir `grid_resize`).
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - Le nouveau nombre de lignes de la grille.
+    /// * `cols` - Le nouveau nombre de colonnes de la grille.
+    /// * `anchor` - Le point de la grille qui reste fixe ; les lignes et les colonnes sont ajoutées
+    ///   ou retirées du côté opposé.
+    ///
+    fn resize(&mut self, rows: usize, cols: usize, anchor: Anchor);
+
     /// Méthode pour indiquer si les bords de la grille sont connectés (forme une grille torique)
     fn is_toricgrid(&self) -> bool;
 
@@ -68,6 +81,20 @@ pub trait Grid {
     ///
     fn toggle_cell_state(&mut self, row: usize, col: usize);
 
+    /// Fait passer une cellule spécifiée à l'état suivant lors de l'édition.
+    ///
+    /// Pour une grille à deux états, cela revient à inverser l'état de la cellule. Les grilles
+    /// à plusieurs états redéfinissent cette méthode pour parcourir tous leurs états.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - L'indice de la ligne de la cellule dans la grille.
+    /// * `col` - L'indice de la colonne de la cellule dans la grille.
+    ///
+    fn cycle_cell_state(&mut self, row: usize, col: usize) {
+        self.toggle_cell_state(row, col);
+    }
+
     /// Vérifie si une cellule spécifiée dans la grille est vivante.
     ///
     /// # Arguments
@@ -100,6 +127,93 @@ pub trait Grid {
     /// Cette méthode parcourt chaque cellule de la grille, compte ses voisins vivants et applique les règles du jeu pour mettre à jour son état.
     fn update(&mut self);
 
+    /// Renvoie la probabilité qu'une cellule soit inversée à chaque génération.
+    ///
+    /// Les grilles sans mise à jour stochastique renvoient toujours 0.
+    fn noise(&self) -> f64 {
+        0.0
+    }
+
+    /// Modifie la probabilité qu'une cellule soit inversée à chaque génération.
+    ///
+    /// Les grilles sans mise à jour stochastique ignorent cette valeur.
+    fn set_noise(&mut self, _noise: f64) {}
+
+    /// Renvoie le nombre de couleurs qu'une cellule vivante peut prendre (voir `ColorMode`).
+    ///
+    /// Les grilles monochromes renvoient toujours 1.
+    fn colors(&self) -> u8 {
+        1
+    }
+
+    /// Renvoie la règle `B/S` de la grille, utilisée par les analyses de motifs (voir `grid_emissions`).
+    ///
+    /// Les grilles dont la règle n'est pas une règle `B/S` renvoient `None`.
+    fn life_rule(&self) -> Option<LifeRule> {
+        None
+    }
+
+    /// Modifie la couleur de chaque état de cellule, `palette[0]` étant celle des cellules mortes
+    /// et `palette[1]` celle des cellules vivantes ; une couleur `None` est transparente.
+    ///
+    /// Par défaut, seules les couleurs des états 0 et 1 sont utilisées. Les grilles à plusieurs
+    /// états redéfinissent cette méthode pour colorer leurs autres états.
+    fn set_state_colors(&mut self, palette: &[Option<Color>]) {
+        if let Some(&color) = palette.first() {
+            self.set_color_not_alive(color);
+        }
+        if let Some(&color) = palette.get(1) {
+            self.set_color_alive(color);
+        }
+    }
+
+    /// Renvoie le style (espacement et arrondi) des cellules dessinées.
+    ///
+    /// Les grilles sans style configurable renvoient le style par défaut, sans espacement ni arrondi.
+    fn cell_style(&self) -> CellStyle {
+        CellStyle::default()
+    }
+
+    /// Modifie le style des cellules dessinées.
+    ///
+    /// Les grilles sans style configurable ignorent cette valeur.
+    fn set_cell_style(&mut self, _style: CellStyle) {}
+
+    /// Indique si la grille est dessinée en grille hexagonale, les lignes impaires étant décalées
+    /// d'une demi-cellule vers la droite (voir `draw_hex_grid`).
+    fn is_hexagonal(&self) -> bool {
+        false
+    }
+
+    /// Renvoie la cellule `(ligne, colonne)` dessinée au point (`x`, `y`), en pixels depuis le coin
+    /// supérieur gauche de la grille, ou `None` si ce point est hors de la grille.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::{ConwaysGrid, Grid, LifeRule};
+    ///
+    /// let mut grid = ConwaysGrid::new(4, 4, false);
+    /// assert_eq!(grid.cell_at(25.0, 15.0, 10.0), Some((1, 2)));
+    /// // Sur une grille hexagonale, la ligne 1 est décalée d'une demi-cellule
+    /// grid.set_rule(LifeRule::parse("B2/S34H").unwrap());
+    /// assert_eq!(grid.cell_at(25.0, 15.0, 10.0), Some((1, 2)));
+    /// assert_eq!(grid.cell_at(22.0, 15.0, 10.0), Some((1, 1)));
+    /// assert_eq!(grid.cell_at(3.0, 15.0, 10.0), None);
+    /// ```
+    fn cell_at(&self, x: f32, y: f32, cell_size: f32) -> Option<(usize, usize)> {
+        if cell_size <= 0.0 || y < 0.0 {
+            return None;
+        }
+        let row = (y / cell_size) as usize;
+        let x = if self.is_hexagonal() && row % 2 == 1 { x - cell_size / 2. } else { x };
+        if x < 0.0 {
+            return None;
+        }
+        let col = (x / cell_size) as usize;
+        (row < self.rows() && col < self.cols()).then_some((row, col))
+    }
+
     /// Dessine la grille en utilisant le contexte `ctx` spécifié et la taille de cellule `cell_size`.
     ///
     /// Cette méthode appelle la fonction `draw_grid` pour dessiner la grille en utilisant le contexte
@@ -186,7 +300,7 @@ pub fn grid_fmt(
     f: &mut fmt::Formatter<'_>,
     rows: usize,
     cols: usize,
-    current_cells: &Vec<u8>,
+    current_cells: &[u8],
 ) -> fmt::Result {
     for row in 0..rows {
         for col in 0..cols {
@@ -238,7 +352,7 @@ pub fn grid_set_cell_state(
     row: usize,
     col: usize,
     alive: u8,
-    current_cells: &mut Vec<u8>,
+    current_cells: &mut [u8],
     cols: usize,
 ) {
     let index: usize = grid_index(row, col, cols);
@@ -277,7 +391,7 @@ pub fn grid_set_cell_state(
 /// // Vérifie si la cellule en haut à gauche est maintenant morte
 /// assert_eq!(current_cells[0], 0);
 /// ```
-pub fn grid_toggle_cell_state(row: usize, col: usize, current_cells: &mut Vec<u8>, cols: usize) {
+pub fn grid_toggle_cell_state(row: usize, col: usize, current_cells: &mut [u8], cols: usize) {
     let index: usize = grid_index(row, col, cols);
     // Inverse l'état de la cellule : de vivante à morte ou de morte à vivante
     current_cells[index] = if current_cells[index] >= 1 { 0 } else { 1 };
@@ -315,7 +429,7 @@ pub fn grid_toggle_cell_state(row: usize, col: usize, current_cells: &mut Vec<u8
 /// // Vérifie si la cellule en bas à droite est vivante
 /// assert_eq!(grid_is_alive(2, 2, &current_cells, cols), false);
 /// ```
-pub fn grid_is_alive(row: usize, col: usize, current_cells: &Vec<u8>, cols: usize) -> bool {
+pub fn grid_is_alive(row: usize, col: usize, current_cells: &[u8], cols: usize) -> bool {
     current_cells[grid_index(row, col, cols)] >= 1
 }
 
@@ -328,7 +442,7 @@ pub fn grid_is_alive(row: usize, col: usize, current_cells: &Vec<u8>, cols: usiz
 /// * `current_cells` - Vecteur contenant l'état actuel de chaque cellule de la grille.
 /// * `rows` - Nombre de lignes de la grille.
 /// * `cols` - Nombre de colonnes de la grille.
-/// * `toricgrid` - Indique si les bords de la grille sont connectés, formant une grille torique.
+/// * `topology` - La topologie de la grille.
 ///
 /// # Returns
 ///
@@ -357,46 +471,69 @@ pub fn grid_count_neighbors(
     current_cells: &[u8],
     rows: usize,
     cols: usize,
-    toricgrid: bool,
+    topology: impl Into<Topology>,
+) -> usize {
+    grid_count_neighbors_in(
+        row,
+        col,
+        current_cells,
+        rows,
+        cols,
+        topology,
+        &Neighborhood::Moore,
+    )
+}
+
+/// Compte le nombre de voisins vivants d'une cellule dans le voisinage spécifié.
+///
+/// # Arguments
+///
+/// * `row` - L'indice de la ligne de la cellule dans la grille.
+/// * `col` - L'indice de la colonne de la cellule dans la grille.
+/// * `current_cells` - Vecteur contenant l'état actuel de chaque cellule de la grille.
+/// * `rows` - Nombre de lignes de la grille.
+/// * `cols` - Nombre de colonnes de la grille.
+/// * `topology` - La topologie de la grille.
+/// * `neighborhood` - Le voisinage à utiliser (Moore, von Neumann, hexagonal ou personnalisé).
+///
+/// # Returns
+///
+/// Le nombre de voisins vivants de la cellule spécifiée.
+///
+/// # Exemple
+///
+/// ```
+/// use crate::lifers::{grid_count_neighbors_in, Neighborhood};
+///
+/// let current_cells = vec![
+///     1, 0, 1,
+///     0, 0, 1,
+///     0, 1, 0,
+/// ];
+///
+/// // Seuls les voisins orthogonaux sont comptés dans le voisinage de von Neumann
+/// let neighbors_count = grid_count_neighbors_in(1, 1, &current_cells, 3, 3, false, &Neighborhood::VonNeumann);
+/// assert_eq!(neighbors_count, 2);
+/// ```
+pub fn grid_count_neighbors_in(
+    row: usize,
+    col: usize,
+    current_cells: &[u8],
+    rows: usize,
+    cols: usize,
+    topology: impl Into<Topology>,
+    neighborhood: &Neighborhood,
 ) -> usize {
+    let topology = topology.into();
     let mut count = 0;
     // Parcours des cellules voisines de la cellule spécifiée
-    for i in (row as isize - 1)..=(row as isize + 1) {
-        for j in (col as isize - 1)..=(col as isize + 1) {
-            if toricgrid {
-                // Si la grille est torique, ajuste les indices des bords
-                let mut i_wrapped = i;
-                let mut j_wrapped = j;
-                // Ajustement des indices pour les bords
-                if i_wrapped < 0 {
-                    i_wrapped = rows as isize - 1;
-                } else if i_wrapped >= rows as isize {
-                    i_wrapped = 0;
-                }
-                if j_wrapped < 0 {
-                    j_wrapped = cols as isize - 1;
-                } else if j_wrapped >= cols as isize {
-                    j_wrapped = 0;
-                }
-                let index = grid_index(i_wrapped as usize, j_wrapped as usize, cols);
-                // Vérification et comptage des voisins vivants
-                if !(i == row as isize && j == col as isize) && current_cells[index] >= 1 {
-                    count += 1;
-                }
-            } else {
-                // Si la grille n'est pas torique, on compte les voisins normalement
-                if i >= 0
-                    && i < rows as isize
-                    && j >= 0
-                    && j < cols as isize
-                    && !(i == row as isize && j == col as isize)
-                {
-                    let index = grid_index(i as usize, j as usize, cols);
-                    if current_cells[index] >= 1 {
-                        count += 1;
-                    }
-                }
-            }
+    for &(di, dj) in neighborhood.offsets(row) {
+        let i = row as isize + di;
+        let j = col as isize + dj;
+        // Vérification et comptage des voisins vivants, les cellules hors de la grille prenant
+        // l'état donné par la topologie
+        if topology.state_at(current_cells, i, j, rows, cols) >= 1 {
+            count += 1;
         }
     }
     count
@@ -414,7 +551,7 @@ pub fn grid_count_neighbors(
 ///   pour éviter de copier la grille inutilement à chaque mise à jour.
 /// * `rows` - Nombre de lignes de la grille.
 /// * `cols` - Nombre de colonnes de la grille.
-/// * `toricgrid` - Indique si les bords de la grille sont connectés, formant une grille torique.
+/// * `topology` - La topologie de la grille.
 ///
 /// # Exemple
 ///
@@ -439,23 +576,73 @@ pub fn grid_update(
     next_cells: &mut Vec<u8>,
     rows: usize,
     cols: usize,
-    toricgrid: bool,
+    topology: impl Into<Topology>,
 ) {
+    grid_update_rule(
+        current_cells,
+        next_cells,
+        rows,
+        cols,
+        topology,
+        &LifeRule::conway(),
+    );
+}
+
+/// Met à jour l'état de la grille selon la règle spécifiée.
+///
+/// Le voisinage utilisé pour compter les voisins vivants est celui de la règle.
+///
+/// # Arguments
+///
+/// * `current_cells` - Vecteur contenant l'état actuel de chaque cellule de la grille.
+/// * `next_cells` - Vecteur contenant l'état suivant de chaque cellule de la grille.
+/// * `rows` - Nombre de lignes de la grille.
+/// * `cols` - Nombre de colonnes de la grille.
+/// * `topology` - La topologie de la grille.
+/// * `rule` - La règle à appliquer.
+///
+/// # Exemple
+///
+/// ```
+/// use crate::lifers::{grid_update_rule, LifeRule};
+///
+/// let mut current_cells = vec![
+///     0, 0, 0,
+///     0, 1, 0,
+///     0, 0, 0,
+/// ];
+/// let mut next_cells = vec![0; 9];
+///
+/// // Avec B1/S1V, chaque voisin orthogonal d'une cellule isolée naît
+/// let rule = LifeRule::parse("B1/S1V").unwrap();
+/// grid_update_rule(&mut current_cells, &mut next_cells, 3, 3, false, &rule);
+/// assert_eq!(current_cells, vec![0, 1, 0, 1, 0, 1, 0, 1, 0]);
+/// ```
+pub fn grid_update_rule(
+    current_cells: &mut Vec<u8>,
+    next_cells: &mut Vec<u8>,
+    rows: usize,
+    cols: usize,
+    topology: impl Into<Topology>,
+    rule: &LifeRule,
+) {
+    let topology = topology.into();
     // Parcours de chaque cellule de la grille
     for row in 0..rows {
         for col in 0..cols {
             let current_index = grid_index(row, col, cols); // Calcul de l'index de la cellule actuelle
-            let neighbors_count =
-                grid_count_neighbors(row, col, &current_cells, rows, cols, toricgrid); // Comptage des voisins vivants de la cellule actuelle
-                                                                                       // Application des règles du jeu de la vie pour mettre à jour l'état de la cellule
-            next_cells[current_index] = match (current_cells[current_index], neighbors_count) {
-                // Si la cellule est vivante et a 2 ou 3 voisins vivants, elle reste vivante
-                (cell, 2) | (cell, 3) if cell > 1 => cell,
-                // Si la cellule est morte et a exactement 3 voisins vivants, elle devient vivante
-                (_, 3) => 1,
-                // Sinon, la cellule meurt
-                _ => 0,
-            };
+            // Comptage des voisins vivants de la cellule actuelle
+            let neighbors_count = grid_count_neighbors_in(
+                row,
+                col,
+                current_cells,
+                rows,
+                cols,
+                topology,
+                rule.neighborhood(),
+            );
+            // Application de la règle pour mettre à jour l'état de la cellule
+            next_cells[current_index] = rule.next_state(current_cells[current_index], neighbors_count);
         }
     }
     // Échange des vecteurs d'état actuel avec le prochain pour mettre à jour l'état de la grille
@@ -468,6 +655,7 @@ pub fn grid_update(
 /// et la taille de chaque cellule de la grille `cell_size`.
 ///
 /// La grille est dessinée en utilisant la couleur de fond spécifiée (`graphics::Color::BLACK`).
+/// Les cellules dont la couleur est `None` ne sont pas dessinées et laissent voir le fond.
 ///
 /// # Arguments
 ///
@@ -481,19 +669,106 @@ pub fn grid_update(
 /// Cette fonction peut retourner une erreur de type `GameError` si une erreur survient lors du dessin.
 ///
 pub fn draw_grid<G: Grid>(ctx: &mut Context, canvas : &mut Canvas, grid: &G, cell_size: f32, color_alive : Option<Color>, color_not_alive : Option<Color>) -> GameResult {
+    draw_cells(ctx, canvas, grid, cell_size, color_alive, color_not_alive, 0.)
+}
+
+/// Dessine une grille hexagonale à lignes décalées.
+///
+/// Les lignes impaires sont décalées d'une demi-cellule vers la droite, ce qui correspond à la
+/// disposition utilisée par `Neighborhood::Hexagonal`.
+///
+/// # Arguments
+///
+/// * `ctx` - Le contexte du jeu.
+/// * `canvas` - Le canva sur lequel dessiner.
+/// * `grid` - La grille à dessiner.
+/// * `cell_size` - La taille de chaque cellule de la grille.
+///
+/// # Erreurs
+///
+/// Cette fonction peut retourner une erreur de type `GameError` si une erreur survient lors du dessin.
+///
+pub fn draw_hex_grid<G: Grid>(ctx: &mut Context, canvas : &mut Canvas, grid: &G, cell_size: f32, color_alive : Option<Color>, color_not_alive : Option<Color>) -> GameResult {
+    draw_cells(ctx, canvas, grid, cell_size, color_alive, color_not_alive, cell_size / 2.)
+}
+
+/// Dessine une grille dont chaque état de cellule a sa propre couleur.
+///
+/// La couleur d'une cellule est `palette[état]` ; les états au-delà de la palette utilisent sa
+/// dernière couleur. Les cellules dont la couleur est `None` ne sont pas dessinées.
+///
+/// # Arguments
+///
+/// * `ctx` - Le contexte du jeu.
+/// * `canvas` - Le canva sur lequel dessiner.
+/// * `grid` - La grille à dessiner.
+/// * `cell_size` - La taille de chaque cellule de la grille.
+/// * `palette` - La couleur associée à chaque état.
+///
+/// # Erreurs
+///
+/// Cette fonction peut retourner une erreur de type `GameError` si une erreur survient lors du dessin.
+///
+pub fn draw_grid_palette<G: Grid>(ctx: &mut Context, canvas : &mut Canvas, grid: &G, cell_size: f32, palette: &[Option<Color>]) -> GameResult {
+    draw_palette_cells(ctx, canvas, grid, cell_size, palette, 0.)
+}
+
+/// Dessine une grille hexagonale à lignes décalées dont chaque état de cellule a sa propre couleur
+/// (voir `draw_hex_grid` et `draw_grid_palette`).
+///
+/// # Arguments
+///
+/// * `ctx` - Le contexte du jeu.
+/// * `canvas` - Le canva sur lequel dessiner.
+/// * `grid` - La grille à dessiner.
+/// * `cell_size` - La taille de chaque cellule de la grille.
+/// * `palette` - La couleur associée à chaque état.
+///
+/// # Erreurs
+///
+/// Cette fonction peut retourner une erreur de type `GameError` si une erreur survient lors du dessin.
+///
+pub fn draw_hex_grid_palette<G: Grid>(ctx: &mut Context, canvas : &mut Canvas, grid: &G, cell_size: f32, palette: &[Option<Color>]) -> GameResult {
+    draw_palette_cells(ctx, canvas, grid, cell_size, palette, cell_size / 2.)
+}
+
+/// Dessine les cellules de la grille selon la couleur de leur état, les lignes impaires étant
+/// décalées de `odd_row_shift` pixels.
+fn draw_palette_cells<G: Grid>(ctx: &mut Context, canvas : &mut Canvas, grid: &G, cell_size: f32, palette: &[Option<Color>], odd_row_shift: f32) -> GameResult {
+    let cells = grid.current_cells();
+    let style = grid.cell_style();
+    for row in 0..grid.rows() {
+        let shift = if row % 2 == 1 { odd_row_shift } else { 0. };
+        for col in 0..grid.cols() {
+            let state = cells[grid_index(row, col, grid.cols())] as usize;
+            let color = match palette.get(state).or(palette.last()) {
+                Some(Some(color)) => *color,
+                _ => continue,
+            };
+            let rect = graphics::Rect::new(col as f32 * cell_size + shift, row as f32 * cell_size, cell_size, cell_size);
+            let mesh: graphics::Mesh = cell_mesh(ctx, rect, color, &style)?;
+            canvas.draw(&mesh, graphics::DrawParam::default());
+        }
+    }
+    Ok(())
+}
+
+/// Dessine les cellules de la grille, les lignes impaires étant décalées de `odd_row_shift` pixels.
+fn draw_cells<G: Grid>(ctx: &mut Context, canvas : &mut Canvas, grid: &G, cell_size: f32, color_alive : Option<Color>, color_not_alive : Option<Color>, odd_row_shift: f32) -> GameResult {
+    let style = grid.cell_style();
     for row in 0..grid.rows() {
+        let shift = if row % 2 == 1 { odd_row_shift } else { 0. };
         for col in 0..grid.cols() {
-            let x = col as f32 * cell_size;
+            let x = col as f32 * cell_size + shift;
             let y = row as f32 * cell_size;
             let rect = graphics::Rect::new(x, y, cell_size, cell_size);
             // graphics::Color::BLACK
-            let color : Color = if grid_is_alive(row, col, &grid.current_cells(), grid.cols()) {
-                color_alive.expect("Color for dead cells not found")
-            } else { 
-                color_not_alive.expect("Color for dead cells not found")
+            // Les cellules sans couleur sont transparentes
+            let color = if grid_is_alive(row, col, grid.current_cells(), grid.cols()) { color_alive } else { color_not_alive };
+            let Some(color) = color else {
+                continue;
             };
-            let mesh: graphics::Mesh =
-                graphics::Mesh::new_rectangle(ctx, graphics::DrawMode::fill(), rect, color)?;
+            let mesh: graphics::Mesh = cell_mesh(ctx, rect, color, &style)?;
             canvas.draw(&mesh, graphics::DrawParam::default());
             // graphics::draw(ctx, &mesh, graphics::DrawParam::default());
         }
@@ -1,19 +1,144 @@
-use ggez::{ContextBuilder, graphics::Color,};
+use ggez::{ContextBuilder, conf::WindowMode, graphics::Color,};
 use ggez::event;
 use lifers::ConwaysGrid;
 use lifers::Grid;
-use lifers::LifeGui;
+use lifers::{LifeGui, ResizeMode};
+use lifers::LifeTui;
+use lifers::{ElementaryGrid, ElementaryRule, LifeRule, Rule, SearchOptions, SoupSearch, TableRule, Theme};
 
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("elementary") {
+        let number = args.get(1).map_or(Ok(30), |number| number.parse::<u8>()).unwrap_or_else(|_| {
+            eprintln!("usage: lifers elementary [0-255]");
+            std::process::exit(2);
+        });
+        return run_elementary(ElementaryRule::Wolfram(number));
+    }
+    if args.first().map(String::as_str) == Some("search") {
+        return run_search(&args[1..]).unwrap_or_else(|error| {
+            eprintln!("{}", error);
+            std::process::exit(2);
+        });
+    }
+    let (mode, options) = match args.first().map(String::as_str) {
+        Some("tui") => ("tui", &args[1..]),
+        Some("gui") => ("gui", &args[1..]),
+        _ => ("gui", &args[..]),
+    };
+    let (rule, themes) = parse_options(options).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        std::process::exit(2);
+    });
+    match mode {
+        "tui" => run_tui(rule),
+        _ => run_gui(rule, themes),
+    }
+}
+
+/// Lit la règle demandée par `--rule <règle>` ou `--rule-file <fichier.rule>`, celle du jeu de la vie par défaut,
+/// et les thèmes du fichier donné par `--themes <fichier>`, les thèmes prédéfinis par défaut.
+fn parse_options(options: &[String]) -> Result<(Rule, Vec<Theme>), String> {
+    let mut rule = Rule::default();
+    let mut themes = Theme::presets();
+    let mut options = options.iter();
+    while let Some(option) = options.next() {
+        let mut value = || options.next().ok_or_else(|| format!("missing value for {}", option));
+        match option.as_str() {
+            "--rule" => rule = Rule::parse(value()?).map_err(|error| error.to_string())?,
+            "--rule-file" => rule = TableRule::load(value()?).map_err(|error| error.to_string())?.into(),
+            "--themes" => themes = Theme::load(value()?).map_err(|error| error.to_string())?,
+            _ => return Err(format!("unknown option {}\nusage: lifers [gui|tui] [--rule <rule> | --rule-file <file.rule>] [--themes <file>]", option)),
+        };
+    }
+    Ok((rule, themes))
+}
+
+/// Lance une recherche de soupes.
+///
+/// Options : `--rule <règle>`, `--symmetry <C1|C2_4|C4_4|D2_+2|D8_4>`, `--soups <nombre>`,
+/// `--seed <première graine>`, `--threads <nombre>`, `--output <fichier>` et `--resume` pour
+/// reprendre la recherche enregistrée dans le fichier de sortie.
+fn run_search(options: &[String]) -> Result<(), String> {
+    const USAGE: &str = "usage: lifers search [--rule <rule>] [--symmetry <symmetry>] [--soups <n>] [--seed <n>] [--threads <n>] [--output <file>] [--resume]";
+    let mut search_options = SearchOptions::default();
+    let (mut soups, mut seed, mut output, mut resume) = (1000, 0, String::from("search.txt"), false);
+    let mut options = options.iter();
+    while let Some(option) = options.next() {
+        let mut value = || options.next().ok_or_else(|| format!("missing value for {}\n{}", option, USAGE));
+        let number = |value: &String| value.parse::<u64>().map_err(|_| format!("invalid number {}\n{}", value, USAGE));
+        match option.as_str() {
+            "--rule" => search_options.census.rule = value()?.parse::<LifeRule>().map_err(|error| error.to_string())?,
+            "--symmetry" => search_options.symmetry = value()?.parse().map_err(|error: lifers::SearchError| error.to_string())?,
+            "--soups" => soups = number(value()?)?,
+            "--seed" => seed = number(value()?)?,
+            "--threads" => search_options.threads = number(value()?)? as usize,
+            "--output" => output = value()?.clone(),
+            "--resume" => resume = true,
+            _ => return Err(format!("unknown option {}\n{}", option, USAGE)),
+        }
+    }
+
+    let mut search = if resume {
+        SoupSearch::resume(search_options, &output).map_err(|error| format!("could not resume from {}: {}", output, error))?
+    } else {
+        SoupSearch::new(search_options, seed)
+    };
+    search
+        .run_with_checkpoints(soups, 100, &output)
+        .map_err(|error| format!("could not write {}: {}", output, error))?;
+
+    let tally = search.tally();
+    println!("{} soups, results in {}", tally.soups, output);
+    for (code, entry) in tally.rarest(10) {
+        println!("{:>8}  {:<24} seeds {:?}", entry.count, code, entry.seeds);
+    }
+    Ok(())
+}
+
+/// Lance l'interface graphique `ggez`.
+fn run_gui(rule: Rule, themes: Vec<Theme>) {
     // Make a Context.
     let (ctx, event_loop) = ContextBuilder::new("my_game", "Cool Game Author")
+        .window_mode(WindowMode::default().resizable(true))
         .build()
         .expect("aieee, could not create ggez context!");
- 
+
     let mut grid : ConwaysGrid = ConwaysGrid::new_random(100, 100, true);
+    grid.set_rule(rule);
     grid.set_color_alive(Some(Color::from_rgb(0, 0, 255)));
     grid.set_color_not_alive(Some(Color::from_rgb(0, 0, 0)));
     let mut my_game :LifeGui<ConwaysGrid> = LifeGui::new(grid.clone(), 8.);
     my_game.set_fps(3);
+    my_game.set_themes(themes);
+    my_game.set_resize_mode(ResizeMode::FitCells);
     event::run(ctx, event_loop, my_game);
 }
+
+/// Lance l'interface graphique sur le diagramme espace-temps d'un automate élémentaire.
+fn run_elementary(rule: ElementaryRule) {
+    let (ctx, event_loop) = ContextBuilder::new("my_game", "Cool Game Author")
+        .build()
+        .expect("aieee, could not create ggez context!");
+
+    let mut grid = ElementaryGrid::new(150, 200, true);
+    grid.set_rule(rule);
+    grid.seed_center();
+    let mut my_game: LifeGui<ElementaryGrid> = LifeGui::new(grid, 4.);
+    my_game.set_fps(30);
+    event::run(ctx, event_loop, my_game);
+}
+
+/// Lance l'interface en mode terminal, la grille occupant toute la fenêtre du terminal.
+fn run_tui(rule: Rule) {
+    let (width, height) = crossterm::terminal::size().unwrap_or((80, 24));
+    // Une ligne est réservée à la barre d'état, chaque caractère affiche deux lignes de la grille
+    let rows = (height.max(2) as usize - 1) * 2;
+    let cols = width.max(1) as usize;
+
+    let mut grid: ConwaysGrid = ConwaysGrid::new_random(rows, cols, true);
+    grid.set_rule(rule);
+    let mut tui: LifeTui<ConwaysGrid> = LifeTui::new(grid);
+    tui.set_fps(10);
+    tui.run().expect("could not run the terminal interface");
+}
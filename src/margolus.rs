@@ -0,0 +1,383 @@
+use crate::life::*;
+use crate::{grid_resize, Anchor, CellStyle, Topology};
+use ggez::{graphics::Color, graphics::Canvas, Context, GameResult};
+use rand::prelude::*;
+use std::fmt;
+
+/// Règle d'un automate à blocs de Margolus, donnée par une permutation des 16 états d'un bloc 2x2.
+///
+/// L'état d'un bloc s'écrit avec un bit par cellule : `1` en haut à gauche, `2` en haut à droite,
+/// `4` en bas à gauche et `8` en bas à droite. La règle étant une permutation, l'automate est
+/// réversible.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MargolusRule {
+    table: [u8; 16],
+}
+
+impl MargolusRule {
+    /// Crée une règle à partir de l'image de chacun des 16 états d'un bloc.
+    ///
+    /// Renvoie `None` si la table n'est pas une permutation de `0..16`.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::MargolusRule;
+    ///
+    /// let identity: Vec<u8> = (0..16).collect();
+    /// assert!(MargolusRule::new(identity.try_into().unwrap()).is_some());
+    /// assert!(MargolusRule::new([0; 16]).is_none());
+    /// ```
+    pub fn new(table: [u8; 16]) -> Option<Self> {
+        let mut seen = [false; 16];
+        for &block in &table {
+            if block >= 16 || seen[block as usize] {
+                return None;
+            }
+            seen[block as usize] = true;
+        }
+        Some(MargolusRule { table })
+    }
+
+    /// Règle Critters : un bloc contenant exactement deux cellules vivantes est inchangé, les autres
+    /// sont inversés, et ceux qui en contenaient trois sont en plus tournés d'un demi-tour.
+    pub fn critters() -> Self {
+        let mut table = [0; 16];
+        for (block, next) in table.iter_mut().enumerate() {
+            let block = block as u8;
+            *next = match block.count_ones() {
+                2 => block,
+                3 => rotate_half_turn(!block & 0xF),
+                _ => !block & 0xF,
+            };
+        }
+        MargolusRule { table }
+    }
+
+    /// Règle Tron : un bloc uniforme est inversé, les autres sont inchangés.
+    pub fn tron() -> Self {
+        let mut table: [u8; 16] = std::array::from_fn(|block| block as u8);
+        table.swap(0, 15);
+        MargolusRule { table }
+    }
+
+    /// Modèle des boules de billard : une boule seule traverse le bloc en diagonale, deux boules
+    /// opposées rebondissent sur l'autre diagonale, et les autres blocs sont inchangés.
+    pub fn billiard_ball() -> Self {
+        let mut table: [u8; 16] = std::array::from_fn(|block| block as u8);
+        table.swap(1, 8);
+        table.swap(2, 4);
+        table.swap(6, 9);
+        MargolusRule { table }
+    }
+
+    /// Renvoie l'état suivant d'un bloc.
+    pub fn next_block(&self, block: u8) -> u8 {
+        self.table[block as usize & 0xF]
+    }
+
+    /// Renvoie la règle inverse, qui annule une étape de celle-ci.
+    pub fn inverse(&self) -> Self {
+        let mut table = [0; 16];
+        for (block, &next) in self.table.iter().enumerate() {
+            table[next as usize] = block as u8;
+        }
+        MargolusRule { table }
+    }
+}
+
+/// Fait tourner un bloc d'un demi-tour en échangeant les cellules opposées.
+fn rotate_half_turn(block: u8) -> u8 {
+    (block & 1) << 3 | (block & 2) << 1 | (block & 4) >> 1 | (block & 8) >> 3
+}
+
+/// Structure représentant une grille d'automate à blocs de Margolus.
+///
+/// La grille est découpée en blocs 2x2 dont l'origine est décalée d'une cellule en diagonale à
+/// chaque étape. Sur une grille torique de dimensions paires, les blocs traversent les bords ;
+/// sinon, les blocs incomplets au bord de la grille sont laissés inchangés.
+#[derive(Clone)]
+pub struct MargolusGrid {
+    /// Vecteur contenant l'état actuel de chaque cellule de la grille.
+    current_cells: Vec<u8>,
+    /// Nombre de lignes de la grille.
+    rows: usize,
+    /// Nombre de colonnes de la grille.
+    cols: usize,
+    /// Topologie de la grille ; les blocs ne chevauchent que les bords d'un tore, les autres
+    /// topologies se comportant comme le plan.
+    topology: Topology,
+    /// Règle appliquée à chaque bloc.
+    rule: MargolusRule,
+    /// Nombre d'étapes effectuées, dont la parité donne le découpage en blocs à utiliser.
+    steps: usize,
+    color_alive: Option<Color>,
+    color_not_alive: Option<Color>,
+    /// Style des cellules dessinées.
+    cell_style: CellStyle,
+}
+
+impl MargolusGrid {
+    /// Renvoie la règle appliquée par la grille.
+    pub fn rule(&self) -> MargolusRule {
+        self.rule
+    }
+
+    /// Modifie la règle appliquée par la grille.
+    pub fn set_rule(&mut self, rule: MargolusRule) {
+        self.rule = rule;
+    }
+
+    /// Renvoie le nombre d'étapes effectuées depuis la création de la grille.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Annule la dernière étape en appliquant la règle inverse au découpage utilisé par celle-ci.
+    ///
+    /// Ne fait rien si aucune étape n'a été effectuée.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::{Grid, MargolusGrid, MargolusRule};
+    ///
+    /// let mut grid = MargolusGrid::new_random(8, 8, true);
+    /// grid.set_rule(MargolusRule::critters());
+    /// let start = grid.current_cells().clone();
+    /// grid.update();
+    /// grid.update();
+    /// grid.reverse_step();
+    /// grid.reverse_step();
+    /// assert_eq!(grid.current_cells(), &start);
+    /// ```
+    pub fn reverse_step(&mut self) {
+        if self.steps == 0 {
+            return;
+        }
+        self.steps -= 1;
+        self.apply(self.rule.inverse(), self.steps % 2);
+    }
+
+    /// Renvoie les paires d'indices (ligne ou colonne) formant les blocs le long d'une dimension.
+    fn block_spans(&self, len: usize, offset: usize) -> Vec<(usize, usize)> {
+        (offset..len)
+            .step_by(2)
+            .filter_map(|start| {
+                if start + 1 < len {
+                    Some((start, start + 1))
+                } else if self.topology == Topology::Torus && len.is_multiple_of(2) && len > 1 {
+                    Some((start, 0))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Applique une règle à chaque bloc du découpage décalé de `offset` cellules.
+    fn apply(&mut self, rule: MargolusRule, offset: usize) {
+        let col_spans = self.block_spans(self.cols, offset);
+        for (top, bottom) in self.block_spans(self.rows, offset) {
+            for &(left, right) in &col_spans {
+                let indices = [
+                    grid_index(top, left, self.cols),
+                    grid_index(top, right, self.cols),
+                    grid_index(bottom, left, self.cols),
+                    grid_index(bottom, right, self.cols),
+                ];
+                let block = indices
+                    .iter()
+                    .enumerate()
+                    .fold(0u8, |block, (bit, &index)| block | ((self.current_cells[index] >= 1) as u8) << bit);
+                let next = rule.next_block(block);
+                for (bit, &index) in indices.iter().enumerate() {
+                    self.current_cells[index] = (next >> bit) & 1;
+                }
+            }
+        }
+    }
+}
+
+// Implémentation d'une méthode pour afficher la grille
+impl fmt::Display for MargolusGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        grid_fmt(f, self.rows, self.cols, &self.current_cells)
+    }
+}
+
+impl Grid for MargolusGrid {
+    fn display(&self) {
+        println!("{}", self);
+    }
+
+    /// Initialise une nouvelle grille avec des cellules mortes et la règle Critters.
+    fn new(rows: usize, cols: usize, topology: impl Into<Topology>) -> MargolusGrid {
+        MargolusGrid {
+            current_cells: vec![0; rows * cols],
+            rows,
+            cols,
+            topology: topology.into(),
+            rule: MargolusRule::critters(),
+            steps: 0,
+            color_alive: Some(Color::from_rgb(0, 0, 0)),
+            color_not_alive: Some(Color::from_rgb(204, 204, 204)),
+            cell_style: CellStyle::default(),
+        }
+    }
+
+    fn new_random(rows: usize, cols: usize, topology: impl Into<Topology>) -> MargolusGrid {
+        let mut rng: ThreadRng = rand::thread_rng();
+        let current_cells: Vec<u8> = (0..rows * cols).map(|_| rng.gen_range(0..2)).collect();
+        MargolusGrid::from_vect(current_cells, rows, cols, topology)
+    }
+
+    /// Crée une nouvelle grille à partir d'un vecteur de cellules.
+    ///
+    /// # Panics
+    ///
+    /// Cette méthode panique si la longueur du vecteur `cels` ne correspond pas à `rows * cols`.
+    fn from_vect(cels: Vec<u8>, rows: usize, cols: usize, topology: impl Into<Topology>) -> Self {
+        assert_eq!(cels.len(), rows * cols);
+        MargolusGrid {
+            current_cells: cels,
+            ..MargolusGrid::new(rows, cols, topology)
+        }
+    }
+
+    /// Setter pour la couleur des cellules vivantes
+    fn set_color_alive(&mut self, color: Option<Color>) {
+        self.color_alive = color;
+    }
+
+    /// Setter pour la couleur des cellules mortes
+    fn set_color_not_alive(&mut self, color: Option<Color>) {
+        self.color_not_alive = color;
+    }
+
+    fn cell_style(&self) -> CellStyle {
+        self.cell_style
+    }
+
+    fn set_cell_style(&mut self, style: CellStyle) {
+        self.cell_style = style;
+    }
+
+    fn rows(&self) -> usize {
+        self.rows
+    }
+
+    fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Redimensionne la grille en conservant ses cellules.
+    ///
+    /// Le découpage en blocs dépend de la position des cellules : un décalage impair de l'ancre
+    /// change les blocs auxquels elles appartiennent.
+    fn resize(&mut self, rows: usize, cols: usize, anchor: Anchor) {
+        self.current_cells = grid_resize(&self.current_cells, self.rows, self.cols, rows, cols, anchor);
+        self.rows = rows;
+        self.cols = cols;
+    }
+
+    fn is_toricgrid(&self) -> bool {
+        self.topology == Topology::Torus
+    }
+
+    fn current_cells(&self) -> &Vec<u8> {
+        &self.current_cells
+    }
+
+    fn set_cell_state(&mut self, row: usize, col: usize, alive: u8) {
+        grid_set_cell_state(row, col, alive.min(1), &mut self.current_cells, self.cols)
+    }
+
+    fn toggle_cell_state(&mut self, row: usize, col: usize) {
+        grid_toggle_cell_state(row, col, &mut self.current_cells, self.cols)
+    }
+
+    fn is_alive(&self, row: usize, col: usize) -> bool {
+        grid_is_alive(row, col, &self.current_cells, self.cols)
+    }
+
+    fn index(&self, row: usize, col: usize) -> usize {
+        grid_index(row, col, self.cols)
+    }
+
+    /// Compte les autres cellules vivantes du bloc qui contient la cellule lors de la prochaine étape.
+    ///
+    /// Renvoie 0 si la cellule appartient à un bloc incomplet, laissé inchangé.
+    fn count_neighbors(&self, row: usize, col: usize) -> usize {
+        let offset = self.steps % 2;
+        let find = |spans: Vec<(usize, usize)>, index: usize| {
+            spans.into_iter().find(|&(first, second)| first == index || second == index)
+        };
+        let (Some((top, bottom)), Some((left, right))) = (
+            find(self.block_spans(self.rows, offset), row),
+            find(self.block_spans(self.cols, offset), col),
+        ) else {
+            return 0;
+        };
+        [(top, left), (top, right), (bottom, left), (bottom, right)]
+            .iter()
+            .filter(|&&(i, j)| (i, j) != (row, col) && self.is_alive(i, j))
+            .count()
+    }
+
+    /// Applique la règle à chaque bloc puis décale le découpage pour l'étape suivante.
+    fn update(&mut self) {
+        self.apply(self.rule, self.steps % 2);
+        self.steps += 1;
+    }
+
+    fn draw(&self, ctx: &mut Context, canvas: &mut Canvas, cell_size: f32) -> GameResult {
+        draw_grid(ctx, canvas, self, cell_size, self.color_alive, self.color_not_alive)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets_are_permutations() {
+        for rule in [MargolusRule::critters(), MargolusRule::tron(), MargolusRule::billiard_ball()] {
+            assert_eq!(MargolusRule::new(rule.table), Some(rule));
+            assert_eq!(rule.inverse().inverse(), rule);
+        }
+        // Critters : un bloc à trois cellules devient la cellule opposée à la case vide
+        assert_eq!(MargolusRule::critters().next_block(0b0111), 0b0001);
+    }
+
+    #[test]
+    fn test_billiard_ball_moves_diagonally() {
+        let mut grid = MargolusGrid::new(6, 6, true);
+        grid.set_rule(MargolusRule::billiard_ball());
+        grid.set_cell_state(0, 0, 1);
+        grid.update();
+        assert!(grid.is_alive(1, 1));
+        grid.update();
+        assert!(grid.is_alive(2, 2));
+        assert_eq!(grid.current_cells().iter().filter(|&&cell| cell == 1).count(), 1);
+    }
+
+    #[test]
+    fn test_reverse_step_restores_toric_and_bounded_grids() {
+        for (rows, cols, toricgrid) in [(8, 10, true), (7, 9, false), (5, 6, true)] {
+            let mut rng = StdRng::seed_from_u64(3);
+            let cells: Vec<u8> = (0..rows * cols).map(|_| rng.gen_range(0..2)).collect();
+            let mut grid = MargolusGrid::from_vect(cells.clone(), rows, cols, toricgrid);
+            grid.set_rule(MargolusRule::critters());
+            for _ in 0..15 {
+                grid.update();
+            }
+            assert_ne!(grid.current_cells(), &cells);
+            for _ in 0..15 {
+                grid.reverse_step();
+            }
+            assert_eq!(grid.current_cells(), &cells);
+            assert_eq!(grid.steps(), 0);
+        }
+    }
+}
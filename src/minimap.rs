@@ -0,0 +1,253 @@
+use ggez::{graphics::{self, Canvas, Color, Mesh, MeshBuilder, Rect}, Context, GameResult};
+
+use crate::grid_index;
+
+/// Partie visible d'une grille, en cellules.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Viewport {
+    /// Ligne du bord supérieur de la partie visible.
+    pub row: f32,
+    /// Colonne du bord gauche de la partie visible.
+    pub col: f32,
+    /// Nombre de lignes visibles.
+    pub rows: f32,
+    /// Nombre de colonnes visibles.
+    pub cols: f32,
+}
+
+impl Viewport {
+    /// Calcule la partie visible d'une fenêtre de `width` × `height` pixels dont le coin supérieur
+    /// gauche montre la cellule (`row`, `col`).
+    pub fn new(row: f32, col: f32, width: f32, height: f32, cell_size: f32) -> Self {
+        Viewport {
+            row,
+            col,
+            rows: height / cell_size,
+            cols: width / cell_size,
+        }
+    }
+
+    /// Déplace la partie visible pour que son centre soit la cellule (`row`, `col`), sans sortir
+    /// d'une grille de `grid_rows` × `grid_cols` cellules ; une grille plus petite que la partie
+    /// visible reste calée en haut à gauche.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::Viewport;
+    ///
+    /// let viewport = Viewport { row: 0.0, col: 0.0, rows: 10.0, cols: 20.0 };
+    /// let centered = viewport.centered_on(50.0, 50.0, 100, 100);
+    /// assert_eq!((centered.row, centered.col), (45.0, 40.0));
+    /// // Près du bord, la partie visible reste dans la grille
+    /// let centered = viewport.centered_on(98.0, 2.0, 100, 100);
+    /// assert_eq!((centered.row, centered.col), (90.0, 0.0));
+    /// ```
+    pub fn centered_on(&self, row: f32, col: f32, grid_rows: usize, grid_cols: usize) -> Self {
+        Viewport {
+            row: row - self.rows / 2.0,
+            col: col - self.cols / 2.0,
+            ..*self
+        }
+        .clamped(grid_rows, grid_cols)
+    }
+
+    /// Ramène la partie visible dans une grille de `grid_rows` × `grid_cols` cellules.
+    pub fn clamped(&self, grid_rows: usize, grid_cols: usize) -> Self {
+        Viewport {
+            row: self.row.min(grid_rows as f32 - self.rows).max(0.0),
+            col: self.col.min(grid_cols as f32 - self.cols).max(0.0),
+            ..*self
+        }
+    }
+}
+
+/// Calcule la densité de cellules vivantes de chaque bloc d'une grille réduite à
+/// `map_rows` × `map_cols` blocs.
+///
+/// Chaque bloc couvre une part égale de la grille, arrondie à la cellule ; sa densité est la
+/// proportion de cellules vivantes, entre 0 et 1.
+///
+/// # Arguments
+///
+/// * `current_cells` - Vecteur contenant l'état de chaque cellule de la grille.
+/// * `rows` - Nombre de lignes de la grille.
+/// * `cols` - Nombre de colonnes de la grille.
+/// * `map_rows` - Nombre de lignes de blocs.
+/// * `map_cols` - Nombre de colonnes de blocs.
+///
+/// # Exemple
+///
+/// ```
+/// use crate::lifers::grid_block_density;
+///
+/// let cells = vec![
+///     1, 1, 0, 0,
+///     1, 0, 0, 0,
+/// ];
+/// assert_eq!(grid_block_density(&cells, 2, 4, 1, 2), vec![0.75, 0.0]);
+/// ```
+pub fn grid_block_density(current_cells: &[u8], rows: usize, cols: usize, map_rows: usize, map_cols: usize) -> Vec<f32> {
+    if map_rows == 0 || map_cols == 0 {
+        return Vec::new();
+    }
+    let mut alive = vec![0u32; map_rows * map_cols];
+    let mut total = vec![0u32; map_rows * map_cols];
+    for row in 0..rows {
+        let block_row = row * map_rows / rows;
+        for col in 0..cols {
+            let block = grid_index(block_row, col * map_cols / cols, map_cols);
+            total[block] += 1;
+            if current_cells[grid_index(row, col, cols)] >= 1 {
+                alive[block] += 1;
+            }
+        }
+    }
+    alive
+        .iter()
+        .zip(&total)
+        .map(|(&alive, &total)| if total == 0 { 0.0 } else { alive as f32 / total as f32 })
+        .collect()
+}
+
+/// Vue d'ensemble d'une grille, dessinée dans un coin de la fenêtre.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Minimap {
+    /// Côté maximal de la vue d'ensemble, en pixels.
+    pub size: f32,
+    /// Distance aux bords de la fenêtre, en pixels.
+    pub margin: f32,
+    /// Couleur du fond, c'est-à-dire des blocs sans cellule vivante.
+    pub background: Color,
+    /// Couleur des blocs entièrement vivants ; les autres blocs sont plus transparents.
+    pub color: Color,
+    /// Couleur du rectangle de la partie visible.
+    pub viewport_color: Color,
+}
+
+impl Default for Minimap {
+    fn default() -> Self {
+        Minimap {
+            size: 160.0,
+            margin: 10.0,
+            background: Color::from_rgba(0, 0, 0, 180),
+            color: Color::from_rgb(255, 255, 255),
+            viewport_color: Color::from_rgb(255, 64, 64),
+        }
+    }
+}
+
+impl Minimap {
+    /// Renvoie le rectangle occupé par la vue d'ensemble d'une grille de `rows` × `cols` cellules,
+    /// dans le coin supérieur droit d'une fenêtre de `width` pixels de large.
+    ///
+    /// La vue d'ensemble garde les proportions de la grille, son plus grand côté mesurant `size`.
+    pub fn area(&self, rows: usize, cols: usize, width: f32) -> Rect {
+        let scale = self.size / rows.max(cols).max(1) as f32;
+        let (map_width, map_height) = (cols as f32 * scale, rows as f32 * scale);
+        Rect::new(width - self.margin - map_width, self.margin, map_width, map_height)
+    }
+
+    /// Renvoie la cellule `(ligne, colonne)` montrée au point (`x`, `y`) de la fenêtre, ou `None`
+    /// si ce point est hors de la vue d'ensemble.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::Minimap;
+    ///
+    /// let minimap = Minimap { size: 100.0, margin: 0.0, ..Minimap::default() };
+    /// // Grille de 200x400 cellules : la vue d'ensemble mesure 100x50 pixels
+    /// assert_eq!(minimap.cell_at(200, 400, 800.0, 750.0, 25.0), Some((100.0, 200.0)));
+    /// assert_eq!(minimap.cell_at(200, 400, 800.0, 10.0, 10.0), None);
+    /// ```
+    pub fn cell_at(&self, rows: usize, cols: usize, width: f32, x: f32, y: f32) -> Option<(f32, f32)> {
+        let area = self.area(rows, cols, width);
+        if !area.contains([x, y]) {
+            return None;
+        }
+        Some(((y - area.y) / area.h * rows as f32, (x - area.x) / area.w * cols as f32))
+    }
+}
+
+/// Dessine la vue d'ensemble d'une grille et le rectangle de sa partie visible.
+///
+/// Chaque pixel de la vue d'ensemble, au plus, correspond à un bloc de cellules dont la densité
+/// (voir `grid_block_density`) donne l'opacité.
+///
+/// # Arguments
+///
+/// * `ctx` - Le contexte du jeu.
+/// * `canvas` - Le canva sur lequel dessiner, en coordonnées de la fenêtre.
+/// * `current_cells` - Vecteur contenant l'état de chaque cellule de la grille.
+/// * `rows` - Nombre de lignes de la grille.
+/// * `cols` - Nombre de colonnes de la grille.
+/// * `viewport` - La partie visible de la grille.
+/// * `minimap` - La taille, la position et les couleurs de la vue d'ensemble.
+///
+/// # Erreurs
+///
+/// Cette fonction peut retourner une erreur de type `GameError` si une erreur survient lors du dessin.
+///
+pub fn draw_minimap(
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    current_cells: &[u8],
+    rows: usize,
+    cols: usize,
+    viewport: &Viewport,
+    minimap: &Minimap,
+) -> GameResult {
+    let (width, _) = ctx.gfx.drawable_size();
+    let area = minimap.area(rows, cols, width);
+    let map_rows = (area.h as usize).clamp(1, rows.max(1));
+    let map_cols = (area.w as usize).clamp(1, cols.max(1));
+    let density = grid_block_density(current_cells, rows, cols, map_rows, map_cols);
+    let (block_width, block_height) = (area.w / map_cols as f32, area.h / map_rows as f32);
+
+    let mut builder = MeshBuilder::new();
+    builder.rectangle(graphics::DrawMode::fill(), area, minimap.background)?;
+    for (block, &value) in density.iter().enumerate() {
+        if value > 0.0 {
+            let (row, col) = (block / map_cols, block % map_cols);
+            let rect = Rect::new(area.x + col as f32 * block_width, area.y + row as f32 * block_height, block_width, block_height);
+            // Un bloc peu peuplé reste visible
+            let alpha = minimap.color.a * (0.3 + 0.7 * value);
+            builder.rectangle(graphics::DrawMode::fill(), rect, Color { a: alpha, ..minimap.color })?;
+        }
+    }
+    let (scale_x, scale_y) = (area.w / cols.max(1) as f32, area.h / rows.max(1) as f32);
+    let view = Rect::new(
+        area.x + viewport.col * scale_x,
+        area.y + viewport.row * scale_y,
+        (viewport.cols * scale_x).min(area.w),
+        (viewport.rows * scale_y).min(area.h),
+    );
+    builder.rectangle(graphics::DrawMode::stroke(1.5), view, minimap.viewport_color)?;
+    canvas.draw(&Mesh::from_data(ctx, builder.build()), graphics::DrawParam::default());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_density_with_uneven_blocks() {
+        // 5 colonnes en 2 blocs : les colonnes 0 à 2 puis 3 et 4
+        let cells = vec![1, 1, 1, 0, 1];
+        assert_eq!(grid_block_density(&cells, 1, 5, 1, 2), vec![1.0, 0.5]);
+        assert!(grid_block_density(&cells, 1, 5, 0, 2).is_empty());
+    }
+
+    #[test]
+    fn test_viewport_clamped_to_small_grid() {
+        let viewport = Viewport::new(-5.0, 30.0, 400.0, 200.0, 10.0);
+        assert_eq!((viewport.rows, viewport.cols), (20.0, 40.0));
+        // La grille de 10x10 est plus petite que la partie visible
+        let clamped = viewport.clamped(10, 10);
+        assert_eq!((clamped.row, clamped.col), (0.0, 0.0));
+        let clamped = viewport.clamped(100, 100);
+        assert_eq!((clamped.row, clamped.col), (0.0, 30.0));
+    }
+}
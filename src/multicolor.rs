@@ -0,0 +1,161 @@
+use ggez::graphics::Color;
+
+use crate::{Neighborhood, Topology};
+
+/// Mode de couleur des cellules vivantes, stockée dans la valeur `u8` de chaque cellule.
+///
+/// En mode multicolore, une cellule vivante vaut un numéro de couleur entre 1 et `colors()`. Les
+/// survivantes gardent leur couleur et une cellule qui naît prend la couleur majoritaire de ses
+/// voisines vivantes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorMode {
+    /// Une seule couleur : toutes les cellules vivantes valent 1.
+    #[default]
+    Single,
+    /// Immigration : deux couleurs en compétition.
+    Immigration,
+    /// QuadLife : quatre couleurs ; si les trois parentes d'une naissance ont des couleurs toutes
+    /// différentes, la nouvelle cellule prend la quatrième.
+    QuadLife,
+}
+
+impl ColorMode {
+    /// Renvoie le nombre de couleurs qu'une cellule vivante peut prendre.
+    pub fn colors(&self) -> u8 {
+        match self {
+            ColorMode::Single => 1,
+            ColorMode::Immigration => 2,
+            ColorMode::QuadLife => 4,
+        }
+    }
+
+    /// Renvoie la couleur d'affichage de chaque état, la couleur 1 étant `color_alive`.
+    pub fn palette(&self, color_alive: Option<Color>, color_not_alive: Option<Color>) -> Vec<Option<Color>> {
+        let mut palette = vec![color_not_alive, color_alive];
+        palette.extend(
+            [
+                Color::from_rgb(220, 40, 40),
+                Color::from_rgb(40, 160, 60),
+                Color::from_rgb(230, 170, 0),
+            ]
+            .iter()
+            .take(self.colors() as usize - 1)
+            .map(|&color| Some(color)),
+        );
+        palette
+    }
+
+    /// Choisit la couleur d'une cellule qui naît à partir du nombre de voisines vivantes de chaque couleur.
+    ///
+    /// La couleur majoritaire l'emporte, la plus petite en cas d'égalité, sauf en QuadLife lorsque
+    /// trois parentes ont trois couleurs différentes : la naissance prend alors la couleur absente.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::ColorMode;
+    ///
+    /// // Comptes indexés par couleur, l'indice 0 n'étant pas utilisé
+    /// assert_eq!(ColorMode::Immigration.birth_color(&[0, 1, 2]), 2);
+    /// assert_eq!(ColorMode::QuadLife.birth_color(&[0, 1, 0, 1, 1]), 2);
+    /// ```
+    pub fn birth_color(&self, counts: &[usize]) -> u8 {
+        let colors = self.colors() as usize;
+        let counts = &counts[1..=colors];
+        let parents: usize = counts.iter().sum();
+        if *self == ColorMode::QuadLife && parents == 3 && counts.iter().all(|&count| count <= 1) {
+            if let Some(missing) = counts.iter().position(|&count| count == 0) {
+                return missing as u8 + 1;
+            }
+        }
+        let max = counts.iter().copied().max().unwrap_or(0);
+        counts.iter().position(|&count| count == max).unwrap_or(0) as u8 + 1
+    }
+}
+
+/// Renvoie la couleur d'une cellule qui naît, d'après ses voisines vivantes dans la grille.
+///
+/// # Arguments
+///
+/// * `row` - L'indice de la ligne de la cellule dans la grille.
+/// * `col` - L'indice de la colonne de la cellule dans la grille.
+/// * `current_cells` - Vecteur contenant l'état de chaque cellule à la génération des parentes.
+/// * `rows` - Nombre de lignes de la grille.
+/// * `cols` - Nombre de colonnes de la grille.
+/// * `topology` - La topologie de la grille.
+/// * `neighborhood` - Le voisinage de la règle.
+/// * `mode` - Le mode de couleur.
+#[allow(clippy::too_many_arguments)]
+pub fn grid_birth_color(
+    row: usize,
+    col: usize,
+    current_cells: &[u8],
+    rows: usize,
+    cols: usize,
+    topology: impl Into<Topology>,
+    neighborhood: &Neighborhood,
+    mode: ColorMode,
+) -> u8 {
+    let topology = topology.into();
+    let mut counts = [0usize; 256];
+    for &(di, dj) in neighborhood.offsets(row) {
+        let state = topology.state_at(current_cells, row as isize + di, col as isize + dj, rows, cols);
+        counts[state.min(mode.colors()) as usize] += 1;
+    }
+    mode.birth_color(&counts)
+}
+
+/// Compte les cellules vivantes de chaque couleur.
+///
+/// Le résultat est indexé par couleur, l'indice 0 contenant le nombre de cellules mortes ; les
+/// valeurs supérieures à `colors` sont comptées avec la dernière couleur.
+///
+/// # Exemple
+///
+/// ```
+/// use crate::lifers::grid_color_counts;
+///
+/// let current_cells = vec![0, 1, 2, 2, 0, 1];
+/// assert_eq!(grid_color_counts(&current_cells, 2), vec![2, 2, 2]);
+/// ```
+pub fn grid_color_counts(current_cells: &[u8], colors: u8) -> Vec<usize> {
+    let mut counts = vec![0; colors as usize + 1];
+    for &cell in current_cells {
+        counts[cell.min(colors) as usize] += 1;
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConwaysGrid, Grid};
+
+    #[test]
+    fn test_immigration_birth_takes_majority_colour() {
+        let mut grid = ConwaysGrid::new(5, 5, false);
+        grid.set_color_mode(ColorMode::Immigration);
+        // Clignotant avec deux cellules de couleur 2 et une de couleur 1
+        grid.set_cell_state(2, 1, 2);
+        grid.set_cell_state(2, 2, 1);
+        grid.set_cell_state(2, 3, 2);
+        grid.update();
+        assert_eq!(grid.current_cells()[grid.index(1, 2)], 2);
+        assert_eq!(grid.current_cells()[grid.index(3, 2)], 2);
+        // La survivante garde sa couleur
+        assert_eq!(grid.current_cells()[grid.index(2, 2)], 1);
+        assert_eq!(grid_color_counts(grid.current_cells(), 2), vec![22, 1, 2]);
+    }
+
+    #[test]
+    fn test_quadlife_three_different_parents() {
+        let mut grid = ConwaysGrid::new(5, 5, false);
+        grid.set_color_mode(ColorMode::QuadLife);
+        grid.set_cell_state(2, 1, 1);
+        grid.set_cell_state(2, 2, 3);
+        grid.set_cell_state(2, 3, 4);
+        grid.update();
+        assert_eq!(grid.current_cells()[grid.index(1, 2)], 2);
+        assert_eq!(grid.colors(), 4);
+    }
+}
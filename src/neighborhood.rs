@@ -0,0 +1,214 @@
+/// Décalages du voisinage de Moore (les 8 cellules entourant la cellule centrale).
+const MOORE: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Décalages du voisinage de von Neumann (les 4 cellules orthogonalement adjacentes).
+const VON_NEUMANN: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// Décalages du voisinage hexagonal pour une ligne paire.
+const HEXAGONAL_EVEN: [(isize, isize); 6] = [(-1, -1), (-1, 0), (0, -1), (0, 1), (1, -1), (1, 0)];
+
+/// Décalages du voisinage hexagonal pour une ligne impaire.
+const HEXAGONAL_ODD: [(isize, isize); 6] = [(-1, 0), (-1, 1), (0, -1), (0, 1), (1, 0), (1, 1)];
+
+/// Voisinage utilisé pour compter les voisins vivants d'une cellule.
+///
+/// Le voisinage hexagonal utilise une disposition en lignes décalées : les lignes impaires sont
+/// décalées d'une demi-cellule vers la droite. Sur une grille torique, le nombre de lignes doit
+/// être pair pour que ce décalage reste cohérent au raccord.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub enum Neighborhood {
+    /// Les 8 cellules entourant la cellule (voisinage 3x3).
+    #[default]
+    Moore,
+    /// Les 4 cellules orthogonalement adjacentes.
+    VonNeumann,
+    /// Les 6 cellules adjacentes dans une grille hexagonale à lignes décalées.
+    Hexagonal,
+    /// Un masque arbitraire de rayon `r` défini par l'utilisateur.
+    Custom(CustomNeighborhood),
+}
+
+/// Voisinage défini par un masque carré de côté `2 * radius + 1`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CustomNeighborhood {
+    radius: usize,
+    mask: Vec<bool>,
+    offsets: Vec<(isize, isize)>,
+}
+
+impl Neighborhood {
+    /// Crée un voisinage personnalisé à partir d'un masque.
+    ///
+    /// # Arguments
+    ///
+    /// * `radius` - Le rayon du voisinage.
+    /// * `mask` - Le masque de côté `2 * radius + 1`, ligne par ligne. La cellule centrale est ignorée.
+    ///
+    /// # Panics
+    ///
+    /// Cette méthode panique si la longueur du masque ne correspond pas à `(2 * radius + 1)²`.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::Neighborhood;
+    ///
+    /// // Voisinage en croix de rayon 2
+    /// let cross = Neighborhood::custom(2, vec![
+    ///     false, false, true, false, false,
+    ///     false, false, true, false, false,
+    ///     true,  true,  false, true, true,
+    ///     false, false, true, false, false,
+    ///     false, false, true, false, false,
+    /// ]);
+    /// assert_eq!(cross.size(), 8);
+    /// ```
+    pub fn custom(radius: usize, mask: Vec<bool>) -> Self {
+        let side = 2 * radius + 1;
+        assert_eq!(mask.len(), side * side);
+        let r = radius as isize;
+        let mut offsets = Vec::new();
+        for (index, &included) in mask.iter().enumerate() {
+            let di = (index / side) as isize - r;
+            let dj = (index % side) as isize - r;
+            if included && (di, dj) != (0, 0) {
+                offsets.push((di, dj));
+            }
+        }
+        Neighborhood::Custom(CustomNeighborhood {
+            radius,
+            mask,
+            offsets,
+        })
+    }
+
+    /// Renvoie le rayon du voisinage.
+    pub fn radius(&self) -> usize {
+        match self {
+            Neighborhood::Moore | Neighborhood::VonNeumann | Neighborhood::Hexagonal => 1,
+            Neighborhood::Custom(custom) => custom.radius,
+        }
+    }
+
+    /// Renvoie le nombre de voisins d'une cellule.
+    pub fn size(&self) -> usize {
+        self.offsets(0).len()
+    }
+
+    /// Renvoie le masque d'un voisinage personnalisé, `None` pour les voisinages prédéfinis.
+    pub fn mask(&self) -> Option<&[bool]> {
+        match self {
+            Neighborhood::Custom(custom) => Some(&custom.mask),
+            _ => None,
+        }
+    }
+
+    /// Renvoie les décalages `(ligne, colonne)` des voisins d'une cellule de la ligne `row`.
+    ///
+    /// La ligne n'a d'importance que pour le voisinage hexagonal, dont les voisins dépendent
+    /// de la parité de la ligne.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::Neighborhood;
+    ///
+    /// assert_eq!(Neighborhood::VonNeumann.offsets(0), &[(-1, 0), (0, -1), (0, 1), (1, 0)]);
+    /// assert!(Neighborhood::Hexagonal.offsets(1).contains(&(1, 1)));
+    /// ```
+    pub fn offsets(&self, row: usize) -> &[(isize, isize)] {
+        match self {
+            Neighborhood::Moore => &MOORE,
+            Neighborhood::VonNeumann => &VON_NEUMANN,
+            Neighborhood::Hexagonal if row.is_multiple_of(2) => &HEXAGONAL_EVEN,
+            Neighborhood::Hexagonal => &HEXAGONAL_ODD,
+            Neighborhood::Custom(custom) => &custom.offsets,
+        }
+    }
+
+    /// Renvoie le suffixe utilisé dans la notation des règles (`H` pour hexagonal, `V` pour von Neumann).
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            Neighborhood::VonNeumann => "V",
+            Neighborhood::Hexagonal => "H",
+            _ => "",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{grid_count_neighbors, grid_count_neighbors_in};
+
+    #[test]
+    fn test_moore_counts_all_eight_neighbors() {
+        let current_cells = vec![1, 1, 1, 1, 0, 1, 1, 1, 1];
+        let count = grid_count_neighbors_in(1, 1, &current_cells, 3, 3, false, &Neighborhood::Moore);
+        assert_eq!(count, 8);
+    }
+
+    #[test]
+    fn test_von_neumann_ignores_diagonals() {
+        let current_cells = vec![1, 0, 1, 1, 0, 0, 1, 1, 1];
+        let count =
+            grid_count_neighbors_in(1, 1, &current_cells, 3, 3, false, &Neighborhood::VonNeumann);
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn test_hexagonal_depends_on_row_parity() {
+        #[rustfmt::skip]
+        let current_cells = vec![
+            0, 0, 0,
+            1, 0, 1,
+            0, 0, 0,
+            1, 0, 1,
+        ];
+        // Ligne paire : seuls les voisins diagonaux de gauche sont comptés
+        let even = grid_count_neighbors_in(2, 1, &current_cells, 4, 3, false, &Neighborhood::Hexagonal);
+        assert_eq!(even, 2);
+        assert_eq!(grid_count_neighbors(2, 1, &current_cells, 4, 3, false), 4);
+
+        #[rustfmt::skip]
+        let current_cells = vec![
+            1, 0, 0,
+            0, 0, 0,
+            1, 0, 1,
+            0, 0, 0,
+        ];
+        // Ligne impaire : seuls les voisins diagonaux de droite sont comptés
+        let odd = grid_count_neighbors_in(1, 1, &current_cells, 4, 3, false, &Neighborhood::Hexagonal);
+        assert_eq!(odd, 1);
+        assert_eq!(Neighborhood::Hexagonal.size(), 6);
+    }
+
+    #[test]
+    fn test_custom_mask_radius_two() {
+        // Seuls les coins du carré 5x5 font partie du voisinage
+        let mut mask = vec![false; 25];
+        for index in [0, 4, 20, 24] {
+            mask[index] = true;
+        }
+        let corners = Neighborhood::custom(2, mask);
+        assert_eq!(corners.radius(), 2);
+        assert_eq!(corners.size(), 4);
+
+        let mut current_cells = vec![0; 25];
+        current_cells[0] = 1;
+        current_cells[18] = 1;
+        current_cells[24] = 1;
+        assert_eq!(grid_count_neighbors_in(2, 2, &current_cells, 5, 5, false, &corners), 2);
+        // Sur une grille torique, les coins se rejoignent
+        assert_eq!(grid_count_neighbors_in(0, 0, &current_cells, 5, 5, true, &corners), 1);
+    }
+}
@@ -0,0 +1,329 @@
+use crate::{grid_index, ConwaysGrid, Grid, LifeRule};
+
+/// Paramètres d'une recherche de prédécesseur.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PredecessorOptions {
+    /// Nombre de cellules ajoutées autour de la région cible pour former la boîte du prédécesseur.
+    ///
+    /// Avec une marge égale au rayon du voisinage, l'absence de prédécesseur prouve que la région
+    /// est un jardin d'Éden : aucune cellule plus éloignée ne peut l'influencer.
+    pub margin: usize,
+    /// Nombre maximal d'affectations essayées avant d'abandonner, sans limite si `None`.
+    pub node_limit: Option<u64>,
+}
+
+impl Default for PredecessorOptions {
+    fn default() -> Self {
+        PredecessorOptions {
+            margin: 1,
+            node_limit: None,
+        }
+    }
+}
+
+/// Résultat d'une recherche de prédécesseur.
+#[derive(Clone)]
+pub enum PredecessorResult {
+    /// Une génération précédente, de la taille de la boîte, dont la génération suivante contient la
+    /// région cible décalée de la marge.
+    Found(Box<ConwaysGrid>),
+    /// Aucun prédécesseur n'existe dans la boîte.
+    GardenOfEden,
+    /// La limite d'affectations a été atteinte avant de conclure.
+    Undecided,
+}
+
+/// Terme d'une contrainte : une cellule inconnue du solveur ou un état fixé.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Term {
+    /// Cellule d'indice donné parmi les inconnues.
+    Var(usize),
+    /// État connu d'avance.
+    Const(u8),
+}
+
+/// Rôle d'une inconnue dans une contrainte.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Role {
+    Center,
+    Neighbor,
+    Target,
+}
+
+/// Contrainte de transition : l'état suivant de `center`, d'après ses voisines, doit valoir `target`.
+struct Constraint {
+    /// Nombre de voisines déjà affectées vivantes.
+    alive_neighbors: usize,
+    /// Nombre de voisines pas encore affectées.
+    unknown_neighbors: usize,
+    /// État de la cellule elle-même, `None` tant qu'il n'est pas choisi.
+    center: Option<u8>,
+    /// État voulu à la génération suivante, `None` tant qu'il n'est pas choisi.
+    target: Option<u8>,
+}
+
+/// Solveur par retour sur trace pour des cellules à deux états reliées par une règle `B/S`.
+///
+/// Les inconnues sont affectées dans l'ordre donné, la valeur morte d'abord, et chaque affectation
+/// est abandonnée dès qu'une contrainte où elle apparaît ne peut plus être satisfaite.
+pub(crate) struct CellSolver<'a> {
+    rule: &'a LifeRule,
+    constraints: Vec<Constraint>,
+    /// Pour chaque inconnue, les contraintes où elle apparaît et son rôle.
+    watches: Vec<Vec<(usize, Role)>>,
+    /// Affectation courante ; une inconnue non affectée vaut 0.
+    cells: Vec<u8>,
+    /// Ordre d'affectation des inconnues.
+    order: Vec<usize>,
+    nodes: u64,
+    node_limit: Option<u64>,
+}
+
+impl<'a> CellSolver<'a> {
+    /// Crée un solveur de `variables` inconnues, affectées dans l'ordre de leurs indices.
+    pub(crate) fn new(rule: &'a LifeRule, variables: usize, node_limit: Option<u64>) -> Self {
+        CellSolver {
+            rule,
+            constraints: Vec::new(),
+            watches: vec![Vec::new(); variables],
+            cells: vec![0; variables],
+            order: (0..variables).collect(),
+            nodes: 0,
+            node_limit,
+        }
+    }
+
+    /// Modifie l'ordre d'affectation des inconnues.
+    pub(crate) fn set_order(&mut self, order: Vec<usize>) {
+        self.order = order;
+    }
+
+    /// Ajoute la contrainte « l'état suivant de `center` avec les voisines `neighbors` vaut `target` ».
+    pub(crate) fn add_constraint(&mut self, center: Term, neighbors: &[Term], target: Term) {
+        let index = self.constraints.len();
+        let mut constraint = Constraint {
+            alive_neighbors: 0,
+            unknown_neighbors: 0,
+            center: None,
+            target: None,
+        };
+        for (term, role) in [(center, Role::Center), (target, Role::Target)] {
+            match term {
+                Term::Var(cell) => self.watches[cell].push((index, role)),
+                Term::Const(state) if role == Role::Center => constraint.center = Some(state),
+                Term::Const(state) => constraint.target = Some(state),
+            }
+        }
+        for &neighbor in neighbors {
+            match neighbor {
+                Term::Var(cell) => {
+                    self.watches[cell].push((index, Role::Neighbor));
+                    constraint.unknown_neighbors += 1;
+                }
+                Term::Const(state) => constraint.alive_neighbors += state.min(1) as usize,
+            }
+        }
+        self.constraints.push(constraint);
+    }
+
+    /// Indique si une contrainte peut encore être satisfaite par une affectation des inconnues restantes.
+    fn is_feasible(&self, constraint: &Constraint) -> bool {
+        let states = |state: Option<u8>| -> &[u8] {
+            match state {
+                Some(0) => &[0],
+                Some(_) => &[1],
+                None => &[0, 1],
+            }
+        };
+        let counts = constraint.alive_neighbors..=constraint.alive_neighbors + constraint.unknown_neighbors;
+        states(constraint.center).iter().any(|&center| {
+            counts.clone().any(|count| {
+                let next = self.rule.next_state(center, count).min(1);
+                states(constraint.target).contains(&next)
+            })
+        })
+    }
+
+    /// Affecte ou désaffecte une inconnue et renvoie `false` si une contrainte devient insatisfiable.
+    fn set(&mut self, cell: usize, state: Option<u8>) -> bool {
+        for &(index, role) in &self.watches[cell] {
+            let constraint = &mut self.constraints[index];
+            match (role, state) {
+                (Role::Center, _) => constraint.center = state,
+                (Role::Target, _) => constraint.target = state,
+                (Role::Neighbor, Some(state)) => {
+                    constraint.unknown_neighbors -= 1;
+                    constraint.alive_neighbors += state as usize;
+                }
+                (Role::Neighbor, None) => {
+                    constraint.unknown_neighbors += 1;
+                    constraint.alive_neighbors -= self.cells[cell] as usize;
+                }
+            }
+        }
+        self.cells[cell] = state.unwrap_or(0);
+        state.is_none()
+            || self.watches[cell]
+                .iter()
+                .all(|&(index, _)| self.is_feasible(&self.constraints[index]))
+    }
+
+    /// Énumère les affectations qui satisfont toutes les contraintes.
+    ///
+    /// `on_solution` est appelée sur chaque solution et renvoie `true` pour arrêter l'énumération.
+    /// Renvoie `Some(true)` si elle a été arrêtée ainsi, `Some(false)` si toutes les affectations
+    /// ont été examinées et `None` si la limite d'affectations a été atteinte.
+    pub(crate) fn solve(&mut self, on_solution: &mut impl FnMut(&[u8]) -> bool) -> Option<bool> {
+        // Une contrainte peut être insatisfiable avant toute affectation
+        if !self.constraints.iter().all(|constraint| self.is_feasible(constraint)) {
+            return Some(false);
+        }
+        self.search(0, on_solution)
+    }
+
+    fn search(&mut self, depth: usize, on_solution: &mut impl FnMut(&[u8]) -> bool) -> Option<bool> {
+        let Some(&cell) = self.order.get(depth) else {
+            return Some(on_solution(&self.cells));
+        };
+        for state in [0, 1] {
+            self.nodes += 1;
+            if self.node_limit.is_some_and(|limit| self.nodes > limit) {
+                return None;
+            }
+            let feasible = self.set(cell, Some(state));
+            let stopped = feasible && self.search(depth + 1, on_solution)?;
+            self.set(cell, None);
+            if stopped {
+                return Some(true);
+            }
+        }
+        Some(false)
+    }
+}
+
+/// Cherche une génération précédente d'une région, ou prouve qu'il n'en existe aucune dans une boîte.
+///
+/// Le prédécesseur est cherché dans la région agrandie de `options.margin` cellules de chaque
+/// côté, les cellules hors de cette boîte étant mortes ; seules les cellules de la région cible
+/// sont contraintes à la génération suivante. La recherche n'utilise pas de solveur externe : elle
+/// procède par retour sur trace, en vérifiant à chaque affectation que toutes les cellules cibles
+/// concernées peuvent encore prendre l'état voulu.
+///
+/// # Arguments
+///
+/// * `target` - Vecteur contenant l'état voulu de chaque cellule de la région.
+/// * `rows` - Nombre de lignes de la région.
+/// * `cols` - Nombre de colonnes de la région.
+/// * `rule` - La règle qui fait passer du prédécesseur à la région.
+/// * `options` - La marge de la boîte et la limite d'affectations.
+///
+/// # Exemple
+///
+/// ```
+/// use crate::lifers::{grid_predecessor, LifeRule, PredecessorOptions, PredecessorResult};
+///
+/// // Une cellule seule n'a aucun prédécesseur dans une boîte d'une seule cellule
+/// let options = PredecessorOptions { margin: 0, ..PredecessorOptions::default() };
+/// let result = grid_predecessor(&[1], 1, 1, &LifeRule::conway(), &options);
+/// assert!(matches!(result, PredecessorResult::GardenOfEden));
+///
+/// // Mais elle en a un dès que ses voisines peuvent être vivantes
+/// let result = grid_predecessor(&[1], 1, 1, &LifeRule::conway(), &PredecessorOptions::default());
+/// assert!(matches!(result, PredecessorResult::Found(_)));
+/// ```
+pub fn grid_predecessor(
+    target: &[u8],
+    rows: usize,
+    cols: usize,
+    rule: &LifeRule,
+    options: &PredecessorOptions,
+) -> PredecessorResult {
+    let margin = options.margin;
+    let (box_rows, box_cols) = (rows + 2 * margin, cols + 2 * margin);
+    let mut solver = CellSolver::new(rule, box_rows * box_cols, options.node_limit);
+    for row in 0..rows {
+        for col in 0..cols {
+            let (box_row, box_col) = (row + margin, col + margin);
+            // Les voisines hors de la boîte sont mortes
+            let neighbors: Vec<Term> = rule
+                .neighborhood()
+                .offsets(box_row)
+                .iter()
+                .map(|&(di, dj)| (box_row as isize + di, box_col as isize + dj))
+                .filter(|&(i, j)| (0..box_rows as isize).contains(&i) && (0..box_cols as isize).contains(&j))
+                .map(|(i, j)| Term::Var(grid_index(i as usize, j as usize, box_cols)))
+                .collect();
+            let target = Term::Const(target[grid_index(row, col, cols)].min(1));
+            solver.add_constraint(Term::Var(grid_index(box_row, box_col, box_cols)), &neighbors, target);
+        }
+    }
+
+    let mut found = None;
+    match solver.solve(&mut |cells| {
+        found = Some(cells.to_vec());
+        true
+    }) {
+        Some(true) => {
+            let mut grid = ConwaysGrid::from_vect(found.unwrap_or_default(), box_rows, box_cols, false);
+            grid.set_rule(rule.clone());
+            PredecessorResult::Found(Box::new(grid))
+        }
+        Some(false) => PredecessorResult::GardenOfEden,
+        None => PredecessorResult::Undecided,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConwaysGrid, Grid, Rule};
+
+    /// Vérifie que la génération suivante du prédécesseur contient la cible décalée de la marge.
+    fn check_predecessor(target: &[u8], rows: usize, cols: usize, rule: &LifeRule) {
+        let options = PredecessorOptions::default();
+        let PredecessorResult::Found(mut grid) = grid_predecessor(target, rows, cols, rule, &options) else {
+            panic!("no predecessor found");
+        };
+        assert!(matches!(grid.rule(), Rule::Life(found) if found == rule));
+        grid.update();
+        for row in 0..rows {
+            for col in 0..cols {
+                let index = grid.index(row + options.margin, col + options.margin);
+                assert_eq!(grid.current_cells()[index], target[grid_index(row, col, cols)]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_predecessors_evolve_into_target() {
+        let conway = LifeRule::conway();
+        // Clignotant, planeur et un motif sans symétrie
+        check_predecessor(&[0, 0, 0, 1, 1, 1, 0, 0, 0], 3, 3, &conway);
+        check_predecessor(&[0, 1, 0, 0, 0, 1, 1, 1, 1], 3, 3, &conway);
+        check_predecessor(&[1, 1, 0, 1, 0, 0, 1, 0, 1, 1, 0, 1, 0, 0, 1, 1], 4, 4, &conway);
+        check_predecessor(&[1, 0, 1, 1, 0, 1], 2, 3, &LifeRule::parse("B36/S23").unwrap());
+    }
+
+    #[test]
+    fn test_garden_of_eden_and_node_limit() {
+        // En Seeds, une cellule ne peut naître que morte avec exactement deux voisines vivantes :
+        // un carré plein de 3x3 n'a aucun prédécesseur
+        let seeds = LifeRule::parse("B2/S").unwrap();
+        let result = grid_predecessor(&[1; 9], 3, 3, &seeds, &PredecessorOptions::default());
+        assert!(matches!(result, PredecessorResult::GardenOfEden));
+
+        let options = PredecessorOptions { node_limit: Some(3), ..PredecessorOptions::default() };
+        let result = grid_predecessor(&[1; 16], 4, 4, &LifeRule::conway(), &options);
+        assert!(matches!(result, PredecessorResult::Undecided));
+    }
+
+    #[test]
+    fn test_region_outside_grid() {
+        let grid = ConwaysGrid::new(4, 4, false);
+        let options = PredecessorOptions::default();
+        // La région dépasse en bas, puis seulement à droite
+        assert!(grid.find_predecessor(2, 2, 3, 3, &options).is_none());
+        assert!(grid.find_predecessor(0, 2, 3, 3, &options).is_none());
+        assert!(grid.find_predecessor(1, 1, 3, 3, &options).is_some());
+    }
+}
@@ -0,0 +1,166 @@
+use ggez::{graphics::{self, Canvas, Color, Mesh, MeshBuilder, Rect}, Context, GameResult};
+
+/// Style des cellules dessinées : espacement et arrondi.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CellStyle {
+    /// Espace laissé entre deux cellules voisines, en pixels.
+    pub gap: f32,
+    /// Rayon des coins des cellules, en pixels ; les cellules sont carrées si ce rayon est nul.
+    pub corner_radius: f32,
+}
+
+impl CellStyle {
+    /// Renvoie le rectangle effectivement rempli pour une cellule occupant `rect`.
+    ///
+    /// La moitié de l'espacement est retirée de chaque côté ; une cellule garde au moins un pixel
+    /// de côté, même à très faible zoom.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use ggez::graphics::Rect;
+    /// use crate::lifers::CellStyle;
+    ///
+    /// let style = CellStyle { gap: 2.0, corner_radius: 0.0 };
+    /// assert_eq!(style.inset(Rect::new(10.0, 0.0, 8.0, 8.0)), Rect::new(11.0, 1.0, 6.0, 6.0));
+    /// ```
+    pub fn inset(&self, rect: Rect) -> Rect {
+        let gap = self.gap.clamp(0.0, (rect.w.min(rect.h) - 1.0).max(0.0));
+        Rect::new(rect.x + gap / 2.0, rect.y + gap / 2.0, rect.w - gap, rect.h - gap)
+    }
+}
+
+/// Crée le maillage d'une cellule occupant `rect`, selon le style spécifié.
+pub(crate) fn cell_mesh(ctx: &mut Context, rect: Rect, color: Color, style: &CellStyle) -> GameResult<Mesh> {
+    let rect = style.inset(rect);
+    let radius = style.corner_radius.min(rect.w.min(rect.h) / 2.0);
+    if radius > 0.0 {
+        Mesh::new_rounded_rectangle(ctx, graphics::DrawMode::fill(), rect, radius, color)
+    } else {
+        Mesh::new_rectangle(ctx, graphics::DrawMode::fill(), rect, color)
+    }
+}
+
+/// Lignes de séparation dessinées entre les cellules.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GridLines {
+    /// Couleur des lignes.
+    pub color: Color,
+    /// Épaisseur des lignes, en pixels.
+    pub thickness: f32,
+    /// Taille de cellule en dessous de laquelle les lignes sont masquées.
+    pub min_cell_size: f32,
+    /// Nombre de cellules entre deux lignes principales, sans lignes principales si `None`.
+    pub major_every: Option<usize>,
+    /// Couleur des lignes principales.
+    pub major_color: Color,
+    /// Épaisseur des lignes principales, en pixels.
+    pub major_thickness: f32,
+}
+
+impl Default for GridLines {
+    fn default() -> Self {
+        GridLines {
+            color: Color::from_rgba(128, 128, 128, 96),
+            thickness: 1.0,
+            min_cell_size: 6.0,
+            major_every: Some(10),
+            major_color: Color::from_rgba(96, 96, 96, 200),
+            major_thickness: 2.0,
+        }
+    }
+}
+
+impl GridLines {
+    /// Renvoie la couleur et l'épaisseur de la ligne qui précède la cellule `index`, ou `None` si
+    /// cette ligne est masquée au zoom `cell_size`.
+    ///
+    /// Les lignes principales restent visibles tant que l'écart qui les sépare dépasse
+    /// `min_cell_size`, ce qui garde un repère à faible zoom.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::GridLines;
+    ///
+    /// let lines = GridLines::default();
+    /// assert!(lines.line(3, 8.0).is_some());
+    /// // À faible zoom, seules les lignes principales, toutes les 10 cellules, sont dessinées
+    /// assert!(lines.line(3, 2.0).is_none());
+    /// assert_eq!(lines.line(20, 2.0), Some((lines.major_color, lines.major_thickness)));
+    /// ```
+    pub fn line(&self, index: usize, cell_size: f32) -> Option<(Color, f32)> {
+        match self.major_every {
+            Some(every) if every > 0 && index.is_multiple_of(every) && cell_size * every as f32 >= self.min_cell_size => {
+                Some((self.major_color, self.major_thickness))
+            }
+            _ if cell_size >= self.min_cell_size => Some((self.color, self.thickness)),
+            _ => None,
+        }
+    }
+}
+
+/// Dessine les lignes de séparation d'une grille de `rows` × `cols` cellules.
+///
+/// Les lignes sont centrées sur les bords des cellules, bords extérieurs compris.
+///
+/// # Arguments
+///
+/// * `ctx` - Le contexte du jeu.
+/// * `canvas` - Le canva sur lequel dessiner.
+/// * `rows` - Nombre de lignes de la grille.
+/// * `cols` - Nombre de colonnes de la grille.
+/// * `cell_size` - La taille de chaque cellule de la grille.
+/// * `lines` - La couleur, l'épaisseur et l'espacement des lignes.
+///
+/// # Erreurs
+///
+/// Cette fonction peut retourner une erreur de type `GameError` si une erreur survient lors du dessin.
+///
+pub fn draw_gridlines(ctx: &mut Context, canvas: &mut Canvas, rows: usize, cols: usize, cell_size: f32, lines: &GridLines) -> GameResult {
+    let (width, height) = (cols as f32 * cell_size, rows as f32 * cell_size);
+    let mut builder = MeshBuilder::new();
+    let mut empty = true;
+    for col in 0..=cols {
+        if let Some((color, thickness)) = lines.line(col, cell_size) {
+            let x = col as f32 * cell_size - thickness / 2.0;
+            builder.rectangle(graphics::DrawMode::fill(), Rect::new(x, 0.0, thickness, height), color)?;
+            empty = false;
+        }
+    }
+    for row in 0..=rows {
+        if let Some((color, thickness)) = lines.line(row, cell_size) {
+            let y = row as f32 * cell_size - thickness / 2.0;
+            builder.rectangle(graphics::DrawMode::fill(), Rect::new(0.0, y, width, thickness), color)?;
+            empty = false;
+        }
+    }
+    if !empty {
+        canvas.draw(&Mesh::from_data(ctx, builder.build()), graphics::DrawParam::default());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inset_keeps_at_least_one_pixel() {
+        let style = CellStyle { gap: 10.0, corner_radius: 3.0 };
+        let rect = style.inset(Rect::new(0.0, 0.0, 4.0, 4.0));
+        assert_eq!((rect.w, rect.h), (1.0, 1.0));
+        assert_eq!(CellStyle::default().inset(Rect::new(1.0, 2.0, 3.0, 3.0)), Rect::new(1.0, 2.0, 3.0, 3.0));
+    }
+
+    #[test]
+    fn test_gridlines_hide_at_low_zoom() {
+        let lines = GridLines { major_every: None, ..GridLines::default() };
+        assert_eq!(lines.line(0, 6.0), Some((lines.color, lines.thickness)));
+        assert_eq!(lines.line(0, 5.0), None);
+        // Les lignes principales disparaissent elles aussi quand leur écart devient trop petit
+        let lines = GridLines { major_every: Some(4), ..GridLines::default() };
+        assert!(lines.line(8, 1.5).is_some());
+        assert!(lines.line(8, 1.0).is_none());
+    }
+}
@@ -0,0 +1,153 @@
+use crate::grid_index;
+
+/// Point de la grille qui reste fixe lors d'un redimensionnement.
+///
+/// Les lignes et les colonnes sont ajoutées ou retirées du côté opposé à l'ancre : avec
+/// `TopLeft`, la grille grandit ou rétrécit vers le bas et vers la droite ; avec `Center`, elle
+/// grandit ou rétrécit autant de chaque côté, l'éventuelle ligne ou colonne restante étant ajoutée
+/// ou retirée en bas ou à droite.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Anchor {
+    /// Coin supérieur gauche.
+    #[default]
+    TopLeft,
+    /// Milieu du bord supérieur.
+    Top,
+    /// Coin supérieur droit.
+    TopRight,
+    /// Milieu du bord gauche.
+    Left,
+    /// Centre de la grille.
+    Center,
+    /// Milieu du bord droit.
+    Right,
+    /// Coin inférieur gauche.
+    BottomLeft,
+    /// Milieu du bord inférieur.
+    Bottom,
+    /// Coin inférieur droit.
+    BottomRight,
+}
+
+impl Anchor {
+    /// Renvoie le décalage `(lignes, colonnes)` appliqué aux cellules lors du passage d'une grille
+    /// de `rows` × `cols` cellules à une grille de `new_rows` × `new_cols` cellules.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::Anchor;
+    ///
+    /// assert_eq!(Anchor::TopLeft.offset(4, 4, 8, 6), (0, 0));
+    /// assert_eq!(Anchor::Center.offset(4, 4, 8, 6), (2, 1));
+    /// assert_eq!(Anchor::BottomRight.offset(4, 4, 2, 2), (-2, -2));
+    /// ```
+    pub fn offset(&self, rows: usize, cols: usize, new_rows: usize, new_cols: usize) -> (isize, isize) {
+        // Part de la différence de taille ajoutée avant la grille : 0, la moitié ou la totalité
+        let share = |before: usize, after: usize, part: isize| {
+            let difference = after as isize - before as isize;
+            match part {
+                0 => 0,
+                1 => difference / 2,
+                _ => difference,
+            }
+        };
+        let (vertical, horizontal) = match self {
+            Anchor::TopLeft => (0, 0),
+            Anchor::Top => (0, 1),
+            Anchor::TopRight => (0, 2),
+            Anchor::Left => (1, 0),
+            Anchor::Center => (1, 1),
+            Anchor::Right => (1, 2),
+            Anchor::BottomLeft => (2, 0),
+            Anchor::Bottom => (2, 1),
+            Anchor::BottomRight => (2, 2),
+        };
+        (share(rows, new_rows, vertical), share(cols, new_cols, horizontal))
+    }
+}
+
+/// Redimensionne une grille en conservant ses cellules.
+///
+/// Les cellules sont déplacées selon l'ancre (voir `Anchor::offset`) ; celles qui sortent de la
+/// nouvelle grille sont perdues et les nouvelles cellules sont mortes.
+///
+/// # Arguments
+///
+/// * `current_cells` - Vecteur contenant l'état de chaque cellule de la grille.
+/// * `rows` - Nombre de lignes de la grille.
+/// * `cols` - Nombre de colonnes de la grille.
+/// * `new_rows` - Nombre de lignes de la nouvelle grille.
+/// * `new_cols` - Nombre de colonnes de la nouvelle grille.
+/// * `anchor` - Le point de la grille qui reste fixe.
+///
+/// # Exemple
+///
+/// ```
+/// use crate::lifers::{grid_resize, Anchor};
+///
+/// let cells = vec![
+///     1, 2,
+///     3, 4,
+/// ];
+/// // Agrandissement autour du centre
+/// assert_eq!(grid_resize(&cells, 2, 2, 4, 3, Anchor::Center), vec![
+///     0, 0, 0,
+///     1, 2, 0,
+///     3, 4, 0,
+///     0, 0, 0,
+/// ]);
+/// // Réduction vers le coin inférieur droit
+/// assert_eq!(grid_resize(&cells, 2, 2, 1, 1, Anchor::BottomRight), vec![4]);
+/// ```
+pub fn grid_resize(
+    current_cells: &[u8],
+    rows: usize,
+    cols: usize,
+    new_rows: usize,
+    new_cols: usize,
+    anchor: Anchor,
+) -> Vec<u8> {
+    let (row_offset, col_offset) = anchor.offset(rows, cols, new_rows, new_cols);
+    let mut cells = vec![0; new_rows * new_cols];
+    for row in 0..rows {
+        let new_row = row as isize + row_offset;
+        if !(0..new_rows as isize).contains(&new_row) {
+            continue;
+        }
+        for col in 0..cols {
+            let new_col = col as isize + col_offset;
+            if (0..new_cols as isize).contains(&new_col) {
+                cells[grid_index(new_row as usize, new_col as usize, new_cols)] = current_cells[grid_index(row, col, cols)];
+            }
+        }
+    }
+    cells
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConwaysGrid, Grid};
+
+    #[test]
+    fn test_shrink_then_grow_keeps_anchored_cells() {
+        let cells: Vec<u8> = (0..12).collect();
+        let shrunk = grid_resize(&cells, 3, 4, 2, 2, Anchor::Right);
+        assert_eq!(shrunk, vec![2, 3, 6, 7]);
+        let grown = grid_resize(&shrunk, 2, 2, 3, 4, Anchor::Right);
+        assert_eq!(grown, vec![0, 0, 2, 3, 0, 0, 6, 7, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_resized_grid_keeps_evolving() {
+        // Clignotant vertical dans une grille de 3x3, agrandie en 5x5 autour du centre
+        let mut grid = ConwaysGrid::from_vect(vec![0, 1, 0, 0, 1, 0, 0, 1, 0], 3, 3, false);
+        grid.resize(5, 5, Anchor::Center);
+        assert_eq!((grid.rows(), grid.cols()), (5, 5));
+        assert!(grid.is_alive(1, 2) && grid.is_alive(2, 2) && grid.is_alive(3, 2));
+        grid.update();
+        assert!(grid.is_alive(2, 1) && grid.is_alive(2, 2) && grid.is_alive(2, 3));
+        assert_eq!(grid.current_cells().iter().filter(|&&cell| cell >= 1).count(), 3);
+    }
+}
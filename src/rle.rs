@@ -0,0 +1,222 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::grid_index;
+
+/// Longueur maximale des lignes du corps d'un fichier RLE.
+const RLE_LINE_LENGTH: usize = 70;
+
+/// Nombre maximal de cellules d'un motif lu, pour refuser les en-têtes démesurés.
+const RLE_MAX_CELLS: usize = 1 << 28;
+
+/// Erreur renvoyée lorsqu'un motif RLE ne peut pas être lu.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RleError {
+    message: String,
+}
+
+impl RleError {
+    /// Crée une nouvelle erreur avec le message spécifié.
+    pub fn new(message: impl Into<String>) -> Self {
+        RleError {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for RleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid RLE: {}", self.message)
+    }
+}
+
+impl Error for RleError {}
+
+/// Motif lu depuis un fichier RLE.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RlePattern {
+    /// Vecteur contenant l'état de chaque cellule du motif, ligne par ligne.
+    pub cells: Vec<u8>,
+    /// Nombre de lignes du motif.
+    pub rows: usize,
+    /// Nombre de colonnes du motif.
+    pub cols: usize,
+    /// Règle indiquée dans l'en-tête, s'il y en a une.
+    pub rule: Option<String>,
+}
+
+/// Encode un motif à deux états au format RLE de Golly, en-tête compris.
+///
+/// Les cellules vivantes sont écrites `o` et les mortes `b` ; les fins de ligne sont écrites `$`
+/// et le motif se termine par `!`. Les cellules mortes en fin de ligne et les lignes vides en fin
+/// de motif sont omises.
+///
+/// # Arguments
+///
+/// * `cells` - Vecteur contenant l'état de chaque cellule du motif.
+/// * `rows` - Nombre de lignes du motif.
+/// * `cols` - Nombre de colonnes du motif.
+/// * `rule` - La règle écrite dans l'en-tête.
+///
+/// # Exemple
+///
+/// ```
+/// use crate::lifers::{rle_encode, LifeRule};
+///
+/// // Planeur
+/// let cells = vec![0, 1, 0, 0, 0, 1, 1, 1, 1];
+/// assert_eq!(rle_encode(&cells, 3, 3, &LifeRule::conway()), "x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n");
+/// ```
+pub fn rle_encode(cells: &[u8], rows: usize, cols: usize, rule: &impl fmt::Display) -> String {
+    // Suite de (nombre, symbole), les lignes vides consécutives étant regroupées
+    let mut runs: Vec<(usize, char)> = Vec::new();
+    let mut push = |count: usize, symbol: char| match runs.last_mut() {
+        Some((last_count, last_symbol)) if *last_symbol == symbol => *last_count += count,
+        _ => runs.push((count, symbol)),
+    };
+    for row in 0..rows {
+        let line = &cells[grid_index(row, 0, cols)..grid_index(row, 0, cols) + cols];
+        let len = line.iter().rposition(|&cell| cell >= 1).map_or(0, |last| last + 1);
+        if row > 0 {
+            push(1, '$');
+        }
+        for &cell in &line[..len] {
+            push(1, if cell >= 1 { 'o' } else { 'b' });
+        }
+    }
+    while runs.last().is_some_and(|&(_, symbol)| symbol == '$') {
+        runs.pop();
+    }
+
+    let mut text = format!("x = {}, y = {}, rule = {}\n", cols, rows, rule);
+    let mut line = String::new();
+    let tokens = runs
+        .iter()
+        .map(|&(count, symbol)| if count == 1 { symbol.to_string() } else { format!("{}{}", count, symbol) })
+        .chain(std::iter::once(String::from("!")));
+    for token in tokens {
+        if line.len() + token.len() > RLE_LINE_LENGTH {
+            text.push_str(&line);
+            text.push('\n');
+            line.clear();
+        }
+        line.push_str(&token);
+    }
+    text.push_str(&line);
+    text.push('\n');
+    text
+}
+
+/// Lit un motif à deux états au format RLE.
+///
+/// Les lignes de commentaire `#` sont ignorées ; tout symbole autre que `b` et `.` est lu comme une
+/// cellule vivante.
+///
+/// # Exemple
+///
+/// ```
+/// use crate::lifers::rle_decode;
+///
+/// let pattern = rle_decode("#N Blinker\nx = 3, y = 1\n3o!").unwrap();
+/// assert_eq!((pattern.rows, pattern.cols), (1, 3));
+/// assert_eq!(pattern.cells, vec![1, 1, 1]);
+/// ```
+///
+/// # Erreurs
+///
+/// Renvoie une erreur si l'en-tête est absent ou mal formé, si les dimensions annoncées sont trop
+/// grandes, si un nombre de répétitions déborde ou si le motif dépasse les dimensions annoncées.
+pub fn rle_decode(text: &str) -> Result<RlePattern, RleError> {
+    let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#'));
+    let header = lines.next().ok_or_else(|| RleError::new("missing header"))?;
+    let (mut rows, mut cols, mut rule) = (None, None, None);
+    for field in header.split(',') {
+        let (key, value) = field
+            .split_once('=')
+            .ok_or_else(|| RleError::new(format!("invalid header field {:?}", field)))?;
+        let value = value.trim();
+        let number = || value.parse::<usize>().map_err(|_| RleError::new(format!("invalid size {:?}", value)));
+        match key.trim() {
+            "x" => cols = Some(number()?),
+            "y" => rows = Some(number()?),
+            "rule" => rule = Some(value.to_string()),
+            _ => {}
+        }
+    }
+    let (Some(rows), Some(cols)) = (rows, cols) else {
+        return Err(RleError::new("header must give x and y"));
+    };
+
+    let size = rows
+        .checked_mul(cols)
+        .filter(|&size| size <= RLE_MAX_CELLS)
+        .ok_or_else(|| RleError::new(format!("size {}x{} is too large", cols, rows)))?;
+    let mut cells = vec![0; size];
+    let (mut row, mut col, mut count) = (0usize, 0usize, None::<usize>);
+    let overflow = || RleError::new("run count overflows");
+    'body: for line in lines {
+        for symbol in line.chars() {
+            if let Some(digit) = symbol.to_digit(10) {
+                let value = count.unwrap_or(0).checked_mul(10).and_then(|value| value.checked_add(digit as usize));
+                count = Some(value.ok_or_else(overflow)?);
+                continue;
+            }
+            let run = count.take().unwrap_or(1);
+            match symbol {
+                '!' => break 'body,
+                '$' => {
+                    row = row.checked_add(run).ok_or_else(overflow)?;
+                    col = 0;
+                }
+                'b' | '.' => col = col.checked_add(run).ok_or_else(overflow)?,
+                symbol if symbol.is_whitespace() => {}
+                _ => {
+                    if row >= rows || col.checked_add(run).is_none_or(|end| end > cols) {
+                        return Err(RleError::new(format!("pattern exceeds its {}x{} size", cols, rows)));
+                    }
+                    cells[grid_index(row, col, cols)..grid_index(row, col, cols) + run].fill(1);
+                    col += run;
+                }
+            }
+        }
+    }
+    Ok(RlePattern { cells, rows, cols, rule })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LifeRule;
+
+    #[test]
+    fn test_round_trip_with_blank_lines_and_long_runs() {
+        let (rows, cols) = (6, 80);
+        let mut cells = vec![0; rows * cols];
+        for col in 0..80 {
+            cells[grid_index(0, col, cols)] = (col % 3 == 0) as u8;
+        }
+        cells[grid_index(4, 79, cols)] = 1;
+        let text = rle_encode(&cells, rows, cols, &LifeRule::conway());
+        assert!(text.contains("4$"));
+        assert!(text.lines().all(|line| line.len() <= RLE_LINE_LENGTH));
+
+        let pattern = rle_decode(&text).unwrap();
+        assert_eq!(pattern.rule.as_deref(), Some("B3/S23"));
+        assert_eq!((pattern.rows, pattern.cols), (rows, cols));
+        assert_eq!(pattern.cells, cells);
+    }
+
+    #[test]
+    fn test_invalid_rle() {
+        assert!(rle_decode("").is_err());
+        assert!(rle_decode("x = 2\n2o!").is_err());
+        assert!(rle_decode("x = 2, y = 1\n3o!").is_err());
+
+        // Des dimensions ou des répétitions démesurées sont refusées sans paniquer
+        assert!(rle_decode("x = 99999999999, y = 99999999999\no!").is_err());
+        assert!(rle_decode("x = 100000, y = 100000\no!").is_err());
+        assert!(rle_decode("x = 2, y = 1\n99999999999999999999999o!").is_err());
+        assert!(rle_decode("x = 2, y = 1\n18446744073709551615b18446744073709551615bo!").is_err());
+        assert!(rle_decode("x = 2, y = 1\n18446744073709551615$o!").is_err());
+    }
+}
@@ -0,0 +1,411 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::isotropic::is_hensel_rulestring;
+use crate::{
+    grid_count_neighbors_in, grid_index, grid_moore_index, ltl_next_cell_state, table_next_cell_state, IsotropicRule,
+    LtlRule, Neighborhood, TableNeighborhood, TableRule, Topology,
+};
+
+/// Erreur renvoyée lorsqu'une chaîne de règle ne peut pas être interprétée.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RuleParseError {
+    message: String,
+}
+
+impl RuleParseError {
+    /// Crée une nouvelle erreur avec le message spécifié.
+    pub fn new(message: impl Into<String>) -> Self {
+        RuleParseError {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for RuleParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid rule: {}", self.message)
+    }
+}
+
+impl Error for RuleParseError {}
+
+/// Règle totalistique externe (« Life-like ») : une cellule naît ou survit selon son nombre de voisins vivants.
+///
+/// Les règles sont écrites en notation `B/S` (`B3/S23` pour le jeu de la vie), éventuellement
+/// suivies d'un suffixe de voisinage : `H` pour hexagonal, `V` pour von Neumann.
+/// La notation `S/B` (`23/3`) est également acceptée.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LifeRule {
+    /// `birth[n]` indique si une cellule morte avec `n` voisins vivants naît.
+    birth: Vec<bool>,
+    /// `survival[n]` indique si une cellule vivante avec `n` voisins vivants survit.
+    survival: Vec<bool>,
+    /// Voisinage utilisé pour compter les voisins.
+    neighborhood: Neighborhood,
+}
+
+impl Default for LifeRule {
+    fn default() -> Self {
+        LifeRule::conway()
+    }
+}
+
+impl LifeRule {
+    /// Crée une règle à partir des nombres de voisins provoquant une naissance ou une survie.
+    ///
+    /// # Arguments
+    ///
+    /// * `birth` - Les nombres de voisins vivants pour lesquels une cellule morte naît.
+    /// * `survival` - Les nombres de voisins vivants pour lesquels une cellule vivante survit.
+    /// * `neighborhood` - Le voisinage utilisé pour compter les voisins.
+    ///
+    /// Les nombres supérieurs à la taille du voisinage sont ignorés.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::{LifeRule, Neighborhood};
+    ///
+    /// // HighLife
+    /// let rule = LifeRule::new(&[3, 6], &[2, 3], Neighborhood::Moore);
+    /// assert_eq!(rule.to_string(), "B36/S23");
+    /// ```
+    pub fn new(birth: &[usize], survival: &[usize], neighborhood: Neighborhood) -> Self {
+        let size = neighborhood.size();
+        let mut rule = LifeRule {
+            birth: vec![false; size + 1],
+            survival: vec![false; size + 1],
+            neighborhood,
+        };
+        for &count in birth.iter().filter(|&&count| count <= size) {
+            rule.birth[count] = true;
+        }
+        for &count in survival.iter().filter(|&&count| count <= size) {
+            rule.survival[count] = true;
+        }
+        rule
+    }
+
+    /// Renvoie la règle du jeu de la vie de Conway (`B3/S23`).
+    pub fn conway() -> Self {
+        LifeRule::new(&[3], &[2, 3], Neighborhood::Moore)
+    }
+
+    /// Interprète une chaîne de règle en notation `B/S` ou `S/B`.
+    ///
+    /// # Erreurs
+    ///
+    /// Renvoie une `RuleParseError` si la chaîne est mal formée ou si un nombre de voisins
+    /// dépasse la taille du voisinage.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::{LifeRule, Neighborhood};
+    ///
+    /// let rule = LifeRule::parse("B2/S34H").unwrap();
+    /// assert_eq!(rule.neighborhood(), &Neighborhood::Hexagonal);
+    /// assert!(rule.is_birth(2));
+    /// assert!(rule.is_survival(4));
+    ///
+    /// assert!(LifeRule::parse("B9/S23").is_err());
+    /// ```
+    pub fn parse(rulestring: &str) -> Result<Self, RuleParseError> {
+        let trimmed = rulestring.trim();
+        let (body, neighborhood) = match trimmed.chars().last() {
+            Some('H') | Some('h') => (&trimmed[..trimmed.len() - 1], Neighborhood::Hexagonal),
+            Some('V') | Some('v') => (&trimmed[..trimmed.len() - 1], Neighborhood::VonNeumann),
+            Some('M') | Some('m') => (&trimmed[..trimmed.len() - 1], Neighborhood::Moore),
+            _ => (trimmed, Neighborhood::Moore),
+        };
+
+        let (birth, survival) = split_birth_survival(body)
+            .ok_or_else(|| RuleParseError::new(format!("`{}` is not in B/S notation", rulestring)))?;
+
+        let size = neighborhood.size();
+        let birth = parse_counts(birth, size)?;
+        let survival = parse_counts(survival, size)?;
+        Ok(LifeRule::new(&birth, &survival, neighborhood))
+    }
+
+    /// Renvoie le voisinage utilisé par la règle.
+    pub fn neighborhood(&self) -> &Neighborhood {
+        &self.neighborhood
+    }
+
+    /// Indique si une cellule morte avec `count` voisins vivants naît.
+    pub fn is_birth(&self, count: usize) -> bool {
+        self.birth.get(count).copied().unwrap_or(false)
+    }
+
+    /// Indique si une cellule vivante avec `count` voisins vivants survit.
+    pub fn is_survival(&self, count: usize) -> bool {
+        self.survival.get(count).copied().unwrap_or(false)
+    }
+
+    /// Calcule l'état suivant d'une cellule à partir de son état et de son nombre de voisins vivants.
+    ///
+    /// Une cellule qui survit conserve sa valeur, une cellule qui naît prend la valeur 1.
+    pub fn next_state(&self, cell: u8, count: usize) -> u8 {
+        if cell >= 1 {
+            if self.is_survival(count) {
+                cell
+            } else {
+                0
+            }
+        } else if self.is_birth(count) {
+            1
+        } else {
+            0
+        }
+    }
+}
+
+impl FromStr for LifeRule {
+    type Err = RuleParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        LifeRule::parse(s)
+    }
+}
+
+impl fmt::Display for LifeRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let counts = |table: &[bool]| -> String {
+            table
+                .iter()
+                .enumerate()
+                .filter(|(_, &enabled)| enabled)
+                .map(|(count, _)| count.to_string())
+                .collect()
+        };
+        write!(
+            f,
+            "B{}/S{}{}",
+            counts(&self.birth),
+            counts(&self.survival),
+            self.neighborhood.suffix()
+        )
+    }
+}
+
+/// Règle appliquée par une grille, quelle que soit sa famille.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Rule {
+    /// Règle totalistique externe en notation `B/S`.
+    Life(LifeRule),
+    /// Règle Larger than Life de rayon `r`.
+    LargerThanLife(LtlRule),
+    /// Règle isotrope non totalistique en notation de Hensel.
+    Isotropic(IsotropicRule),
+    /// Règle chargée depuis un fichier `@RULE` de Golly, partagée entre les grilles qui l'utilisent.
+    Table(Arc<TableRule>),
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        Rule::Life(LifeRule::conway())
+    }
+}
+
+impl Rule {
+    /// Interprète une chaîne de règle en reconnaissant sa famille à partir de sa syntaxe.
+    ///
+    /// # Erreurs
+    ///
+    /// Renvoie une `RuleParseError` si la chaîne ne correspond à aucune famille de règles.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::Rule;
+    ///
+    /// assert!(matches!(Rule::parse("B36/S23"), Ok(Rule::Life(_))));
+    /// assert!(matches!(Rule::parse("R5,C0,M1,S34..58,B34..45,NM"), Ok(Rule::LargerThanLife(_))));
+    /// assert!(matches!(Rule::parse("B2n3/S23-q"), Ok(Rule::Isotropic(_))));
+    /// ```
+    pub fn parse(rulestring: &str) -> Result<Self, RuleParseError> {
+        let trimmed = rulestring.trim();
+        let mut chars = trimmed.chars();
+        match (chars.next(), chars.next()) {
+            (Some('R') | Some('r'), Some(c)) if c.is_ascii_digit() => {
+                LtlRule::parse(trimmed).map(Rule::LargerThanLife)
+            }
+            _ if is_hensel_rulestring(trimmed) => {
+                IsotropicRule::parse(trimmed).map(Rule::Isotropic)
+            }
+            _ => LifeRule::parse(trimmed).map(Rule::Life),
+        }
+    }
+
+    /// Calcule l'état suivant d'une seule cellule à partir de l'état actuel de la grille.
+    ///
+    /// Cette méthode permet les mises à jour asynchrones, où les cellules sont modifiées sur place
+    /// une par une au lieu d'être toutes calculées à partir de la génération précédente.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - L'indice de la ligne de la cellule dans la grille.
+    /// * `col` - L'indice de la colonne de la cellule dans la grille.
+    /// * `current_cells` - Vecteur contenant l'état actuel de chaque cellule de la grille.
+    /// * `rows` - Nombre de lignes de la grille.
+    /// * `cols` - Nombre de colonnes de la grille.
+    /// * `topology` - La topologie de la grille.
+    pub fn next_cell_state(
+        &self,
+        row: usize,
+        col: usize,
+        current_cells: &[u8],
+        rows: usize,
+        cols: usize,
+        topology: impl Into<Topology>,
+    ) -> u8 {
+        let topology = topology.into();
+        let cell = current_cells[grid_index(row, col, cols)];
+        match self {
+            Rule::Life(rule) => {
+                let count = grid_count_neighbors_in(row, col, current_cells, rows, cols, topology, rule.neighborhood());
+                rule.next_state(cell, count)
+            }
+            Rule::LargerThanLife(rule) => ltl_next_cell_state(row, col, current_cells, rows, cols, topology, rule),
+            Rule::Isotropic(rule) => rule.next_state(cell, grid_moore_index(row, col, current_cells, rows, cols, topology)),
+            Rule::Table(rule) => table_next_cell_state(row, col, current_cells, rows, cols, topology, rule),
+        }
+    }
+
+    /// Renvoie le voisinage utilisé pour compter les voisins d'une cellule.
+    pub fn neighborhood(&self) -> Neighborhood {
+        match self {
+            Rule::Life(rule) => rule.neighborhood().clone(),
+            Rule::LargerThanLife(rule) => rule.neighborhood(),
+            Rule::Isotropic(_) => Neighborhood::Moore,
+            Rule::Table(rule) => match rule.neighborhood() {
+                TableNeighborhood::Moore => Neighborhood::Moore,
+                TableNeighborhood::VonNeumann => Neighborhood::VonNeumann,
+                TableNeighborhood::Hexagonal => Neighborhood::Hexagonal,
+                TableNeighborhood::OneDimensional => Neighborhood::custom(1, vec![false, false, false, true, false, true, false, false, false]),
+            },
+        }
+    }
+}
+
+impl From<LifeRule> for Rule {
+    fn from(rule: LifeRule) -> Self {
+        Rule::Life(rule)
+    }
+}
+
+impl From<LtlRule> for Rule {
+    fn from(rule: LtlRule) -> Self {
+        Rule::LargerThanLife(rule)
+    }
+}
+
+impl From<IsotropicRule> for Rule {
+    fn from(rule: IsotropicRule) -> Self {
+        Rule::Isotropic(rule)
+    }
+}
+
+impl From<TableRule> for Rule {
+    fn from(rule: TableRule) -> Self {
+        Rule::Table(Arc::new(rule))
+    }
+}
+
+impl FromStr for Rule {
+    type Err = RuleParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Rule::parse(s)
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rule::Life(rule) => rule.fmt(f),
+            Rule::LargerThanLife(rule) => rule.fmt(f),
+            Rule::Isotropic(rule) => rule.fmt(f),
+            Rule::Table(rule) => rule.fmt(f),
+        }
+    }
+}
+
+/// Sépare une règle en ses parties naissance et survie, en acceptant `B3/S23`, `b3s23`, `S23/B3` et `23/3`.
+fn split_birth_survival(body: &str) -> Option<(&str, &str)> {
+    let lower = body.to_ascii_lowercase();
+    if let (Some(b), Some(s)) = (lower.find('b'), lower.find('s')) {
+        // Notation explicite : chaque partie commence par sa lettre
+        let strip = |start: usize, end: usize| body[start + 1..end].trim_end_matches('/');
+        return if b < s {
+            Some((strip(b, s), strip(s, body.len())))
+        } else {
+            Some((strip(b, body.len()), strip(s, b)))
+        };
+    }
+    // Notation historique S/B sans lettres
+    let (survival, birth) = body.split_once('/')?;
+    Some((birth, survival))
+}
+
+/// Interprète une suite de chiffres en nombres de voisins, chacun ne dépassant pas `max`.
+fn parse_counts(digits: &str, max: usize) -> Result<Vec<usize>, RuleParseError> {
+    digits
+        .chars()
+        .map(|c| match c.to_digit(10) {
+            Some(count) if count as usize <= max => Ok(count as usize),
+            Some(count) => Err(RuleParseError::new(format!(
+                "{} neighbours exceed the neighbourhood size {}",
+                count, max
+            ))),
+            None => Err(RuleParseError::new(format!("unexpected character `{}`", c))),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_conway_notations() {
+        for rulestring in ["B3/S23", "b3s23", "S23/B3", "23/3", "B3/S23M"] {
+            assert_eq!(LifeRule::parse(rulestring).unwrap(), LifeRule::conway(), "{}", rulestring);
+        }
+    }
+
+    #[test]
+    fn test_parse_neighborhood_suffixes() {
+        let hex = LifeRule::parse("B2/S34H").unwrap();
+        assert_eq!(hex.neighborhood(), &Neighborhood::Hexagonal);
+        assert!(hex.is_birth(2) && !hex.is_birth(3));
+        assert!(hex.is_survival(3) && hex.is_survival(4));
+        assert_eq!(hex.to_string(), "B2/S34H");
+
+        let von_neumann = LifeRule::parse("B1/S1V").unwrap();
+        assert_eq!(von_neumann.neighborhood(), &Neighborhood::VonNeumann);
+        assert_eq!(von_neumann.to_string(), "B1/S1V");
+        // 5 voisins dépassent la taille du voisinage de von Neumann
+        assert!(LifeRule::parse("B5/S1V").is_err());
+    }
+
+    #[test]
+    fn test_parse_invalid_rules() {
+        assert!(LifeRule::parse("B3S2x").is_err());
+        assert!(LifeRule::parse("life").is_err());
+        assert!(LifeRule::parse("B7/S9H").is_err());
+    }
+
+    #[test]
+    fn test_next_state() {
+        let rule = LifeRule::conway();
+        assert_eq!(rule.next_state(0, 3), 1);
+        assert_eq!(rule.next_state(0, 2), 0);
+        assert_eq!(rule.next_state(2, 2), 2);
+        assert_eq!(rule.next_state(1, 4), 0);
+    }
+}
@@ -0,0 +1,925 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+use ggez::graphics::Color;
+
+use crate::{grid_index, RuleParseError, Topology};
+
+/// Nombre maximal d'entrées de la table de correspondance précalculée d'une règle `@TABLE`.
+const MAX_LOOKUP_SIZE: usize = 1 << 21;
+
+/// Voisinage d'une règle chargée depuis un fichier `@RULE`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TableNeighborhood {
+    /// Voisins dans l'ordre N, NE, E, SE, S, SW, W, NW.
+    Moore,
+    /// Voisins dans l'ordre N, E, S, W.
+    VonNeumann,
+    /// Voisins dans l'ordre N, E, SE, S, W, NW, placés sur la grille hexagonale à lignes décalées
+    /// de `Neighborhood::Hexagonal`.
+    Hexagonal,
+    /// Voisins dans l'ordre W, E.
+    OneDimensional,
+}
+
+impl TableNeighborhood {
+    /// Renvoie le nombre de voisins d'une cellule.
+    pub fn size(&self) -> usize {
+        match self {
+            TableNeighborhood::Moore => 8,
+            TableNeighborhood::VonNeumann => 4,
+            TableNeighborhood::Hexagonal => 6,
+            TableNeighborhood::OneDimensional => 2,
+        }
+    }
+
+    /// Renvoie les décalages `(ligne, colonne)` des voisins d'une cellule de la ligne `row`,
+    /// dans l'ordre utilisé par les transitions.
+    pub fn offsets(&self, row: usize) -> &'static [(isize, isize)] {
+        match self {
+            TableNeighborhood::Moore => &[(-1, 0), (-1, 1), (0, 1), (1, 1), (1, 0), (1, -1), (0, -1), (-1, -1)],
+            TableNeighborhood::VonNeumann => &[(-1, 0), (0, 1), (1, 0), (0, -1)],
+            TableNeighborhood::Hexagonal if row.is_multiple_of(2) => {
+                &[(-1, 0), (0, 1), (1, 0), (1, -1), (0, -1), (-1, -1)]
+            }
+            TableNeighborhood::Hexagonal => &[(-1, 1), (0, 1), (1, 1), (1, 0), (0, -1), (-1, 0)],
+            TableNeighborhood::OneDimensional => &[(0, -1), (0, 1)],
+        }
+    }
+
+    fn parse(name: &str) -> Result<Self, RuleParseError> {
+        match name.to_ascii_lowercase().as_str() {
+            "moore" => Ok(TableNeighborhood::Moore),
+            "vonneumann" => Ok(TableNeighborhood::VonNeumann),
+            "hexagonal" => Ok(TableNeighborhood::Hexagonal),
+            "onedimensional" => Ok(TableNeighborhood::OneDimensional),
+            _ => Err(RuleParseError::new(format!("unsupported neighborhood `{}`", name))),
+        }
+    }
+
+    /// Renvoie l'image de chaque voisin par le miroir de référence du voisinage.
+    fn mirror(&self, position: usize) -> usize {
+        let size = self.size();
+        match self {
+            TableNeighborhood::OneDimensional => size - 1 - position,
+            _ => (size - position) % size,
+        }
+    }
+}
+
+/// Ensemble d'états (jusqu'à 256) accepté par un terme de transition.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct StateSet([u64; 4]);
+
+impl StateSet {
+    fn empty() -> Self {
+        StateSet([0; 4])
+    }
+
+    fn single(state: u8) -> Self {
+        let mut set = StateSet::empty();
+        set.insert(state);
+        set
+    }
+
+    fn insert(&mut self, state: u8) {
+        self.0[state as usize / 64] |= 1 << (state % 64);
+    }
+
+    fn contains(&self, state: u8) -> bool {
+        self.0[state as usize / 64] & 1 << (state % 64) != 0
+    }
+
+    fn states(&self) -> impl Iterator<Item = u8> + '_ {
+        (0..=255u8).filter(|&state| self.contains(state))
+    }
+}
+
+/// Terme d'une transition : un ensemble d'états, éventuellement lié à une variable.
+///
+/// Une variable utilisée plusieurs fois dans une même transition prend la même valeur partout.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Term {
+    states: StateSet,
+    binding: Option<usize>,
+}
+
+/// État produit par une transition : un état fixe ou la valeur d'une variable liée.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Output {
+    State(u8),
+    Binding(usize),
+}
+
+/// Transition d'une règle `@TABLE` : la cellule centrale suivie de ses voisins, puis l'état produit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Transition {
+    inputs: Vec<Term>,
+    output: Output,
+}
+
+impl Transition {
+    /// Renvoie l'état produit si les entrées, dont les voisins sont pris dans l'ordre `order`,
+    /// correspondent à la transition.
+    fn apply(&self, center: u8, neighbors: &[u8], order: &[usize]) -> Option<u8> {
+        let mut bound: HashMap<usize, u8> = HashMap::new();
+        let values = std::iter::once(center).chain(order.iter().map(|&p| neighbors[p]));
+        for (term, value) in self.inputs.iter().zip(values) {
+            if !term.matches(value, &mut bound) {
+                return None;
+            }
+        }
+        Some(self.output_state(&bound))
+    }
+
+    /// Renvoie l'état produit si les voisins correspondent à la transition dans un ordre quelconque.
+    fn apply_permuted(&self, center: u8, neighbors: &[u8]) -> Option<u8> {
+        let mut bound: HashMap<usize, u8> = HashMap::new();
+        if !self.inputs[0].matches(center, &mut bound) {
+            return None;
+        }
+        let mut used = vec![false; neighbors.len()];
+        if self.assign(1, neighbors, &mut used, &mut bound) {
+            Some(self.output_state(&bound))
+        } else {
+            None
+        }
+    }
+
+    /// Recherche par retour arrière une association entre les termes restants et les voisins.
+    fn assign(&self, term: usize, neighbors: &[u8], used: &mut [bool], bound: &mut HashMap<usize, u8>) -> bool {
+        if term == self.inputs.len() {
+            return true;
+        }
+        for position in 0..neighbors.len() {
+            // Deux voisins de même valeur sont interchangeables : seul le premier libre est essayé
+            if used[position] || (0..position).any(|p| !used[p] && neighbors[p] == neighbors[position]) {
+                continue;
+            }
+            let mut attempt = bound.clone();
+            if self.inputs[term].matches(neighbors[position], &mut attempt) {
+                used[position] = true;
+                if self.assign(term + 1, neighbors, used, &mut attempt) {
+                    *bound = attempt;
+                    return true;
+                }
+                used[position] = false;
+            }
+        }
+        false
+    }
+
+    fn output_state(&self, bound: &HashMap<usize, u8>) -> u8 {
+        match self.output {
+            Output::State(state) => state,
+            Output::Binding(variable) => bound[&variable],
+        }
+    }
+}
+
+impl Term {
+    fn matches(&self, value: u8, bound: &mut HashMap<usize, u8>) -> bool {
+        if !self.states.contains(value) {
+            return false;
+        }
+        match self.binding {
+            Some(variable) => *bound.entry(variable).or_insert(value) == value,
+            None => true,
+        }
+    }
+}
+
+/// Symétries appliquées aux transitions d'une règle `@TABLE`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Symmetries {
+    /// Liste des ordres de voisins équivalents.
+    Orders(Vec<Vec<usize>>),
+    /// Toutes les permutations des voisins.
+    Permute,
+}
+
+impl Symmetries {
+    fn parse(name: &str, neighborhood: TableNeighborhood) -> Result<Self, RuleParseError> {
+        let size = neighborhood.size();
+        let name = name.to_ascii_lowercase();
+        if name == "permute" {
+            return Ok(Symmetries::Permute);
+        }
+        let (rotation, reflect) = match name.as_str() {
+            "none" => ("rotate1", false),
+            "reflect" | "reflect_horizontal" => ("rotate1", true),
+            _ => match name.strip_suffix("reflect") {
+                Some(rotation) => (rotation, true),
+                None => (name.as_str(), false),
+            },
+        };
+        let folds: usize = rotation
+            .strip_prefix("rotate")
+            .and_then(|folds| folds.parse().ok())
+            .filter(|&folds| folds > 0 && size.is_multiple_of(folds))
+            .ok_or_else(|| RuleParseError::new(format!("unsupported symmetries `{}`", name)))?;
+        // Le voisinage unidimensionnel n'a pas de rotation : son seul miroir échange W et E
+        let step = if neighborhood == TableNeighborhood::OneDimensional { size } else { size / folds };
+
+        let mut orders = Vec::new();
+        for rotation in (0..size).step_by(step) {
+            let rotated: Vec<usize> = (0..size).map(|p| (p + rotation) % size).collect();
+            if reflect {
+                orders.push(rotated.iter().map(|&p| neighborhood.mirror(p)).collect());
+            }
+            orders.push(rotated);
+        }
+        orders.sort();
+        orders.dedup();
+        Ok(Symmetries::Orders(orders))
+    }
+}
+
+/// Moteur de transition d'une règle chargée.
+#[derive(Clone, PartialEq, Eq)]
+enum Engine {
+    /// Transitions d'une section `@TABLE`, avec la table de correspondance précalculée si elle est assez petite.
+    Table {
+        transitions: Vec<Transition>,
+        symmetries: Symmetries,
+        lookup: Option<Vec<u8>>,
+    },
+    /// Arbre de décision d'une section `@TREE` : chaque nœud liste ses enfants par état d'entrée.
+    Tree { nodes: Vec<Vec<u32>>, levels: Vec<u8> },
+}
+
+/// Règle chargée depuis un fichier `@RULE` au format de Golly (`@TABLE`, `@TREE` et `@COLORS`).
+///
+/// Les transitions sont appliquées aux états `u8` existants des cellules ; une cellule dont le
+/// voisinage ne correspond à aucune transition conserve son état.
+#[derive(Clone, PartialEq, Eq)]
+pub struct TableRule {
+    name: String,
+    states: u8,
+    neighborhood: TableNeighborhood,
+    engine: Engine,
+    colors: Vec<Option<(u8, u8, u8)>>,
+}
+
+impl fmt::Debug for TableRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TableRule")
+            .field("name", &self.name)
+            .field("states", &self.states)
+            .field("neighborhood", &self.neighborhood)
+            .finish()
+    }
+}
+
+impl fmt::Display for TableRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl TableRule {
+    /// Charge une règle depuis un fichier `.rule`.
+    ///
+    /// # Erreurs
+    ///
+    /// Renvoie une `RuleParseError` si le fichier ne peut pas être lu ou s'il est mal formé.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RuleParseError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|error| RuleParseError::new(format!("cannot read {}: {}", path.display(), error)))?;
+        TableRule::parse(&text)
+    }
+
+    /// Interprète le contenu d'un fichier `@RULE`.
+    ///
+    /// Le fichier doit contenir une section `@TABLE` ou `@TREE` ; la section `@COLORS` est
+    /// facultative et les autres sections sont ignorées.
+    ///
+    /// # Erreurs
+    ///
+    /// Renvoie une `RuleParseError` indiquant la ligne fautive si le contenu est mal formé.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::TableRule;
+    ///
+    /// let rule = TableRule::parse("
+    /// @RULE Seeds
+    /// @TABLE
+    /// n_states:2
+    /// neighborhood:Moore
+    /// symmetries:permute
+    /// var a={0,1}
+    /// var b={0,1}
+    /// var c={0,1}
+    /// var d={0,1}
+    /// var e={0,1}
+    /// var f={0,1}
+    /// var g={0,1}
+    /// var h={0,1}
+    /// 0,1,1,0,0,0,0,0,0,1
+    /// 1,a,b,c,d,e,f,g,h,0
+    /// ").unwrap();
+    ///
+    /// assert_eq!(rule.name(), "Seeds");
+    /// assert_eq!(rule.next_state(0, &[1, 0, 0, 0, 1, 0, 0, 0]), 1);
+    /// assert_eq!(rule.next_state(1, &[1, 0, 0, 0, 1, 0, 0, 0]), 0);
+    /// ```
+    pub fn parse(text: &str) -> Result<Self, RuleParseError> {
+        let mut name = None;
+        let mut section = String::new();
+        let mut table_lines = Vec::new();
+        let mut tree_lines = Vec::new();
+        let mut color_lines = Vec::new();
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(header) = line.strip_prefix('@') {
+                let mut words = header.split_whitespace();
+                section = words.next().unwrap_or("").to_ascii_uppercase();
+                if section == "RULE" {
+                    name = words.next().map(str::to_string);
+                }
+                continue;
+            }
+            match section.as_str() {
+                "TABLE" => table_lines.push((number + 1, line)),
+                "TREE" => tree_lines.push((number + 1, line)),
+                "COLORS" => color_lines.push((number + 1, line)),
+                _ => {}
+            }
+        }
+
+        let name = name.ok_or_else(|| RuleParseError::new("missing @RULE header"))?;
+        let (states, neighborhood, engine) = if !table_lines.is_empty() {
+            parse_table(&table_lines)?
+        } else if !tree_lines.is_empty() {
+            parse_tree(&tree_lines)?
+        } else {
+            return Err(RuleParseError::new("missing @TABLE or @TREE section"));
+        };
+
+        let mut colors = vec![None; states as usize];
+        for (number, line) in color_lines {
+            let values: Vec<u8> = line
+                .split_whitespace()
+                .map(|value| value.parse())
+                .collect::<Result<_, _>>()
+                .map_err(|_| line_error(number, "invalid color"))?;
+            match values.as_slice() {
+                [state, r, g, b, ..] if (*state as usize) < colors.len() => {
+                    colors[*state as usize] = Some((*r, *g, *b));
+                }
+                _ => return Err(line_error(number, "expected `state r g b`")),
+            }
+        }
+
+        Ok(TableRule {
+            name,
+            states,
+            neighborhood,
+            engine,
+            colors,
+        })
+    }
+
+    /// Renvoie le nom de la règle.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Renvoie le nombre d'états de la règle.
+    pub fn states(&self) -> u8 {
+        self.states
+    }
+
+    /// Renvoie le voisinage de la règle.
+    pub fn neighborhood(&self) -> TableNeighborhood {
+        self.neighborhood
+    }
+
+    /// Renvoie la couleur de chaque état définie par la section `@COLORS`, `None` si elle est absente.
+    pub fn palette(&self) -> Vec<Option<Color>> {
+        self.colors
+            .iter()
+            .map(|color| color.map(|(r, g, b)| Color::from_rgb(r, g, b)))
+            .collect()
+    }
+
+    /// Calcule l'état suivant d'une cellule à partir de son état et de ceux de ses voisins,
+    /// donnés dans l'ordre du voisinage (voir `TableNeighborhood`).
+    ///
+    /// Tous les états doivent être inférieurs à `states()` ; `table_next_cell_state` ramène les
+    /// autres au dernier état de la règle.
+    pub fn next_state(&self, center: u8, neighbors: &[u8]) -> u8 {
+        match &self.engine {
+            Engine::Table {
+                lookup: Some(lookup),
+                ..
+            } => lookup[self.lookup_index(center, neighbors)],
+            Engine::Table {
+                transitions,
+                symmetries,
+                lookup: None,
+            } => match_transitions(transitions, symmetries, center, neighbors).unwrap_or(center),
+            Engine::Tree { nodes, levels } => {
+                // Ordre des entrées de l'arbre : coins, puis côtés, puis cellule centrale
+                let order: &[usize] = match self.neighborhood {
+                    TableNeighborhood::Moore => &[7, 1, 5, 3, 0, 6, 2, 4],
+                    _ => &[0, 3, 1, 2],
+                };
+                let mut node = nodes.len() - 1;
+                for value in order.iter().map(|&p| neighbors[p]).chain(std::iter::once(center)) {
+                    let child = nodes[node][value as usize];
+                    if levels[node] == 1 {
+                        return child as u8;
+                    }
+                    node = child as usize;
+                }
+                center
+            }
+        }
+    }
+
+    fn lookup_index(&self, center: u8, neighbors: &[u8]) -> usize {
+        neighbors
+            .iter()
+            .fold(center as usize, |index, &state| index * self.states as usize + state as usize)
+    }
+}
+
+/// Renvoie l'état produit par la première transition correspondante, `None` si aucune ne correspond.
+fn match_transitions(transitions: &[Transition], symmetries: &Symmetries, center: u8, neighbors: &[u8]) -> Option<u8> {
+    transitions.iter().find_map(|transition| match symmetries {
+        Symmetries::Permute => transition.apply_permuted(center, neighbors),
+        Symmetries::Orders(orders) => orders
+            .iter()
+            .find_map(|order| transition.apply(center, neighbors, order)),
+    })
+}
+
+fn line_error(number: usize, message: &str) -> RuleParseError {
+    RuleParseError::new(format!("line {}: {}", number, message))
+}
+
+/// Découpe une ligne de transition en termes, en respectant les ensembles entre accolades.
+fn split_terms(line: &str) -> Vec<String> {
+    if !line.contains(',') && !line.contains(char::is_whitespace) {
+        // Forme compacte : chaque caractère est un état
+        return line.chars().map(String::from).collect();
+    }
+    let mut terms = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0;
+    for c in line.chars() {
+        match c {
+            '{' => {
+                depth += 1;
+                current.push(c);
+            }
+            '}' => {
+                depth -= 1;
+                current.push(c);
+            }
+            ',' | ' ' | '\t' if depth == 0 => {
+                if !current.trim().is_empty() {
+                    terms.push(current.trim().to_string());
+                }
+                current.clear();
+            }
+            _ => current.push(c),
+        }
+    }
+    if !current.trim().is_empty() {
+        terms.push(current.trim().to_string());
+    }
+    terms
+}
+
+/// Interprète un ensemble `{0,1,a}` dont les membres sont des états ou des variables déjà définies.
+fn parse_set(text: &str, variables: &HashMap<String, (usize, StateSet)>, states: u8) -> Option<StateSet> {
+    let inner = text.strip_prefix('{')?.strip_suffix('}')?;
+    let mut set = StateSet::empty();
+    for member in inner.split(',').map(str::trim) {
+        match member.parse::<u8>() {
+            Ok(state) if state < states => set.insert(state),
+            Ok(_) => return None,
+            Err(_) => {
+                let (_, other) = variables.get(member)?;
+                for state in other.states() {
+                    set.insert(state);
+                }
+            }
+        }
+    }
+    Some(set)
+}
+
+/// Interprète les lignes d'une section `@TABLE`.
+fn parse_table(lines: &[(usize, &str)]) -> Result<(u8, TableNeighborhood, Engine), RuleParseError> {
+    let mut states = None;
+    let mut neighborhood = TableNeighborhood::Moore;
+    let mut symmetries_name = String::from("none");
+    let mut variables: HashMap<String, (usize, StateSet)> = HashMap::new();
+    let mut transitions = Vec::new();
+
+    for &(number, line) in lines {
+        if let Some((key, value)) = line.split_once(':') {
+            let value = value.trim();
+            match key.trim().to_ascii_lowercase().as_str() {
+                "n_states" => {
+                    let count: usize = value.parse().map_err(|_| line_error(number, "invalid n_states"))?;
+                    if !(2..=255).contains(&count) {
+                        return Err(line_error(number, "n_states must be between 2 and 255"));
+                    }
+                    states = Some(count as u8);
+                }
+                "neighborhood" => neighborhood = TableNeighborhood::parse(value).map_err(|e| line_error(number, &e.to_string()))?,
+                "symmetries" => symmetries_name = value.to_string(),
+                _ => return Err(line_error(number, "unknown setting")),
+            }
+            continue;
+        }
+
+        let states = states.ok_or_else(|| line_error(number, "n_states must come first"))?;
+        if let Some(definition) = line.strip_prefix("var ") {
+            let (variable, set) = definition
+                .split_once('=')
+                .ok_or_else(|| line_error(number, "expected `var name={...}`"))?;
+            let set = parse_set(&set.replace(' ', ""), &variables, states)
+                .ok_or_else(|| line_error(number, "invalid variable set"))?;
+            let id = variables.len();
+            variables.insert(variable.trim().to_string(), (id, set));
+            continue;
+        }
+
+        let terms = split_terms(line);
+        if terms.len() != neighborhood.size() + 2 {
+            return Err(line_error(number, "wrong number of states in transition"));
+        }
+        // Seules les variables utilisées plusieurs fois sont liées
+        let mut uses: HashMap<&str, usize> = HashMap::new();
+        for term in &terms {
+            *uses.entry(term.as_str()).or_default() += 1;
+        }
+        let mut inputs = Vec::with_capacity(terms.len() - 1);
+        for term in &terms[..terms.len() - 1] {
+            let parsed = if let Ok(state) = term.parse::<u8>() {
+                (state < states).then(|| Term { states: StateSet::single(state), binding: None })
+            } else if let Some((id, set)) = variables.get(term.as_str()) {
+                Some(Term { states: set.clone(), binding: (uses[term.as_str()] > 1).then_some(*id) })
+            } else {
+                parse_set(term, &variables, states).map(|set| Term { states: set, binding: None })
+            };
+            inputs.push(parsed.ok_or_else(|| line_error(number, &format!("invalid state `{}`", term)))?);
+        }
+        let last = &terms[terms.len() - 1];
+        let output = match last.parse::<u8>() {
+            Ok(state) if state < states => Output::State(state),
+            Ok(_) => return Err(line_error(number, "output state out of range")),
+            Err(_) => match variables.get(last.as_str()) {
+                Some((id, _)) if uses[last.as_str()] > 1 => Output::Binding(*id),
+                _ => return Err(line_error(number, &format!("output `{}` is not bound to an input", last))),
+            },
+        };
+        transitions.push(Transition { inputs, output });
+    }
+
+    let states = states.ok_or_else(|| RuleParseError::new("missing n_states in @TABLE"))?;
+    let symmetries = Symmetries::parse(&symmetries_name, neighborhood)?;
+    let lookup = build_lookup(&transitions, &symmetries, states, neighborhood.size());
+    Ok((
+        states,
+        neighborhood,
+        Engine::Table {
+            transitions,
+            symmetries,
+            lookup,
+        },
+    ))
+}
+
+/// Précalcule l'état suivant de chaque combinaison d'entrées si la table n'est pas trop grande.
+fn build_lookup(transitions: &[Transition], symmetries: &Symmetries, states: u8, neighbors: usize) -> Option<Vec<u8>> {
+    let size = (states as usize).checked_pow(neighbors as u32 + 1)?;
+    if size > MAX_LOOKUP_SIZE {
+        return None;
+    }
+    let mut lookup = Vec::with_capacity(size);
+    // Avec la symétrie `permute`, le résultat ne dépend que du multiensemble des voisins
+    let mut permuted: HashMap<(u8, Vec<u8>), u8> = HashMap::new();
+    let mut inputs = vec![0u8; neighbors + 1];
+    for _ in 0..size {
+        let (center, neighbors) = (inputs[0], &inputs[1..]);
+        let next = match symmetries {
+            Symmetries::Permute => {
+                let mut sorted = neighbors.to_vec();
+                sorted.sort_unstable();
+                *permuted.entry((center, sorted)).or_insert_with(|| {
+                    match_transitions(transitions, symmetries, center, neighbors).unwrap_or(center)
+                })
+            }
+            Symmetries::Orders(_) => match_transitions(transitions, symmetries, center, neighbors).unwrap_or(center),
+        };
+        lookup.push(next);
+        // Passe à la combinaison suivante, le dernier voisin variant le plus vite
+        for value in inputs.iter_mut().rev() {
+            *value += 1;
+            if *value < states {
+                break;
+            }
+            *value = 0;
+        }
+    }
+    Some(lookup)
+}
+
+/// Interprète les lignes d'une section `@TREE`.
+fn parse_tree(lines: &[(usize, &str)]) -> Result<(u8, TableNeighborhood, Engine), RuleParseError> {
+    let mut states = None;
+    let mut neighborhood = None;
+    let mut nodes = Vec::new();
+    let mut levels = Vec::new();
+
+    for &(number, line) in lines {
+        if let Some((key, value)) = line.split_once('=') {
+            let value: usize = value.trim().parse().map_err(|_| line_error(number, "invalid number"))?;
+            match key.trim() {
+                "num_states" if (2..=255).contains(&value) => states = Some(value as u8),
+                "num_neighbors" if value == 8 => neighborhood = Some(TableNeighborhood::Moore),
+                "num_neighbors" if value == 4 => neighborhood = Some(TableNeighborhood::VonNeumann),
+                "num_nodes" => nodes.reserve(value),
+                _ => return Err(line_error(number, "invalid tree setting")),
+            }
+            continue;
+        }
+        let states = states.ok_or_else(|| line_error(number, "num_states must come first"))?;
+        let values: Vec<u32> = line
+            .split_whitespace()
+            .map(|value| value.parse())
+            .collect::<Result<_, _>>()
+            .map_err(|_| line_error(number, "invalid node"))?;
+        let (level, children) = values.split_first().ok_or_else(|| line_error(number, "empty node"))?;
+        let valid = children.len() == states as usize
+            && *level >= 1
+            && children.iter().all(|&child| {
+                if *level == 1 {
+                    child < states as u32
+                } else {
+                    (child as usize) < nodes.len() && levels[child as usize] as u32 == level - 1
+                }
+            });
+        if !valid {
+            return Err(line_error(number, "invalid node"));
+        }
+        levels.push(*level as u8);
+        nodes.push(children.to_vec());
+    }
+
+    let states = states.ok_or_else(|| RuleParseError::new("missing num_states in @TREE"))?;
+    let neighborhood = neighborhood.ok_or_else(|| RuleParseError::new("missing num_neighbors in @TREE"))?;
+    if levels.last().map(|&level| level as usize) != Some(neighborhood.size() + 1) {
+        return Err(RuleParseError::new("the last tree node must be the root"));
+    }
+    Ok((states, neighborhood, Engine::Tree { nodes, levels }))
+}
+
+/// Met à jour l'état de la grille selon une règle chargée depuis un fichier `@RULE`.
+///
+/// Les voisins situés hors d'une grille non torique sont considérés dans l'état 0.
+///
+/// # Arguments
+///
+/// * `current_cells` - Vecteur contenant l'état actuel de chaque cellule de la grille.
+/// * `next_cells` - Vecteur contenant l'état suivant de chaque cellule de la grille.
+/// * `rows` - Nombre de lignes de la grille.
+/// * `cols` - Nombre de colonnes de la grille.
+/// * `topology` - La topologie de la grille.
+/// * `rule` - La règle à appliquer.
+pub fn grid_update_table(
+    current_cells: &mut Vec<u8>,
+    next_cells: &mut Vec<u8>,
+    rows: usize,
+    cols: usize,
+    topology: impl Into<Topology>,
+    rule: &TableRule,
+) {
+    let topology = topology.into();
+    for row in 0..rows {
+        for col in 0..cols {
+            let index = grid_index(row, col, cols);
+            next_cells[index] = table_next_cell_state(row, col, current_cells, rows, cols, topology, rule);
+        }
+    }
+    std::mem::swap(current_cells, next_cells);
+}
+
+/// Calcule l'état suivant d'une cellule selon une règle chargée depuis un fichier `@RULE`.
+///
+/// # Arguments
+///
+/// * `row` - L'indice de la ligne de la cellule dans la grille.
+/// * `col` - L'indice de la colonne de la cellule dans la grille.
+/// * `current_cells` - Vecteur contenant l'état actuel de chaque cellule de la grille.
+/// * `rows` - Nombre de lignes de la grille.
+/// * `cols` - Nombre de colonnes de la grille.
+/// * `topology` - La topologie de la grille.
+/// * `rule` - La règle à appliquer.
+pub fn table_next_cell_state(
+    row: usize,
+    col: usize,
+    current_cells: &[u8],
+    rows: usize,
+    cols: usize,
+    topology: impl Into<Topology>,
+    rule: &TableRule,
+) -> u8 {
+    let topology = topology.into();
+    let offsets = rule.neighborhood().offsets(row);
+    let mut neighbors = [0u8; 8];
+    for (neighbor, &(di, dj)) in neighbors.iter_mut().zip(offsets) {
+        let i = row as isize + di;
+        let j = col as isize + dj;
+        // Un état constant au-delà des bords est limité aux états de la règle
+        *neighbor = topology.state_at(current_cells, i, j, rows, cols).min(rule.states() - 1);
+    }
+    // De même pour la cellule elle-même, dont l'état peut venir d'une autre règle
+    let center = current_cells[grid_index(row, col, cols)].min(rule.states() - 1);
+    rule.next_state(center, &neighbors[..offsets.len()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{grid_update_rule, ColorMode, ConwaysGrid, LifeRule, WireworldGrid, Grid};
+    use rand::prelude::*;
+
+    const LIFE_TABLE: &str = "
+@RULE LifeTable
+# Le jeu de la vie écrit sous forme de table
+@TABLE
+n_states:2
+neighborhood:Moore
+symmetries:permute
+var a={0,1}
+var b={0,1}
+var c={0,1}
+var d={0,1}
+var e={0,1}
+var f={0,1}
+var g={0,1}
+var h={0,1}
+0,1,1,1,0,0,0,0,0,1
+1,1,1,0,0,0,0,0,0,1
+1,1,1,1,0,0,0,0,0,1
+1,a,b,c,d,e,f,g,h,0
+@COLORS
+1 255 255 0
+";
+
+    const WIREWORLD_TABLE: &str = "
+@RULE WireWorld
+@TABLE
+n_states:4
+neighborhood:Moore
+symmetries:rotate8
+var a={0,1,2,3}
+var b={a}
+var c={a}
+var d={a}
+var e={a}
+var f={a}
+var g={a}
+var h={a}
+var i={0,2,3}
+var j={i}
+var k={i}
+var l={i}
+var m={i}
+var n={i}
+var o={i}
+var p={i}
+1,a,b,c,d,e,f,g,h,2
+2,a,b,c,d,e,f,g,h,3
+3,1,i,j,k,l,m,n,o,1
+3,1,1,i,j,k,l,m,n,1
+3,1,i,1,j,k,l,m,n,1
+3,1,i,j,1,k,l,m,n,1
+3,1,i,j,k,1,l,m,n,1
+";
+
+    fn random_cells(rows: usize, cols: usize, states: u8, seed: u64) -> Vec<u8> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..rows * cols).map(|_| rng.gen_range(0..states)).collect()
+    }
+
+    #[test]
+    fn test_life_table_matches_life_rule() {
+        let rule = TableRule::parse(LIFE_TABLE).unwrap();
+        assert_eq!(rule.name(), "LifeTable");
+        assert_eq!(rule.palette(), vec![None, Some(Color::from_rgb(255, 255, 0))]);
+
+        let (rows, cols) = (12, 12);
+        let cells = random_cells(rows, cols, 2, 5);
+        let (mut current, mut next) = (cells.clone(), vec![0; rows * cols]);
+        let (mut expected, mut expected_next) = (cells, vec![0; rows * cols]);
+        for _ in 0..8 {
+            grid_update_table(&mut current, &mut next, rows, cols, true, &rule);
+            grid_update_rule(&mut expected, &mut expected_next, rows, cols, true, &LifeRule::conway());
+        }
+        assert_eq!(current, expected);
+    }
+
+    #[test]
+    fn test_wireworld_table_matches_wireworld_grid() {
+        let rule = TableRule::parse(WIREWORLD_TABLE).unwrap();
+        let (rows, cols) = (10, 10);
+        let cells = random_cells(rows, cols, 4, 9);
+        let mut grid = WireworldGrid::from_vect(cells.clone(), rows, cols, false);
+        let (mut current, mut next) = (cells, vec![0; rows * cols]);
+        for _ in 0..5 {
+            grid.update();
+            grid_update_table(&mut current, &mut next, rows, cols, false, &rule);
+        }
+        assert_eq!(&current, grid.current_cells());
+    }
+
+    #[test]
+    fn test_tree_rule() {
+        // Arbre en voisinage de von Neumann : une cellule naît ou survit si son voisin N est vivant
+        let tree = "
+@RULE NorthCopy
+@TREE
+num_states=2
+num_neighbors=4
+num_nodes=6
+1 0 0
+1 1 1
+2 0 0
+3 2 2
+4 3 3
+5 4 5
+";
+        // Un nœud ne peut désigner qu'un nœud défini avant lui
+        assert!(TableRule::parse(tree).is_err());
+
+        let tree = "
+@RULE NorthCopy
+@TREE
+num_states=2
+num_neighbors=4
+num_nodes=9
+1 0 0
+2 0 0
+3 1 1
+4 2 2
+1 1 1
+2 4 4
+3 5 5
+4 6 6
+5 3 7
+";
+        let rule = TableRule::parse(tree).unwrap();
+        assert_eq!(rule.neighborhood(), TableNeighborhood::VonNeumann);
+        assert_eq!(rule.next_state(0, &[1, 0, 0, 0]), 1);
+        assert_eq!(rule.next_state(1, &[0, 1, 1, 1]), 0);
+    }
+
+    #[test]
+    fn test_bound_variables_and_errors() {
+        // Une cellule naît si ses voisins N et S sont dans le même état non nul
+        let rule = TableRule::parse(
+            "@RULE Bound\n@TABLE\nn_states:3\nneighborhood:vonNeumann\nsymmetries:none\nvar a={1,2}\nvar b={0,1,2}\nvar c={b}\n0,a,b,a,c,a\n",
+        )
+        .unwrap();
+        assert_eq!(rule.next_state(0, &[2, 0, 2, 1]), 2);
+        assert_eq!(rule.next_state(0, &[2, 0, 1, 1]), 0);
+
+        assert!(TableRule::parse("@TABLE\nn_states:2\n").is_err());
+        assert!(TableRule::parse("@RULE X\n@TABLE\nn_states:2\n0,1,1,0,0,0,0,0,0\n").is_err());
+        assert!(TableRule::parse("@RULE X\n@TABLE\nn_states:2\nsymmetries:rotate3\n0,1,1,0,0,0,0,0,0,1\n").is_err());
+        assert!(TableRule::parse("@RULE X\n@TABLE\nn_states:2\nvar a={0,1}\nvar b={0,1}\n0,a,1,0,0,0,0,0,0,b\n").is_err());
+    }
+
+    #[test]
+    fn test_states_beyond_rule_are_clamped() {
+        // Une cellule d'état 2 sous une règle à deux états se comporte comme une cellule vivante
+        let mut grid = ConwaysGrid::new(3, 3, false);
+        grid.set_rule(TableRule::parse(LIFE_TABLE).unwrap());
+        grid.set_cell_state(1, 1, 2);
+        grid.update();
+        assert!(!grid.is_alive(1, 1));
+
+        // Même chose lorsqu'une grille colorée reçoit une règle en table
+        let mut grid = ConwaysGrid::from_vect(vec![0, 3, 0, 0, 4, 0, 0, 2, 0], 3, 3, false);
+        grid.set_color_mode(ColorMode::QuadLife);
+        grid.set_rule(TableRule::parse(LIFE_TABLE).unwrap());
+        grid.update();
+        assert_eq!(grid.current_cells(), &[0, 0, 0, 1, 1, 1, 0, 0, 0]);
+    }
+}
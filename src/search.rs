@@ -0,0 +1,621 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+use std::thread;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    classify_object, grid_census, grid_components, grid_index, grid_update_rule, Census, CensusOptions, LifeRule,
+    ObjectKind, RuleParseError, Topology,
+};
+
+/// Côté des soupes générées, en cellules.
+pub const SOUP_SIZE: usize = 16;
+
+/// Nombre maximal de graines conservées pour chaque objet du décompte.
+pub const SAMPLE_SEEDS: usize = 3;
+
+/// Largeur du bord de la grille de simulation où les objets qui s'échappent sont retirés.
+const ESCAPE_MARGIN: usize = 2;
+
+/// Période maximale de la population pour qu'une soupe soit considérée comme stabilisée.
+const MAX_STABLE_PERIOD: usize = 12;
+
+/// Nombre de générations pendant lesquelles la population doit rester périodique.
+const STABLE_WINDOW: usize = 60;
+
+/// Erreur renvoyée lorsqu'une symétrie ou un décompte de recherche ne peut pas être lu.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchError {
+    message: String,
+}
+
+impl SearchError {
+    /// Crée une nouvelle erreur avec le message spécifié.
+    pub fn new(message: impl Into<String>) -> Self {
+        SearchError {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for SearchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid soup search: {}", self.message)
+    }
+}
+
+impl Error for SearchError {}
+
+/// Symétrie imposée aux soupes, nommée comme dans apgsearch.
+///
+/// Les soupes ayant un côté pair, les centres de rotation et les axes de réflexion passent entre
+/// les cellules : ce sont les symétries `C2_4`, `C4_4`, `D2_+2` et `D8_4` d'apgsearch.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SoupSymmetry {
+    /// Aucune symétrie.
+    #[default]
+    C1,
+    /// Symétrie de rotation d'un demi-tour.
+    C2,
+    /// Symétrie de rotation d'un quart de tour.
+    C4,
+    /// Symétrie par réflexion autour de l'axe vertical.
+    D2,
+    /// Toutes les symétries du carré.
+    D8,
+}
+
+impl SoupSymmetry {
+    /// Renvoie les images d'une cellule `(ligne, colonne)` de la soupe par les symétries imposées.
+    fn orbit(&self, row: usize, col: usize) -> Vec<(usize, usize)> {
+        let n = SOUP_SIZE - 1;
+        match self {
+            SoupSymmetry::C1 => vec![(row, col)],
+            SoupSymmetry::C2 => vec![(row, col), (n - row, n - col)],
+            SoupSymmetry::C4 => vec![(row, col), (col, n - row), (n - row, n - col), (n - col, row)],
+            SoupSymmetry::D2 => vec![(row, col), (row, n - col)],
+            SoupSymmetry::D8 => vec![
+                (row, col),
+                (col, n - row),
+                (n - row, n - col),
+                (n - col, row),
+                (row, n - col),
+                (n - row, col),
+                (col, row),
+                (n - col, n - row),
+            ],
+        }
+    }
+}
+
+impl fmt::Display for SoupSymmetry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SoupSymmetry::C1 => "C1",
+            SoupSymmetry::C2 => "C2_4",
+            SoupSymmetry::C4 => "C4_4",
+            SoupSymmetry::D2 => "D2_+2",
+            SoupSymmetry::D8 => "D8_4",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for SoupSymmetry {
+    type Err = SearchError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "C1" => Ok(SoupSymmetry::C1),
+            "C2_4" => Ok(SoupSymmetry::C2),
+            "C4_4" => Ok(SoupSymmetry::C4),
+            "D2_+2" => Ok(SoupSymmetry::D2),
+            "D8_4" => Ok(SoupSymmetry::D8),
+            _ => Err(SearchError::new(format!(
+                "unknown symmetry {:?} (expected C1, C2_4, C4_4, D2_+2 or D8_4)",
+                name
+            ))),
+        }
+    }
+}
+
+/// Génère la soupe de `SOUP_SIZE` × `SOUP_SIZE` cellules correspondant à une graine.
+///
+/// Chaque cellule est vivante avec une probabilité d'un demi, les cellules liées par la symétrie
+/// ayant le même état. Une même graine donne toujours la même soupe.
+///
+/// # Exemple
+///
+/// ```
+/// use crate::lifers::{soup, SoupSymmetry, SOUP_SIZE};
+///
+/// let cells = soup(42, SoupSymmetry::D2);
+/// assert_eq!(cells, soup(42, SoupSymmetry::D2));
+/// assert_eq!(cells[0], cells[SOUP_SIZE - 1]);
+/// ```
+pub fn soup(seed: u64, symmetry: SoupSymmetry) -> Vec<u8> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut cells = vec![0; SOUP_SIZE * SOUP_SIZE];
+    for row in 0..SOUP_SIZE {
+        for col in 0..SOUP_SIZE {
+            let orbit = symmetry.orbit(row, col);
+            // Seul le premier représentant de chaque orbite tire son état
+            if orbit.iter().any(|&(i, j)| (i, j) < (row, col)) {
+                continue;
+            }
+            let state = rng.gen_bool(0.5) as u8;
+            for (i, j) in orbit {
+                cells[grid_index(i, j, SOUP_SIZE)] = state;
+            }
+        }
+    }
+    cells
+}
+
+/// Paramètres d'une recherche de soupes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchOptions {
+    /// Symétrie imposée aux soupes.
+    pub symmetry: SoupSymmetry,
+    /// Côté de la grille sur laquelle chaque soupe évolue.
+    pub board_size: usize,
+    /// Nombre maximal de générations avant le recensement d'une soupe qui ne se stabilise pas.
+    pub max_generations: usize,
+    /// Nombre de fils d'exécution.
+    pub threads: usize,
+    /// Paramètres du recensement, dont la règle.
+    pub census: CensusOptions,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        SearchOptions {
+            symmetry: SoupSymmetry::default(),
+            board_size: 96,
+            max_generations: 2000,
+            threads: thread::available_parallelism().map_or(1, |threads| threads.get()),
+            census: CensusOptions::default(),
+        }
+    }
+}
+
+/// Indique si la population est périodique sur les dernières générations.
+fn is_stable(populations: &[usize]) -> bool {
+    let len = populations.len();
+    len > STABLE_WINDOW + MAX_STABLE_PERIOD
+        && (1..=MAX_STABLE_PERIOD).any(|period| {
+            (len - STABLE_WINDOW..len).all(|generation| populations[generation] == populations[generation - period])
+        })
+}
+
+/// Fait évoluer la soupe d'une graine jusqu'à sa stabilisation et recense les objets obtenus
+/// (voir `grid_stabilised_census`).
+///
+/// # Arguments
+///
+/// * `seed` - La graine de la soupe (voir `soup`).
+/// * `options` - Les paramètres de la recherche.
+pub fn run_soup(seed: u64, options: &SearchOptions) -> Census {
+    grid_stabilised_census(
+        &soup(seed, options.symmetry),
+        SOUP_SIZE,
+        SOUP_SIZE,
+        options.board_size,
+        options.max_generations,
+        &options.census,
+    )
+}
+
+/// Fait évoluer un motif jusqu'à sa stabilisation et recense les objets obtenus.
+///
+/// Le motif est placé au centre d'une grille plane carrée de côté `board_size`, agrandie si
+/// nécessaire. Les objets qui atteignent le bord de la grille sont retirés avant qu'ils ne le
+/// touchent : les vaisseaux, comme les planeurs, sont recensés, les autres débris sont effacés.
+/// Le motif est considéré comme stabilisé lorsque sa population est périodique ; il est recensé
+/// au plus tard après `max_generations` générations.
+///
+/// # Arguments
+///
+/// * `current_cells` - Vecteur contenant l'état de chaque cellule du motif.
+/// * `rows` - Nombre de lignes du motif.
+/// * `cols` - Nombre de colonnes du motif.
+/// * `board_size` - Côté minimal de la grille de simulation.
+/// * `max_generations` - Nombre maximal de générations avant le recensement.
+/// * `options` - Les paramètres du recensement, dont la règle.
+pub fn grid_stabilised_census(
+    current_cells: &[u8],
+    rows: usize,
+    cols: usize,
+    board_size: usize,
+    max_generations: usize,
+    options: &CensusOptions,
+) -> Census {
+    let size = board_size.max(rows.max(cols) + 4 * ESCAPE_MARGIN);
+    let (row_offset, col_offset) = ((size - rows) / 2, (size - cols) / 2);
+    let mut current = vec![0u8; size * size];
+    let mut next = vec![0u8; size * size];
+    for (index, &state) in current_cells.iter().enumerate() {
+        current[grid_index(row_offset + index / cols, col_offset + index % cols, size)] = state.min(1);
+    }
+
+    let mut census = Census::default();
+    let in_margin = |index: usize| {
+        let (row, col) = (index / size, index % size);
+        row.min(col) < ESCAPE_MARGIN || row.max(col) >= size - ESCAPE_MARGIN
+    };
+    let mut populations = Vec::new();
+    for _ in 0..max_generations {
+        grid_update_rule(&mut current, &mut next, size, size, Topology::Plane, &options.rule);
+        if (0..size * size).any(|index| current[index] >= 1 && in_margin(index)) {
+            // Retire les objets qui s'échappent, après avoir recensé les vaisseaux
+            for component in grid_components(&current, size, size, Topology::Plane, options.merge_distance) {
+                let escaping = component
+                    .iter()
+                    .any(|&(row, col)| in_margin(grid_index(row as usize, col as usize, size)));
+                if escaping {
+                    let (kind, shape) = classify_object(&component, &options.rule, options.max_period);
+                    if matches!(kind, ObjectKind::Spaceship { .. }) {
+                        census.record(kind, shape, 1);
+                    }
+                    for (row, col) in component {
+                        current[grid_index(row as usize, col as usize, size)] = 0;
+                    }
+                }
+            }
+        }
+        populations.push(current.iter().filter(|&&cell| cell >= 1).count());
+        if is_stable(&populations) {
+            break;
+        }
+    }
+    census.merge(&grid_census(&current, size, size, Topology::Plane, options));
+    census
+}
+
+/// Ligne du décompte d'une recherche : un objet, son nombre d'occurrences et des graines qui le produisent.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TallyEntry {
+    /// Nombre total d'occurrences de l'objet.
+    pub count: u64,
+    /// Plus petites graines dont la soupe a produit l'objet, au plus `SAMPLE_SEEDS`.
+    pub seeds: Vec<u64>,
+}
+
+/// Décompte cumulé d'une recherche de soupes, qui sert aussi de point de reprise.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchTally {
+    /// Règle de la recherche.
+    pub rule: LifeRule,
+    /// Distance de fusion des composantes utilisée par le recensement.
+    pub merge_distance: usize,
+    /// Période maximale recherchée par le recensement.
+    pub max_period: usize,
+    /// Symétrie des soupes.
+    pub symmetry: SoupSymmetry,
+    /// Nombre de soupes recensées.
+    pub soups: u64,
+    /// Graine de la prochaine soupe.
+    pub next_seed: u64,
+    /// Objets recensés, indexés par apgcode.
+    pub objects: BTreeMap<String, TallyEntry>,
+}
+
+impl SearchTally {
+    /// Crée un décompte vide qui commence à la graine spécifiée, pour les paramètres de
+    /// recensement donnés.
+    pub fn new(census: &CensusOptions, symmetry: SoupSymmetry, first_seed: u64) -> Self {
+        SearchTally {
+            rule: census.rule.clone(),
+            merge_distance: census.merge_distance,
+            max_period: census.max_period,
+            symmetry,
+            soups: 0,
+            next_seed: first_seed,
+            objects: BTreeMap::new(),
+        }
+    }
+
+    /// Ajoute au décompte le recensement de la soupe d'une graine.
+    pub fn record(&mut self, seed: u64, census: &Census) {
+        for entry in &census.entries {
+            let object = self.objects.entry(entry.apgcode.clone()).or_default();
+            object.count += entry.count as u64;
+            if let Err(position) = object.seeds.binary_search(&seed) {
+                object.seeds.insert(position, seed);
+                object.seeds.truncate(SAMPLE_SEEDS);
+            }
+        }
+        self.soups += 1;
+        self.next_seed = self.next_seed.max(seed + 1);
+    }
+
+    /// Renvoie les `limit` objets les plus rares, du moins fréquent au plus fréquent.
+    pub fn rarest(&self, limit: usize) -> Vec<(&str, &TallyEntry)> {
+        let mut objects: Vec<(&str, &TallyEntry)> =
+            self.objects.iter().map(|(code, entry)| (code.as_str(), entry)).collect();
+        objects.sort_by_key(|&(code, entry)| (entry.count, code.len(), code));
+        objects.truncate(limit);
+        objects
+    }
+
+    /// Enregistre le décompte dans un fichier texte, en remplaçant son contenu de façon atomique.
+    ///
+    /// # Erreurs
+    ///
+    /// Renvoie une erreur si le fichier ne peut pas être écrit.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let temporary = path.with_extension("tmp");
+        fs::write(&temporary, self.to_string())?;
+        fs::rename(&temporary, path)
+    }
+
+    /// Charge un décompte enregistré par `save`.
+    ///
+    /// # Erreurs
+    ///
+    /// Renvoie une erreur si le fichier ne peut pas être lu, ou une erreur `InvalidData`
+    /// contenant une `SearchError` s'il est mal formé.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        fs::read_to_string(path)?
+            .parse()
+            .map_err(|error: SearchError| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+
+    /// Indique si le décompte a été fait avec les paramètres de recensement spécifiés.
+    pub fn matches(&self, census: &CensusOptions) -> bool {
+        self.rule == census.rule && self.merge_distance == census.merge_distance && self.max_period == census.max_period
+    }
+}
+
+impl fmt::Display for SearchTally {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# lifers soup search")?;
+        writeln!(f, "rule {}", self.rule)?;
+        writeln!(f, "merge_distance {}", self.merge_distance)?;
+        writeln!(f, "max_period {}", self.max_period)?;
+        writeln!(f, "symmetry {}", self.symmetry)?;
+        writeln!(f, "soups {}", self.soups)?;
+        writeln!(f, "next_seed {}", self.next_seed)?;
+        let mut objects: Vec<(&String, &TallyEntry)> = self.objects.iter().collect();
+        objects.sort_by(|a, b| b.1.count.cmp(&a.1.count).then(a.0.cmp(b.0)));
+        for (code, entry) in objects {
+            write!(f, "{} {}", code, entry.count)?;
+            for seed in &entry.seeds {
+                write!(f, " {}", seed)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SearchTally {
+    type Err = SearchError;
+
+    /// Lit un décompte écrit par `Display`. Les paramètres de recensement absents prennent leur
+    /// valeur par défaut.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut tally = SearchTally::new(&CensusOptions::default(), SoupSymmetry::C1, 0);
+        let number = |value: &str| {
+            value
+                .parse::<u64>()
+                .map_err(|_| SearchError::new(format!("invalid number {:?}", value)))
+        };
+        for line in text.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')) {
+            let mut words = line.split_whitespace();
+            let key = words.next().unwrap_or_default();
+            let value = words.next().ok_or_else(|| SearchError::new(format!("missing value in {:?}", line)))?;
+            match key {
+                "rule" => {
+                    tally.rule = value.parse().map_err(|error: RuleParseError| SearchError::new(error.to_string()))?
+                }
+                "merge_distance" => tally.merge_distance = number(value)? as usize,
+                "max_period" => tally.max_period = number(value)? as usize,
+                "symmetry" => tally.symmetry = value.parse()?,
+                "soups" => tally.soups = number(value)?,
+                "next_seed" => tally.next_seed = number(value)?,
+                code => {
+                    let entry = TallyEntry {
+                        count: number(value)?,
+                        seeds: words.map(number).collect::<Result<_, _>>()?,
+                    };
+                    tally.objects.insert(code.to_string(), entry);
+                }
+            }
+        }
+        Ok(tally)
+    }
+}
+
+/// Recherche de soupes : génère des soupes, les recense en parallèle et cumule les résultats.
+///
+/// # Exemple
+///
+/// ```
+/// use crate::lifers::{SearchOptions, SoupSearch};
+///
+/// let options = SearchOptions { board_size: 40, max_generations: 200, threads: 2, ..SearchOptions::default() };
+/// let mut search = SoupSearch::new(options, 0);
+/// search.run(4);
+/// assert_eq!(search.tally().soups, 4);
+/// assert_eq!(search.tally().next_seed, 4);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SoupSearch {
+    options: SearchOptions,
+    tally: SearchTally,
+}
+
+impl SoupSearch {
+    /// Crée une recherche dont la première soupe a la graine `first_seed`.
+    pub fn new(options: SearchOptions, first_seed: u64) -> Self {
+        let tally = SearchTally::new(&options.census, options.symmetry, first_seed);
+        SoupSearch { options, tally }
+    }
+
+    /// Reprend une recherche à partir d'un décompte enregistré.
+    ///
+    /// # Erreurs
+    ///
+    /// Renvoie une erreur si le fichier ne peut pas être lu, s'il est mal formé, ou si sa symétrie
+    /// ou ses paramètres de recensement (règle, distance de fusion, période maximale) diffèrent de
+    /// ceux des paramètres.
+    pub fn resume(options: SearchOptions, path: impl AsRef<Path>) -> io::Result<Self> {
+        let tally = SearchTally::load(path)?;
+        if !tally.matches(&options.census) || tally.symmetry != options.symmetry {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "checkpoint was made with {} {} (merge distance {}, max period {})",
+                    tally.rule, tally.symmetry, tally.merge_distance, tally.max_period
+                ),
+            ));
+        }
+        Ok(SoupSearch { options, tally })
+    }
+
+    /// Renvoie le décompte cumulé.
+    pub fn tally(&self) -> &SearchTally {
+        &self.tally
+    }
+
+    /// Recense les `soups` soupes suivantes, réparties entre les fils d'exécution.
+    ///
+    /// Le résultat ne dépend pas du nombre de fils : les recensements sont ajoutés au décompte
+    /// dans l'ordre des graines.
+    pub fn run(&mut self, soups: u64) {
+        let first = self.tally.next_seed;
+        let threads = self.options.threads.clamp(1, soups.max(1) as usize) as u64;
+        let options = &self.options;
+        let results: Vec<(u64, Census)> = thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads)
+                .map(|worker| {
+                    scope.spawn(move || {
+                        (first + worker..first + soups)
+                            .step_by(threads as usize)
+                            .map(|seed| (seed, run_soup(seed, options)))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            let mut results: Vec<(u64, Census)> = workers
+                .into_iter()
+                .flat_map(|worker| worker.join().expect("search worker panicked"))
+                .collect();
+            results.sort_by_key(|&(seed, _)| seed);
+            results
+        });
+        for (seed, census) in &results {
+            self.tally.record(*seed, census);
+        }
+    }
+
+    /// Recense `soups` soupes en enregistrant le décompte dans `path` toutes les `interval` soupes.
+    ///
+    /// # Erreurs
+    ///
+    /// Renvoie une erreur si le fichier ne peut pas être écrit.
+    pub fn run_with_checkpoints(&mut self, soups: u64, interval: u64, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut remaining = soups;
+        while remaining > 0 {
+            let batch = remaining.min(interval.max(1));
+            self.run(batch);
+            self.tally.save(&path)?;
+            remaining -= batch;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(threads: usize) -> SearchOptions {
+        SearchOptions {
+            board_size: 40,
+            max_generations: 300,
+            threads,
+            ..SearchOptions::default()
+        }
+    }
+
+    #[test]
+    fn test_soup_symmetries() {
+        for symmetry in [SoupSymmetry::C2, SoupSymmetry::C4, SoupSymmetry::D8] {
+            let cells = soup(5, symmetry);
+            for row in 0..SOUP_SIZE {
+                for col in 0..SOUP_SIZE {
+                    let state = cells[grid_index(row, col, SOUP_SIZE)];
+                    for (i, j) in symmetry.orbit(row, col) {
+                        assert_eq!(cells[grid_index(i, j, SOUP_SIZE)], state);
+                    }
+                }
+            }
+            assert_eq!(symmetry.to_string().parse::<SoupSymmetry>().unwrap(), symmetry);
+        }
+        assert_ne!(soup(1, SoupSymmetry::C1), soup(2, SoupSymmetry::C1));
+        assert_eq!(SoupSymmetry::D2.to_string(), "D2_+2");
+        assert!("C2_1".parse::<SoupSymmetry>().is_err());
+    }
+
+    #[test]
+    fn test_tally_round_trip_and_errors() {
+        let census = CensusOptions { merge_distance: 3, max_period: 12, ..CensusOptions::default() };
+        let mut tally = SearchTally::new(&census, SoupSymmetry::C4, 7);
+        tally.objects.insert("xs4_33".to_string(), TallyEntry { count: 5, seeds: vec![7, 9] });
+        let parsed: SearchTally = tally.to_string().parse().unwrap();
+        assert_eq!(parsed, tally);
+        assert!(parsed.matches(&census));
+        assert!(!parsed.matches(&CensusOptions::default()));
+
+        // Un décompte sans paramètres de recensement reprend les valeurs par défaut
+        let old: SearchTally = "rule B3/S23\nsymmetry C1\nsoups 0\nnext_seed 0\n".parse().unwrap();
+        assert!(old.matches(&CensusOptions::default()));
+
+        let error = "soups many".parse::<SearchTally>().unwrap_err();
+        assert_eq!(error.to_string(), "invalid soup search: invalid number \"many\"");
+        assert!("rule B9/S23".parse::<SearchTally>().unwrap_err().to_string().starts_with("invalid soup search: "));
+        assert!("symmetry C3".parse::<SearchTally>().is_err());
+    }
+
+    #[test]
+    fn test_results_do_not_depend_on_threads() {
+        let mut single = SoupSearch::new(options(1), 10);
+        let mut parallel = SoupSearch::new(options(3), 10);
+        single.run(6);
+        parallel.run(6);
+        assert_eq!(single.tally(), parallel.tally());
+        assert!(single.tally().objects.contains_key("xs4_33"));
+    }
+
+    #[test]
+    fn test_resume_from_checkpoint() {
+        let path = std::env::temp_dir().join(format!("lifers-search-{}.txt", std::process::id()));
+        let mut uninterrupted = SoupSearch::new(options(2), 0);
+        uninterrupted.run(6);
+
+        let mut first = SoupSearch::new(options(2), 0);
+        first.run_with_checkpoints(4, 2, &path).unwrap();
+        let mut resumed = SoupSearch::resume(options(2), &path).unwrap();
+        resumed.run(2);
+        assert_eq!(resumed.tally(), uninterrupted.tally());
+
+        let other = SearchOptions { symmetry: SoupSymmetry::D8, ..options(2) };
+        assert!(SoupSearch::resume(other, &path).is_err());
+        let mut other = options(2);
+        other.census.merge_distance = 2;
+        assert!(SoupSearch::resume(other, &path).is_err());
+        let mut other = options(2);
+        other.census.max_period = 10;
+        assert!(SoupSearch::resume(other, &path).is_err());
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,255 @@
+use crate::predecessor::{CellSolver, Term};
+use crate::{grid_index, grid_update_rule, rle_encode, LifeRule, Topology};
+
+/// Paramètres d'une recherche d'oscillateurs ou de vaisseaux.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ShipSearchOptions {
+    /// Période recherchée.
+    pub period: usize,
+    /// Déplacement horizontal après une période, nul pour un oscillateur.
+    pub dx: isize,
+    /// Déplacement vertical après une période, nul pour un oscillateur.
+    pub dy: isize,
+    /// Largeur de la boîte qui contient toutes les phases du motif.
+    pub width: usize,
+    /// Hauteur de la boîte qui contient toutes les phases du motif.
+    pub height: usize,
+    /// Nombre de motifs à trouver avant d'arrêter la recherche.
+    pub max_results: usize,
+    /// Nombre maximal d'affectations essayées avant d'abandonner, sans limite si `None`.
+    pub node_limit: Option<u64>,
+}
+
+impl Default for ShipSearchOptions {
+    fn default() -> Self {
+        ShipSearchOptions {
+            period: 4,
+            dx: 1,
+            dy: 1,
+            width: 5,
+            height: 5,
+            max_results: 1,
+            node_limit: None,
+        }
+    }
+}
+
+/// Motif trouvé par une recherche d'oscillateurs ou de vaisseaux, rogné à ses cellules vivantes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FoundPattern {
+    /// Vecteur contenant l'état de chaque cellule du motif.
+    pub cells: Vec<u8>,
+    /// Nombre de lignes du motif.
+    pub rows: usize,
+    /// Nombre de colonnes du motif.
+    pub cols: usize,
+    /// Période du motif.
+    pub period: usize,
+    /// Déplacement horizontal après une période.
+    pub dx: isize,
+    /// Déplacement vertical après une période.
+    pub dy: isize,
+}
+
+impl FoundPattern {
+    /// Renvoie le motif au format RLE, avec la règle dans l'en-tête.
+    pub fn rle(&self, rule: &LifeRule) -> String {
+        rle_encode(&self.cells, self.rows, self.cols, rule)
+    }
+}
+
+/// Résultat d'une recherche d'oscillateurs ou de vaisseaux.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShipSearchResult {
+    /// Motifs trouvés, au plus `max_results`.
+    pub patterns: Vec<FoundPattern>,
+    /// Indique si la recherche a examiné toute la boîte ; `false` si elle a été interrompue par
+    /// `max_results` ou par la limite d'affectations.
+    pub complete: bool,
+}
+
+/// Cherche des oscillateurs ou des vaisseaux de période et de déplacement donnés.
+///
+/// Les inconnues sont les cellules de chacune des `period` phases dans une boîte de `width` ×
+/// `height` cellules, entourée de cellules mortes. Chaque cellule de la boîte et de son pourtour
+/// doit passer d'une phase à la suivante selon la règle, la phase qui suit la dernière étant la
+/// première déplacée de (`dx`, `dy`). Les cellules sont affectées ligne par ligne, toutes phases
+/// confondues, et chaque motif partiel est abandonné dès qu'une transition devient impossible.
+///
+/// Les solutions vides et celles dont la période est un diviseur strict de `period` (les
+/// natures mortes lors d'une recherche d'oscillateurs, par exemple) sont écartées ; un même objet
+/// peut être trouvé à plusieurs positions de la boîte.
+///
+/// # Arguments
+///
+/// * `rule` - La règle sous laquelle chercher.
+/// * `options` - La période, le déplacement, la taille de la boîte et les limites de la recherche.
+///
+/// # Exemple
+///
+/// ```
+/// use crate::lifers::{ship_search, LifeRule, ShipSearchOptions};
+///
+/// // Un oscillateur de période 2 dans une boîte de 3x3 : le clignotant
+/// let options = ShipSearchOptions { period: 2, dx: 0, dy: 0, width: 3, height: 3, ..ShipSearchOptions::default() };
+/// let result = ship_search(&LifeRule::conway(), &options);
+/// assert_eq!(result.patterns[0].rle(&LifeRule::conway()), "x = 3, y = 1, rule = B3/S23\n3o!\n");
+/// ```
+pub fn ship_search(rule: &LifeRule, options: &ShipSearchOptions) -> ShipSearchResult {
+    let ShipSearchOptions {
+        period,
+        dx,
+        dy,
+        width,
+        height,
+        ..
+    } = *options;
+    let period = period.max(1);
+    let radius = rule.neighborhood().radius() as isize;
+    let inside = |row: isize, col: isize| (0..height as isize).contains(&row) && (0..width as isize).contains(&col);
+    // Cellule (ligne, colonne) de la phase `phase`, la phase `period` étant la première déplacée
+    let term = |phase: usize, row: isize, col: isize| {
+        let (phase, row, col) = if phase == period { (0, row - dy, col - dx) } else { (phase, row, col) };
+        if inside(row, col) {
+            Term::Var((phase * height + row as usize) * width + col as usize)
+        } else {
+            Term::Const(0)
+        }
+    };
+
+    let mut solver = CellSolver::new(rule, period * width * height, options.node_limit);
+    for phase in 0..period {
+        for row in -radius..height as isize + radius {
+            for col in -radius..width as isize + radius {
+                let neighbors: Vec<Term> = rule
+                    .neighborhood()
+                    .offsets(row.rem_euclid(2) as usize)
+                    .iter()
+                    .map(|&(di, dj)| term(phase, row + di, col + dj))
+                    .collect();
+                let (center, target) = (term(phase, row, col), term(phase + 1, row, col));
+                let constant = |term: &Term| matches!(term, Term::Const(_));
+                if !(constant(&center) && constant(&target) && neighbors.iter().all(constant)) {
+                    solver.add_constraint(center, &neighbors, target);
+                }
+            }
+        }
+    }
+    // Affectation ligne par ligne : toutes les phases d'une cellule avant la cellule suivante
+    solver.set_order(
+        (0..height * width)
+            .flat_map(|cell| (0..period).map(move |phase| phase * height * width + cell))
+            .collect(),
+    );
+
+    let mut patterns = Vec::new();
+    let outcome = solver.solve(&mut |cells| {
+        let first = &cells[..width * height];
+        if first.iter().any(|&cell| cell >= 1) && has_exact_period(first, width, height, rule, options) {
+            patterns.push(crop(first, width, height, options));
+        }
+        patterns.len() >= options.max_results
+    });
+    ShipSearchResult {
+        patterns,
+        complete: outcome == Some(false),
+    }
+}
+
+/// Indique si aucune période strictement plus petite, avec un déplacement proportionnel, ne
+/// ramène la première phase sur elle-même.
+fn has_exact_period(first: &[u8], width: usize, height: usize, rule: &LifeRule, options: &ShipSearchOptions) -> bool {
+    let period = options.period as isize;
+    // La boîte est agrandie pour que l'évolution y reste identique à celle du plan infini
+    let margin = options.period + 1;
+    let (rows, cols) = (height + 2 * margin, width + 2 * margin);
+    let mut current = vec![0u8; rows * cols];
+    let mut next = vec![0u8; rows * cols];
+    for row in 0..height {
+        for col in 0..width {
+            current[grid_index(row + margin, col + margin, cols)] = first[grid_index(row, col, width)];
+        }
+    }
+    let initial = current.clone();
+    for generation in 1..period {
+        grid_update_rule(&mut current, &mut next, rows, cols, Topology::Plane, rule);
+        let divides = period % generation == 0
+            && (options.dx * generation) % period == 0
+            && (options.dy * generation) % period == 0;
+        if divides {
+            let (shift_x, shift_y) = (options.dx * generation / period, options.dy * generation / period);
+            let shifted = (0..rows * cols).all(|index| {
+                let (row, col) = ((index / cols) as isize - shift_y, (index % cols) as isize - shift_x);
+                let before = if (0..rows as isize).contains(&row) && (0..cols as isize).contains(&col) {
+                    initial[grid_index(row as usize, col as usize, cols)]
+                } else {
+                    0
+                };
+                before == current[index]
+            });
+            if shifted {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// Rogne la première phase d'une solution à ses cellules vivantes.
+fn crop(first: &[u8], width: usize, height: usize, options: &ShipSearchOptions) -> FoundPattern {
+    let live: Vec<(usize, usize)> = (0..width * height)
+        .filter(|&index| first[index] >= 1)
+        .map(|index| (index / width, index % width))
+        .collect();
+    let (top, bottom) = (live.iter().map(|p| p.0).min().unwrap_or(0), live.iter().map(|p| p.0).max().unwrap_or(0));
+    let (left, right) = (live.iter().map(|p| p.1).min().unwrap_or(0), live.iter().map(|p| p.1).max().unwrap_or(0));
+    let (rows, cols) = (bottom - top + 1, right - left + 1);
+    let mut cells = vec![0; rows * cols];
+    for (row, col) in live {
+        cells[grid_index(row - top, col - left, cols)] = 1;
+    }
+    FoundPattern {
+        cells,
+        rows,
+        cols,
+        period: options.period,
+        dx: options.dx,
+        dy: options.dy,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{apgcode, classify_object};
+
+    fn identify(pattern: &FoundPattern) -> String {
+        let cells: Vec<(isize, isize)> = (0..pattern.rows * pattern.cols)
+            .filter(|&index| pattern.cells[index] >= 1)
+            .map(|index| ((index / pattern.cols) as isize, (index % pattern.cols) as isize))
+            .collect();
+        let (kind, shape) = classify_object(&cells, &LifeRule::conway(), 30);
+        apgcode(kind, &shape)
+    }
+
+    #[test]
+    fn test_finds_glider() {
+        let result = ship_search(&LifeRule::conway(), &ShipSearchOptions::default());
+        assert_eq!(result.patterns.len(), 1);
+        assert_eq!(identify(&result.patterns[0]), "xq4_153");
+    }
+
+    #[test]
+    fn test_oscillator_search_skips_still_lifes_and_exhausts_small_boxes() {
+        let conway = LifeRule::conway();
+        let options = ShipSearchOptions { period: 2, dx: 0, dy: 0, width: 4, height: 4, max_results: 1, node_limit: None };
+        let result = ship_search(&conway, &options);
+        assert_eq!(identify(&result.patterns[0]).split('_').next(), Some("xp2"));
+
+        // Aucun vaisseau orthogonal de période 2 ne tient dans une boîte de 3x3
+        let options = ShipSearchOptions { period: 2, dx: 1, dy: 0, width: 3, height: 3, ..ShipSearchOptions::default() };
+        let result = ship_search(&conway, &options);
+        assert!(result.patterns.is_empty());
+        assert!(result.complete);
+    }
+}
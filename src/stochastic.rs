@@ -0,0 +1,116 @@
+use rand::Rng;
+
+/// Paramètres d'une mise à jour stochastique.
+///
+/// Après l'application de la règle, chaque naissance n'a lieu qu'avec la probabilité `birth` et
+/// chaque survie qu'avec la probabilité `survival` ; puis chaque cellule est inversée avec la
+/// probabilité `noise`. Les valeurs par défaut donnent une mise à jour déterministe.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stochastic {
+    /// Probabilité qu'une naissance prévue par la règle ait lieu.
+    pub birth: f64,
+    /// Probabilité qu'une survie prévue par la règle ait lieu.
+    pub survival: f64,
+    /// Probabilité qu'une cellule soit inversée à chaque génération.
+    pub noise: f64,
+}
+
+impl Default for Stochastic {
+    fn default() -> Self {
+        Stochastic {
+            birth: 1.0,
+            survival: 1.0,
+            noise: 0.0,
+        }
+    }
+}
+
+impl Stochastic {
+    /// Indique si les paramètres donnent une mise à jour déterministe.
+    pub fn is_deterministic(&self) -> bool {
+        self.birth >= 1.0 && self.survival >= 1.0 && self.noise <= 0.0
+    }
+}
+
+/// Applique les probabilités de naissance et de survie puis le bruit à une génération calculée.
+///
+/// Une naissance refusée laisse la cellule morte et une survie refusée la fait mourir ; une
+/// cellule inversée par le bruit passe de vivante à morte ou de morte à l'état 1.
+///
+/// # Arguments
+///
+/// * `previous_cells` - Vecteur contenant l'état de chaque cellule avant la mise à jour.
+/// * `current_cells` - Vecteur contenant l'état de chaque cellule calculé par la règle, modifié sur place.
+/// * `stochastic` - Les probabilités à appliquer.
+/// * `rng` - Le générateur aléatoire, à initialiser avec une graine pour des résultats reproductibles.
+///
+/// # Exemple
+///
+/// ```
+/// use crate::lifers::{grid_apply_stochastic, Stochastic};
+/// use rand::{rngs::StdRng, SeedableRng};
+///
+/// let previous_cells = vec![0, 1, 1, 0];
+/// let mut current_cells = vec![1, 1, 0, 0];
+/// let stochastic = Stochastic { birth: 0.0, survival: 1.0, noise: 0.0 };
+///
+/// // Aucune naissance n'est acceptée
+/// grid_apply_stochastic(&previous_cells, &mut current_cells, &stochastic, &mut StdRng::seed_from_u64(1));
+/// assert_eq!(current_cells, vec![0, 1, 0, 0]);
+/// ```
+pub fn grid_apply_stochastic(
+    previous_cells: &[u8],
+    current_cells: &mut [u8],
+    stochastic: &Stochastic,
+    rng: &mut impl Rng,
+) {
+    for (previous, current) in previous_cells.iter().zip(current_cells.iter_mut()) {
+        if *current >= 1 {
+            let probability = if *previous >= 1 { stochastic.survival } else { stochastic.birth };
+            if probability < 1.0 && !rng.gen_bool(probability.max(0.0)) {
+                *current = 0;
+            }
+        }
+        if stochastic.noise > 0.0 && rng.gen_bool(stochastic.noise.min(1.0)) {
+            *current = if *current >= 1 { 0 } else { 1 };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConwaysGrid, Grid};
+
+    #[test]
+    fn test_same_seed_gives_same_history() {
+        let run = |seed| {
+            let mut grid = ConwaysGrid::new(16, 16, true);
+            grid.set_seed(seed);
+            grid.set_stochastic(Stochastic { birth: 0.5, survival: 0.9, noise: 0.01 });
+            for row in 6..9 {
+                for col in 5..10 {
+                    grid.set_cell_state(row, col, 1);
+                }
+            }
+            for _ in 0..10 {
+                grid.update();
+            }
+            grid.current_cells().clone()
+        };
+        assert_eq!(run(7), run(7));
+        assert_ne!(run(7), run(8));
+    }
+
+    #[test]
+    fn test_noise_rate_flips_expected_fraction() {
+        let mut grid = ConwaysGrid::new(100, 100, true);
+        grid.set_seed(3);
+        grid.set_noise(0.1);
+        grid.update();
+        // Une grille vide reste vide, sauf les cellules inversées par le bruit
+        let alive = grid.current_cells().iter().filter(|&&cell| cell >= 1).count();
+        assert!((800..1200).contains(&alive), "{}", alive);
+        assert_eq!(grid.noise(), 0.1);
+    }
+}
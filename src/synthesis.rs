@@ -0,0 +1,250 @@
+use std::ops::RangeInclusive;
+
+use crate::{grid_index, grid_stabilised_census, grid_update_rule, Census, CensusOptions, LifeRule, Shape, Topology};
+
+/// Vaisseau lancé dans une expérience de collision, avec les voies et les délais à essayer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IncomingShip {
+    /// Cellules vivantes du vaisseau, relatives à son coin supérieur gauche.
+    pub shape: Shape,
+    /// Période du vaisseau.
+    pub period: usize,
+    /// Déplacement horizontal après une période, positif vers la droite.
+    pub dx: isize,
+    /// Déplacement vertical après une période, positif vers le bas.
+    pub dy: isize,
+    /// Position `(ligne, colonne)` du coin supérieur gauche du vaisseau, sans décalage ni délai.
+    pub position: (isize, isize),
+    /// Décalages de voie à essayer : une ligne par unité pour un vaisseau qui se déplace
+    /// horizontalement, une colonne par unité sinon.
+    pub lanes: RangeInclusive<isize>,
+    /// Délais d'arrivée à essayer, en générations.
+    pub timings: RangeInclusive<usize>,
+}
+
+impl IncomingShip {
+    /// Crée un vaisseau placé en `position`, sans décalage de voie ni délai.
+    pub fn new(shape: Shape, period: usize, dx: isize, dy: isize, position: (isize, isize)) -> Self {
+        IncomingShip {
+            shape,
+            period,
+            dx,
+            dy,
+            position,
+            lanes: 0..=0,
+            timings: 0..=0,
+        }
+    }
+
+    /// Crée un planeur qui se déplace dans la direction diagonale (`dx`, `dy`), chacun valant 1 ou -1.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::IncomingShip;
+    ///
+    /// // Planeur vers le nord-ouest
+    /// let glider = IncomingShip::glider(-1, -1, (10, 10));
+    /// assert_eq!(glider.shape, vec![(0, 0), (0, 1), (0, 2), (1, 0), (2, 1)]);
+    /// ```
+    pub fn glider(dx: isize, dy: isize, position: (isize, isize)) -> Self {
+        let mut shape: Shape = [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]
+            .iter()
+            .map(|&(row, col)| (if dy < 0 { 2 - row } else { row }, if dx < 0 { 2 - col } else { col }))
+            .collect();
+        shape.sort_unstable();
+        IncomingShip::new(shape, 4, dx.signum(), dy.signum(), position)
+    }
+
+    /// Modifie les décalages de voie à essayer.
+    pub fn with_lanes(mut self, lanes: RangeInclusive<isize>) -> Self {
+        self.lanes = lanes;
+        self
+    }
+
+    /// Modifie les délais d'arrivée à essayer.
+    pub fn with_timings(mut self, timings: RangeInclusive<usize>) -> Self {
+        self.timings = timings;
+        self
+    }
+
+    /// Renvoie les cellules du vaisseau décalé de `lane` voies et retardé de `timing` générations.
+    ///
+    /// Retarder un vaisseau revient à le placer dans l'état qu'il avait `timing` générations plus
+    /// tôt : on le fait avancer jusqu'à la période suivante puis on le recule d'autant de périodes.
+    fn cells(&self, lane: isize, timing: usize, rule: &LifeRule) -> Vec<(isize, isize)> {
+        let period = self.period.max(1);
+        let periods = timing.div_ceil(period);
+        let (mut rows, mut cols) = (0, 0);
+        for &(row, col) in &self.shape {
+            rows = rows.max(row + 1);
+            cols = cols.max(col + 1);
+        }
+        let margin = periods * period + 1;
+        let (board_rows, board_cols) = (rows + 2 * margin, cols + 2 * margin);
+        let mut current = vec![0u8; board_rows * board_cols];
+        let mut next = vec![0u8; board_rows * board_cols];
+        for &(row, col) in &self.shape {
+            current[grid_index(row + margin, col + margin, board_cols)] = 1;
+        }
+        for _ in 0..periods * period - timing {
+            grid_update_rule(&mut current, &mut next, board_rows, board_cols, Topology::Plane, rule);
+        }
+
+        let (lane_row, lane_col) = if self.dy == 0 { (lane, 0) } else { (0, lane) };
+        let shift_row = self.position.0 + lane_row - periods as isize * self.dy - margin as isize;
+        let shift_col = self.position.1 + lane_col - periods as isize * self.dx - margin as isize;
+        (0..board_rows * board_cols)
+            .filter(|&index| current[index] >= 1)
+            .map(|index| ((index / board_cols) as isize + shift_row, (index % board_cols) as isize + shift_col))
+            .collect()
+    }
+}
+
+/// Paramètres d'une expérience de collision.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CollisionOptions {
+    /// Côté minimal de la grille sur laquelle chaque collision évolue.
+    pub board_size: usize,
+    /// Nombre maximal de générations avant le recensement d'une collision qui ne se stabilise pas.
+    pub max_generations: usize,
+    /// Paramètres du recensement, dont la règle.
+    pub census: CensusOptions,
+}
+
+impl Default for CollisionOptions {
+    fn default() -> Self {
+        CollisionOptions {
+            board_size: 64,
+            max_generations: 1000,
+            census: CensusOptions::default(),
+        }
+    }
+}
+
+/// Résultat d'une combinaison de voies et de délais.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CollisionResult {
+    /// Décalage de voie de chaque vaisseau.
+    pub lanes: Vec<isize>,
+    /// Délai de chaque vaisseau.
+    pub timings: Vec<usize>,
+    /// Recensement des objets obtenus après stabilisation, vaisseaux sortis compris.
+    pub census: Census,
+}
+
+/// Essaie toutes les combinaisons de voies et de délais d'un ensemble de vaisseaux.
+///
+/// Pour chaque combinaison, les vaisseaux sont placés sur une grille vierge, puis le tout évolue
+/// jusqu'à sa stabilisation et les objets obtenus sont recensés (voir `grid_stabilised_census`).
+/// Les résultats sont renvoyés dans l'ordre d'énumération, le dernier vaisseau variant le plus vite.
+///
+/// # Arguments
+///
+/// * `ships` - Les vaisseaux, avec leurs voies et leurs délais.
+/// * `options` - La taille de la grille, la durée maximale et les paramètres du recensement.
+///
+/// # Exemple
+///
+/// ```
+/// use crate::lifers::{run_collisions, CollisionOptions, IncomingShip};
+///
+/// // Deux planeurs face à face, sur la même diagonale
+/// let ships = [
+///     IncomingShip::glider(1, 1, (0, 0)),
+///     IncomingShip::glider(-1, -1, (12, 12)).with_timings(0..=3),
+/// ];
+/// let results = run_collisions(&ships, &CollisionOptions::default());
+/// assert_eq!(results.len(), 4);
+/// assert_eq!(results[2].timings, vec![0, 2]);
+/// ```
+pub fn run_collisions(ships: &[IncomingShip], options: &CollisionOptions) -> Vec<CollisionResult> {
+    // Chaque vaisseau peut prendre une de ses combinaisons (voie, délai)
+    let choices: Vec<Vec<(isize, usize)>> = ships
+        .iter()
+        .map(|ship| {
+            ship.lanes
+                .clone()
+                .flat_map(|lane| ship.timings.clone().map(move |timing| (lane, timing)))
+                .collect()
+        })
+        .collect();
+    let total: usize = choices.iter().map(Vec::len).product();
+
+    let mut results = Vec::with_capacity(total);
+    for mut number in 0..total {
+        let mut combination = vec![(0, 0); ships.len()];
+        for (slot, choice) in combination.iter_mut().zip(&choices).rev() {
+            *slot = choice[number % choice.len()];
+            number /= choice.len();
+        }
+
+        let cells: Vec<(isize, isize)> = ships
+            .iter()
+            .zip(&combination)
+            .flat_map(|(ship, &(lane, timing))| ship.cells(lane, timing, &options.census.rule))
+            .collect();
+        let top = cells.iter().map(|&(row, _)| row).min().unwrap_or(0);
+        let left = cells.iter().map(|&(_, col)| col).min().unwrap_or(0);
+        let rows = cells.iter().map(|&(row, _)| (row - top) as usize + 1).max().unwrap_or(1);
+        let cols = cells.iter().map(|&(_, col)| (col - left) as usize + 1).max().unwrap_or(1);
+        let mut grid = vec![0u8; rows * cols];
+        for (row, col) in cells {
+            grid[grid_index((row - top) as usize, (col - left) as usize, cols)] = 1;
+        }
+
+        results.push(CollisionResult {
+            lanes: combination.iter().map(|&(lane, _)| lane).collect(),
+            timings: combination.iter().map(|&(_, timing)| timing).collect(),
+            census: grid_stabilised_census(
+                &grid,
+                rows,
+                cols,
+                options.board_size,
+                options.max_generations,
+                &options.census,
+            ),
+        });
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ObjectKind;
+
+    #[test]
+    fn test_delay_places_ship_earlier_on_its_path() {
+        let rule = LifeRule::conway();
+        let glider = IncomingShip::glider(1, 1, (10, 10));
+        // Un retard d'une période recule le planeur d'une case en diagonale
+        let mut delayed = glider.cells(0, 4, &rule);
+        delayed.sort_unstable();
+        let expected: Vec<(isize, isize)> =
+            glider.shape.iter().map(|&(row, col)| (row as isize + 9, col as isize + 9)).collect();
+        assert_eq!(delayed, expected);
+        // Un retard de deux générations donne une autre phase du planeur
+        assert_eq!(glider.cells(0, 2, &rule).len(), 5);
+        assert_ne!(glider.cells(0, 2, &rule), glider.cells(0, 0, &rule));
+    }
+
+    #[test]
+    fn test_two_glider_collisions() {
+        let ships = [
+            IncomingShip::glider(1, 1, (0, 0)),
+            IncomingShip::glider(-1, -1, (12, 12)).with_lanes(-2..=2).with_timings(0..=1),
+        ];
+        let options = CollisionOptions { board_size: 48, max_generations: 400, ..CollisionOptions::default() };
+        let results = run_collisions(&ships, &options);
+        assert_eq!(results.len(), 10);
+        assert_eq!((results[9].lanes.clone(), results[9].timings.clone()), (vec![0, 2], vec![0, 1]));
+
+        // Une collision de front sur la même voie détruit les deux planeurs ; des voies éloignées
+        // les laissent passer
+        let gliders =
+            |result: &CollisionResult| result.census.count(|kind| matches!(kind, ObjectKind::Spaceship { .. }));
+        assert!(results.iter().any(|result| gliders(result) == 0));
+        assert!(results.iter().any(|result| result.census.total() > 0));
+    }
+}
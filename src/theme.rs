@@ -0,0 +1,311 @@
+use std::error::Error;
+use std::fmt;
+use std::path::Path;
+
+use ggez::graphics::Color;
+
+use crate::Grid;
+
+/// Erreur renvoyée lorsqu'un fichier de thèmes ne peut pas être lu.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ThemeError {
+    message: String,
+}
+
+impl ThemeError {
+    /// Crée une nouvelle erreur avec le message spécifié.
+    pub fn new(message: impl Into<String>) -> Self {
+        ThemeError {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid theme: {}", self.message)
+    }
+}
+
+impl Error for ThemeError {}
+
+/// Jeu de couleurs de l'interface graphique.
+///
+/// La palette donne la couleur de chaque état de cellule : l'état 0 pour les cellules mortes,
+/// l'état 1 pour les cellules vivantes, puis les états suivants des grilles à plusieurs états. Une
+/// couleur `None` est transparente : la cellule laisse voir le fond.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Theme {
+    /// Nom du thème.
+    pub name: String,
+    /// Couleur de fond de la fenêtre.
+    pub background: Color,
+    /// Couleur de chaque état de cellule.
+    pub palette: Vec<Option<Color>>,
+    /// Couleur des lignes de séparation entre les cellules.
+    pub gridlines: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::classic()
+    }
+}
+
+impl Theme {
+    /// Thème classique : cellules noires sur fond gris clair.
+    pub fn classic() -> Self {
+        Theme {
+            name: String::from("classic"),
+            background: Color::from_rgb(204, 204, 204),
+            palette: vec![
+                Some(Color::from_rgb(204, 204, 204)),
+                Some(Color::from_rgb(0, 0, 0)),
+                Some(Color::from_rgb(220, 40, 40)),
+                Some(Color::from_rgb(40, 160, 60)),
+                Some(Color::from_rgb(230, 170, 0)),
+            ],
+            gridlines: Color::from_rgba(128, 128, 128, 96),
+        }
+    }
+
+    /// Thème sombre : cellules claires sur fond presque noir.
+    pub fn dark() -> Self {
+        Theme {
+            name: String::from("dark"),
+            background: Color::from_rgb(12, 12, 16),
+            palette: vec![
+                Some(Color::from_rgb(24, 24, 32)),
+                Some(Color::from_rgb(120, 220, 160)),
+                Some(Color::from_rgb(240, 110, 110)),
+                Some(Color::from_rgb(110, 170, 250)),
+                Some(Color::from_rgb(240, 200, 90)),
+            ],
+            gridlines: Color::from_rgba(80, 80, 96, 128),
+        }
+    }
+
+    /// Thème à fort contraste : cellules blanches sur fond noir, couleurs saturées.
+    pub fn high_contrast() -> Self {
+        Theme {
+            name: String::from("high-contrast"),
+            background: Color::BLACK,
+            palette: vec![
+                Some(Color::BLACK),
+                Some(Color::WHITE),
+                Some(Color::from_rgb(255, 255, 0)),
+                Some(Color::from_rgb(0, 255, 255)),
+                Some(Color::from_rgb(255, 0, 255)),
+            ],
+            gridlines: Color::from_rgba(255, 255, 255, 128),
+        }
+    }
+
+    /// Thème adapté aux daltoniens, tiré de la palette d'Okabe et Ito.
+    pub fn colorblind() -> Self {
+        Theme {
+            name: String::from("colorblind"),
+            background: Color::BLACK,
+            palette: vec![
+                Some(Color::from_rgb(0, 0, 0)),
+                Some(Color::from_rgb(230, 159, 0)),
+                Some(Color::from_rgb(86, 180, 233)),
+                Some(Color::from_rgb(0, 158, 115)),
+                Some(Color::from_rgb(240, 228, 66)),
+                Some(Color::from_rgb(0, 114, 178)),
+                Some(Color::from_rgb(213, 94, 0)),
+                Some(Color::from_rgb(204, 121, 167)),
+            ],
+            gridlines: Color::from_rgba(128, 128, 128, 96),
+        }
+    }
+
+    /// Renvoie les thèmes prédéfinis, dans l'ordre de parcours de l'interface graphique.
+    pub fn presets() -> Vec<Theme> {
+        vec![Theme::classic(), Theme::dark(), Theme::high_contrast(), Theme::colorblind()]
+    }
+
+    /// Renvoie le thème prédéfini de nom `name`, s'il existe.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::Theme;
+    ///
+    /// assert_eq!(Theme::preset("dark"), Some(Theme::dark()));
+    /// assert_eq!(Theme::preset("neon"), None);
+    /// ```
+    pub fn preset(name: &str) -> Option<Theme> {
+        Theme::presets().into_iter().find(|theme| theme.name == name)
+    }
+
+    /// Renvoie la couleur d'un état ; les états au-delà de la palette prennent sa dernière couleur.
+    pub fn color(&self, state: u8) -> Option<Color> {
+        self.palette.get(state as usize).or(self.palette.last()).copied().flatten()
+    }
+
+    /// Applique la palette du thème à une grille (voir `Grid::set_state_colors`).
+    pub fn apply<G: Grid + ?Sized>(&self, grid: &mut G) {
+        grid.set_state_colors(&self.palette);
+    }
+
+    /// Lit les thèmes d'un fichier de configuration (voir `Theme::parse`).
+    ///
+    /// # Erreurs
+    ///
+    /// Renvoie une erreur si le fichier ne peut pas être lu ou si son contenu est invalide.
+    pub fn load(path: impl AsRef<Path>) -> Result<Vec<Theme>, ThemeError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|error| ThemeError::new(format!("cannot read {}: {}", path.display(), error)))?;
+        Theme::parse(&text)
+    }
+
+    /// Interprète le contenu d'un fichier de thèmes.
+    ///
+    /// Chaque thème commence par son nom entre crochets, suivi de lignes `clé = valeur` :
+    /// `base` (un thème prédéfini dont partir, `classic` par défaut), `background`, `gridlines` et
+    /// `palette`, la liste des couleurs des états séparées par des espaces. Les couleurs s'écrivent
+    /// `#rrggbb` ou `#rrggbbaa`, ou `none` pour une couleur transparente. Les commentaires commencent
+    /// par `#` en début de ligne.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::Theme;
+    ///
+    /// let text = "[sepia]\nbackground = #201810\npalette = #f0e0c0 #402010 none\n";
+    /// let themes = Theme::parse(text).unwrap();
+    /// assert_eq!(themes[0].name, "sepia");
+    /// assert_eq!(themes[0].palette.len(), 3);
+    /// assert_eq!(themes[0].color(2), None);
+    /// ```
+    ///
+    /// # Erreurs
+    ///
+    /// Renvoie une erreur si une ligne précède le premier thème, si une clé est inconnue, si une
+    /// couleur est mal formée ou si le thème de base n'existe pas.
+    pub fn parse(text: &str) -> Result<Vec<Theme>, ThemeError> {
+        let mut themes: Vec<Theme> = Vec::new();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |message: String| ThemeError::new(format!("line {}: {}", number + 1, message));
+            if let Some(name) = line.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')) {
+                themes.push(Theme {
+                    name: name.trim().to_string(),
+                    ..Theme::classic()
+                });
+                continue;
+            }
+            let theme = themes
+                .last_mut()
+                .ok_or_else(|| error(String::from("expected [name] before theme settings")))?;
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| error(format!("expected key = value, found {:?}", line)))?;
+            let value = value.trim();
+            match key.trim() {
+                "base" => {
+                    let base = Theme::preset(value).ok_or_else(|| error(format!("unknown preset {:?}", value)))?;
+                    *theme = Theme {
+                        name: theme.name.clone(),
+                        ..base
+                    };
+                }
+                "background" => theme.background = parse_color(value).map_err(error)?,
+                "gridlines" => theme.gridlines = parse_color(value).map_err(error)?,
+                "palette" => {
+                    theme.palette = value
+                        .split_whitespace()
+                        .map(|color| if color == "none" { Ok(None) } else { parse_color(color).map(Some) })
+                        .collect::<Result<_, _>>()
+                        .map_err(error)?;
+                }
+                key => return Err(error(format!("unknown key {:?}", key))),
+            }
+        }
+        Ok(themes)
+    }
+}
+
+impl fmt::Display for Theme {
+    /// Écrit le thème au format lu par `Theme::parse`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "[{}]", self.name)?;
+        writeln!(f, "background = {}", format_color(self.background))?;
+        writeln!(f, "gridlines = {}", format_color(self.gridlines))?;
+        let palette: Vec<String> = self
+            .palette
+            .iter()
+            .map(|color| color.map_or(String::from("none"), format_color))
+            .collect();
+        writeln!(f, "palette = {}", palette.join(" "))
+    }
+}
+
+/// Lit une couleur `#rrggbb` ou `#rrggbbaa`.
+fn parse_color(text: &str) -> Result<Color, String> {
+    let invalid = || format!("invalid color {:?}, expected #rrggbb or #rrggbbaa", text);
+    let hex = text.strip_prefix('#').ok_or_else(invalid)?;
+    if !(hex.len() == 6 || hex.len() == 8) || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+    let channel = |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).map_err(|_| invalid());
+    let alpha = if hex.len() == 8 { channel(6)? } else { 255 };
+    Ok(Color::from_rgba(channel(0)?, channel(2)?, channel(4)?, alpha))
+}
+
+/// Écrit une couleur `#rrggbb`, ou `#rrggbbaa` si elle n'est pas opaque.
+fn format_color(color: Color) -> String {
+    let (r, g, b, a) = color.to_rgba();
+    if a == 255 {
+        format!("#{:02x}{:02x}{:02x}", r, g, b)
+    } else {
+        format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConwaysGrid, WireworldGrid};
+
+    #[test]
+    fn test_presets_round_trip_through_config_text() {
+        let text: String = Theme::presets().iter().map(Theme::to_string).collect::<Vec<_>>().join("\n");
+        assert_eq!(Theme::parse(&text).unwrap(), Theme::presets());
+
+        let themes = Theme::parse("[night]\nbase = dark\ngridlines = #ffffff40\n").unwrap();
+        assert_eq!(themes[0].name, "night");
+        assert_eq!(themes[0].palette, Theme::dark().palette);
+        assert_eq!(themes[0].gridlines, Color::from_rgba(255, 255, 255, 64));
+
+        // Le fond du thème classique est celui des cellules mortes, visible entre les cellules
+        assert_eq!(Some(Theme::classic().background), Theme::classic().palette[0]);
+    }
+
+    #[test]
+    fn test_invalid_theme_files() {
+        assert!(Theme::parse("palette = #000000").is_err());
+        assert!(Theme::parse("[a]\npalette = #00000").is_err());
+        assert!(Theme::parse("[a]\nbase = neon").is_err());
+        assert!(Theme::parse("[a]\nforeground = #000000").is_err());
+        assert!(Theme::load("/nonexistent/themes.txt").unwrap_err().to_string().starts_with("invalid theme: cannot read"));
+    }
+
+    #[test]
+    fn test_apply_sets_multi_state_colors() {
+        let theme = Theme::high_contrast();
+        let mut grid = WireworldGrid::new(2, 2, false);
+        theme.apply(&mut grid);
+        assert_eq!(grid.palette().to_vec(), theme.palette[..4].to_vec());
+
+        // Une grille à deux états reçoit les couleurs des états 0 et 1
+        let mut grid = ConwaysGrid::new(2, 2, false);
+        Theme { palette: vec![None, Some(Color::WHITE)], ..Theme::dark() }.apply(&mut grid);
+        assert_eq!(grid.state_palette(), vec![None, Some(Color::WHITE)]);
+    }
+}
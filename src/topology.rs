@@ -0,0 +1,262 @@
+/// Topologie d'une grille, qui détermine comment les voisins situés hors de la grille sont ramenés à l'intérieur.
+///
+/// Les topologies qui recollent les bords avec une torsion retournent la grille : en traversant un
+/// bord tordu, un motif réapparaît de l'autre côté sous forme de son image miroir.
+///
+/// Les fonctions et les grilles qui acceptent `impl Into<Topology>` acceptent aussi l'ancien
+/// indicateur `toricgrid` : `true` désigne le tore et `false` le plan.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Topology {
+    /// Plan borné : les cellules hors de la grille sont mortes.
+    #[default]
+    Plane,
+    /// Tore : les bords opposés sont recollés.
+    Torus,
+    /// Bouteille de Klein dont les bords haut et bas sont recollés avec une torsion (la colonne est
+    /// retournée) ; les bords gauche et droit sont recollés normalement.
+    KleinBottleVertical,
+    /// Bouteille de Klein dont les bords gauche et droit sont recollés avec une torsion (la ligne
+    /// est retournée) ; les bords haut et bas sont recollés normalement.
+    KleinBottleHorizontal,
+    /// Plan projectif (cross-surface) : les deux paires de bords sont recollées avec une torsion.
+    CrossSurface,
+    /// Sphère : le bord haut est recollé au bord gauche et le bord bas au bord droit. Cette topologie
+    /// suppose une grille carrée ; les voisins diagonaux des coins, qui n'ont pas d'image, sont morts.
+    Sphere,
+    /// Cylindre : les bords gauche et droit sont recollés, les cellules au-delà des bords haut et bas sont mortes.
+    Cylinder,
+    /// Bords réfléchissants : une cellule hors de la grille est l'image miroir de la cellule
+    /// symétrique par rapport au bord (la cellule juste au-delà du bord reflète celle du bord).
+    Mirror,
+    /// Bords à état constant : toutes les cellules hors de la grille sont dans l'état donné, par
+    /// exemple `Constant(1)` pour un cadre de cellules vivantes.
+    Constant(u8),
+}
+
+impl From<bool> for Topology {
+    /// Convertit l'ancien indicateur `toricgrid` : `true` donne le tore et `false` le plan.
+    fn from(toricgrid: bool) -> Self {
+        if toricgrid {
+            Topology::Torus
+        } else {
+            Topology::Plane
+        }
+    }
+}
+
+impl Topology {
+    /// Ramène des coordonnées éventuellement hors de la grille à la cellule qu'elles désignent.
+    ///
+    /// Renvoie `None` si les coordonnées sortent de la grille par un bord qui n'est pas recollé,
+    /// y compris pour `Constant`, dont les cellules extérieures n'ont pas de cellule correspondante,
+    /// ainsi que pour une grille sans ligne ou sans colonne.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - L'indice de la ligne, éventuellement négatif ou supérieur au nombre de lignes.
+    /// * `col` - L'indice de la colonne, éventuellement négatif ou supérieur au nombre de colonnes.
+    /// * `rows` - Nombre de lignes de la grille.
+    /// * `cols` - Nombre de colonnes de la grille.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::Topology;
+    ///
+    /// assert_eq!(Topology::Plane.wrap(-1, 2, 4, 4), None);
+    /// assert_eq!(Topology::Torus.wrap(-1, 2, 4, 4), Some((3, 2)));
+    /// // La torsion retourne la colonne en traversant le bord haut
+    /// assert_eq!(Topology::KleinBottleVertical.wrap(-1, 0, 4, 4), Some((3, 3)));
+    /// // Sur la sphère, la cellule au-dessus de la colonne 2 est la cellule de la ligne 2 au bord gauche
+    /// assert_eq!(Topology::Sphere.wrap(-1, 2, 4, 4), Some((2, 0)));
+    /// ```
+    pub fn wrap(&self, row: isize, col: isize, rows: usize, cols: usize) -> Option<(usize, usize)> {
+        let (r, c) = (rows as isize, cols as isize);
+        let inside = |row: isize, col: isize| row >= 0 && row < r && col >= 0 && col < c;
+        if inside(row, col) {
+            return Some((row as usize, col as usize));
+        }
+        // Une grille vide n'a aucune cellule vers laquelle ramener les coordonnées
+        if rows == 0 || cols == 0 {
+            return None;
+        }
+        let (row, col) = match self {
+            Topology::Plane | Topology::Constant(_) => return None,
+            Topology::Mirror => {
+                let reflect = |index: isize, len: isize| {
+                    let index = index.rem_euclid(2 * len);
+                    if index < len { index } else { 2 * len - 1 - index }
+                };
+                (reflect(row, r), reflect(col, c))
+            }
+            Topology::Torus => (row.rem_euclid(r), col.rem_euclid(c)),
+            Topology::Cylinder if row < 0 || row >= r => return None,
+            Topology::Cylinder => (row, col.rem_euclid(c)),
+            Topology::KleinBottleVertical | Topology::KleinBottleHorizontal | Topology::CrossSurface => {
+                // Chaque traversée d'un bord tordu retourne la coordonnée parallèle à ce bord
+                let row_twists = row.div_euclid(r) % 2 != 0 && *self != Topology::KleinBottleHorizontal;
+                let col_twists = col.div_euclid(c) % 2 != 0 && *self != Topology::KleinBottleVertical;
+                let (mut row, mut col) = (row.rem_euclid(r), col.rem_euclid(c));
+                if row_twists {
+                    col = c - 1 - col;
+                }
+                if col_twists {
+                    row = r - 1 - row;
+                }
+                (row, col)
+            }
+            Topology::Sphere => match (row, col) {
+                (row, col) if row < 0 && col >= 0 && col < c => (col, -1 - row),
+                (row, col) if row >= r && col >= 0 && col < c => (col, c - 1 - (row - r)),
+                (row, col) if col < 0 && row >= 0 && row < r => (-1 - col, row),
+                (row, col) if col >= c && row >= 0 && row < r => (r - 1 - (col - c), row),
+                _ => return None,
+            },
+        };
+        inside(row, col).then_some((row as usize, col as usize))
+    }
+
+    /// Renvoie l'état de la cellule située aux coordonnées données, éventuellement hors de la grille.
+    ///
+    /// Les cellules hors de la grille valent l'état de la cellule désignée par `wrap`, l'état fixé
+    /// par `Constant`, ou 0 au-delà d'un bord mort.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::Topology;
+    ///
+    /// let current_cells = vec![1, 0, 0, 0];
+    /// assert_eq!(Topology::Plane.state_at(&current_cells, -1, 0, 2, 2), 0);
+    /// assert_eq!(Topology::Mirror.state_at(&current_cells, -1, 0, 2, 2), 1);
+    /// assert_eq!(Topology::Constant(3).state_at(&current_cells, 0, 2, 2, 2), 3);
+    /// ```
+    pub fn state_at(&self, current_cells: &[u8], row: isize, col: isize, rows: usize, cols: usize) -> u8 {
+        match self.wrap(row, col, rows, cols) {
+            Some((row, col)) => current_cells[row * cols + col],
+            None => match self {
+                Topology::Constant(state) => *state,
+                _ => 0,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{grid_count_neighbors, grid_update, ConwaysGrid, Grid, WireworldGrid};
+
+    /// Planeur se déplaçant d'une case vers le bas et la droite toutes les 4 générations.
+    const GLIDER: [(isize, isize); 5] = [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)];
+
+    type Cells = Vec<(usize, usize)>;
+
+    /// Fait traverser un bord à un planeur et compare le résultat au planeur déplié dans le plan
+    /// puis ramené dans la grille par la topologie.
+    fn glider_across_seam(topology: Topology, start: (isize, isize)) -> (Cells, Cells) {
+        let (rows, cols) = (12, 12);
+        let mut grid = ConwaysGrid::new(rows, cols, false);
+        grid.set_topology(topology);
+        for (di, dj) in GLIDER {
+            let (row, col) = topology.wrap(start.0 + di, start.1 + dj, rows, cols).unwrap();
+            grid.set_cell_state(row, col, 1);
+        }
+        for _ in 0..24 {
+            grid.update();
+        }
+        let mut actual: Cells = (0..rows)
+            .flat_map(|row| (0..cols).map(move |col| (row, col)))
+            .filter(|&(row, col)| grid.is_alive(row, col))
+            .collect();
+        let mut expected: Cells = GLIDER
+            .iter()
+            .filter_map(|&(di, dj)| topology.wrap(start.0 + 6 + di, start.1 + 6 + dj, rows, cols))
+            .collect();
+        actual.sort();
+        expected.sort();
+        (actual, expected)
+    }
+
+    #[test]
+    fn test_glider_crosses_bottom_seam() {
+        for topology in [Topology::Torus, Topology::KleinBottleVertical, Topology::CrossSurface, Topology::Sphere] {
+            let (actual, expected) = glider_across_seam(topology, (6, 0));
+            assert_eq!(expected.len(), 5);
+            assert_eq!(actual, expected, "{:?}", topology);
+        }
+    }
+
+    #[test]
+    fn test_glider_crosses_right_seam() {
+        for topology in [Topology::Torus, Topology::KleinBottleHorizontal, Topology::CrossSurface, Topology::Cylinder] {
+            let (actual, expected) = glider_across_seam(topology, (0, 6));
+            assert_eq!(expected.len(), 5);
+            assert_eq!(actual, expected, "{:?}", topology);
+        }
+    }
+
+    #[test]
+    fn test_twisted_seam_mirrors_glider() {
+        // Après une traversée tordue du bord bas, le planeur repart vers la gauche
+        let (mirrored, _) = glider_across_seam(Topology::KleinBottleVertical, (6, 0));
+        let (straight, _) = glider_across_seam(Topology::Torus, (6, 0));
+        let min_col = |cells: &[(usize, usize)]| cells.iter().map(|&(_, col)| col).min().unwrap();
+        assert_eq!(min_col(&straight), 6);
+        assert_eq!(min_col(&mirrored), 3);
+    }
+
+    #[test]
+    fn test_mirror_reflects_edge_cells() {
+        // Une cellule isolée dans un coin compte ses trois reflets comme voisines et survit
+        let current_cells = vec![1, 0, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(grid_count_neighbors(0, 0, &current_cells, 3, 3, Topology::Mirror), 3);
+        assert_eq!(grid_count_neighbors(1, 1, &current_cells, 3, 3, Topology::Mirror), 1);
+        assert_eq!(Topology::Mirror.wrap(3, -2, 3, 3), Some((2, 1)));
+    }
+
+    #[test]
+    fn test_constant_frame_feeds_births() {
+        // Avec un cadre vivant, le milieu de chaque bord a trois voisines vivantes et naît,
+        // tandis que les coins en ont cinq
+        let mut current_cells = vec![0; 9];
+        let mut next_cells = vec![0; 9];
+        assert_eq!(grid_count_neighbors(0, 0, &current_cells, 3, 3, Topology::Constant(1)), 5);
+        grid_update(&mut current_cells, &mut next_cells, 3, 3, Topology::Constant(1));
+        assert_eq!(current_cells, vec![0, 1, 0, 1, 0, 1, 0, 1, 0]);
+    }
+
+    #[test]
+    fn test_glider_stops_at_dead_edges() {
+        for topology in [Topology::Plane, Topology::Cylinder] {
+            let (actual, expected) = glider_across_seam(topology, (6, 0));
+            assert!(expected.is_empty());
+            assert_ne!(actual.len(), 5, "{:?}", topology);
+        }
+    }
+
+    #[test]
+    fn test_other_grids_take_a_topology() {
+        // Sur un cylindre, la tête d'électron du bord droit atteint le conducteur du bord gauche
+        for (topology, expected) in [(Topology::Cylinder, WireworldGrid::HEAD), (Topology::Plane, WireworldGrid::CONDUCTOR)] {
+            let mut grid = WireworldGrid::new(3, 3, topology);
+            grid.set_cell_state(1, 2, WireworldGrid::HEAD);
+            grid.set_cell_state(1, 0, WireworldGrid::CONDUCTOR);
+            grid.update();
+            assert_eq!(grid.current_cells()[3], expected, "{:?}", topology);
+            assert!(!grid.is_toricgrid());
+        }
+    }
+
+    #[test]
+    fn test_empty_grid_has_no_wrapped_cells() {
+        for topology in [Topology::Torus, Topology::Mirror, Topology::KleinBottleVertical, Topology::CrossSurface] {
+            assert_eq!(topology.wrap(-1, 2, 0, 5), None);
+            assert_eq!(topology.wrap(1, -1, 3, 0), None);
+        }
+        let mut grid = ConwaysGrid::new(0, 5, true);
+        grid.set_rule(crate::Rule::parse("R2,C2,M0,S2..3,B3..3,NM").unwrap());
+        grid.update();
+        assert!(grid.current_cells().is_empty());
+    }
+}
@@ -0,0 +1,366 @@
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::{cursor, execute, queue, terminal};
+
+use crate::Grid;
+
+/// FPS minimal et maximal accepté lors du changement de vitesse au clavier.
+const MIN_FPS: u32 = 1;
+const MAX_FPS: u32 = 120;
+
+/// Interface en mode terminal, alternative à `LifeGui` pour les machines sans carte graphique.
+///
+/// La grille est affichée sur l'écran alternatif du terminal avec des demi-blocs (`▀`, `▄`, `█`),
+/// chaque caractère représentant deux lignes de la grille. Une grille plus grande que le terminal
+/// est découpée, et la partie affichée suit le curseur.
+///
+/// # Commandes
+///
+/// * `Espace` - Met en pause ou relance la simulation.
+/// * `n` / `.` - Avance d'une génération lorsque la simulation est en pause.
+/// * `+` / `-` - Augmente ou diminue la vitesse de la simulation.
+/// * Flèches / `h` `j` `k` `l` - Déplace le curseur.
+/// * `Entrée` / `x` - Fait passer la cellule sous le curseur à l'état suivant.
+/// * `q` / `Échap` / `Ctrl-C` - Quitte l'interface.
+pub struct LifeTui<G> {
+    grid: G,
+    is_paused: bool,
+    fps: u32,
+    days: u32,
+    cursor_row: usize,
+    cursor_col: usize,
+    quit: bool,
+    /// Taille du terminal, en colonnes et en lignes.
+    size: (u16, u16),
+    /// Première ligne de demi-blocs affichée.
+    view_line: usize,
+    /// Première colonne affichée.
+    view_col: usize,
+}
+
+/// Garde qui restaure l'état du terminal lorsqu'elle est détruite, même en cas d'erreur.
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(TerminalGuard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+impl<G: Grid> LifeTui<G> {
+    /// Crée une nouvelle instance de `LifeTui` avec la grille spécifiée.
+    pub fn new(grid: G) -> Self {
+        LifeTui {
+            grid,
+            is_paused: false,
+            fps: 10,
+            days: 0,
+            cursor_row: 0,
+            cursor_col: 0,
+            quit: false,
+            size: (80, 24),
+            view_line: 0,
+            view_col: 0,
+        }
+    }
+
+    /// Méthode pour modifier le FPS
+    pub fn set_fps(&mut self, fps: u32) {
+        self.fps = fps.clamp(MIN_FPS, MAX_FPS);
+    }
+
+    /// Méthode pour renvoyer une référence vers la grille affichée
+    pub fn grid(&self) -> &G {
+        &self.grid
+    }
+
+    /// Lance la boucle principale de l'interface jusqu'à ce que l'utilisateur quitte.
+    ///
+    /// # Erreurs
+    ///
+    /// Cette méthode retourne une erreur d'entrée/sortie si le terminal ne peut pas être configuré
+    /// ou si l'écriture échoue.
+    pub fn run(&mut self) -> io::Result<()> {
+        let _guard = TerminalGuard::enter()?;
+        let mut stdout = io::stdout();
+        let mut last_update = Instant::now();
+        self.quit = false;
+        self.resize(terminal::size()?);
+
+        while !self.quit {
+            self.draw(&mut stdout)?;
+
+            // Attend un événement du terminal jusqu'à la prochaine génération
+            let frame = Duration::from_secs(1) / self.fps;
+            let timeout = frame.saturating_sub(last_update.elapsed());
+            if event::poll(timeout)? {
+                match event::read()? {
+                    Event::Key(key) => self.handle_key(key),
+                    Event::Resize(width, height) => {
+                        self.resize((width, height));
+                        queue!(stdout, terminal::Clear(terminal::ClearType::All))?;
+                    }
+                    _ => {}
+                }
+            }
+
+            if last_update.elapsed() >= frame {
+                last_update = Instant::now();
+                if !self.is_paused {
+                    self.step();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Avance la simulation d'une génération.
+    fn step(&mut self) {
+        self.days += 1;
+        self.grid.update();
+    }
+
+    /// Prend en compte une nouvelle taille du terminal.
+    fn resize(&mut self, size: (u16, u16)) {
+        self.size = size;
+        self.scroll_to_cursor();
+    }
+
+    /// Nombre de lignes de demi-blocs et de colonnes affichées, la dernière ligne du terminal étant
+    /// réservée à la ligne d'état.
+    fn visible(&self) -> (usize, usize) {
+        let (width, height) = self.size;
+        ((height as usize).saturating_sub(1).max(1), (width as usize).max(1))
+    }
+
+    /// Déplace la partie affichée pour qu'elle contienne le curseur, sans dépasser de la grille.
+    fn scroll_to_cursor(&mut self) {
+        let (lines, cols) = self.visible();
+        let cursor_line = self.cursor_row / 2;
+        self.view_line = self.view_line.min(self.grid.rows().div_ceil(2).saturating_sub(lines));
+        self.view_line = self.view_line.clamp((cursor_line + 1).saturating_sub(lines), cursor_line);
+        self.view_col = self.view_col.min(self.grid.cols().saturating_sub(cols));
+        self.view_col = self.view_col.clamp((self.cursor_col + 1).saturating_sub(cols), self.cursor_col);
+    }
+
+    /// Applique l'action associée à une touche du clavier.
+    fn handle_key(&mut self, key: KeyEvent) {
+        if key.kind == KeyEventKind::Release {
+            return;
+        }
+        match key.code {
+            // Le mode brut du terminal intercepte Ctrl-C, qui n'interrompt plus le programme
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => self.quit = true,
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Char(' ') => self.is_paused = !self.is_paused,
+            KeyCode::Char('n') | KeyCode::Char('.') if self.is_paused => self.step(),
+            KeyCode::Char('+') => self.set_fps(self.fps.saturating_mul(2)),
+            KeyCode::Char('-') => self.set_fps(self.fps / 2),
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.cursor_row = self.cursor_row.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.cursor_row = (self.cursor_row + 1).min(self.grid.rows().saturating_sub(1));
+            }
+            KeyCode::Left | KeyCode::Char('h') => {
+                self.cursor_col = self.cursor_col.saturating_sub(1);
+            }
+            KeyCode::Right | KeyCode::Char('l') => {
+                self.cursor_col = (self.cursor_col + 1).min(self.grid.cols().saturating_sub(1));
+            }
+            KeyCode::Enter | KeyCode::Char('x')
+                if self.cursor_row < self.grid.rows() && self.cursor_col < self.grid.cols() =>
+            {
+                self.grid.cycle_cell_state(self.cursor_row, self.cursor_col);
+            }
+            _ => {}
+        }
+        self.scroll_to_cursor();
+    }
+
+    /// Redessine la partie affichée de la grille et la ligne d'état sur le terminal.
+    fn draw(&self, out: &mut impl Write) -> io::Result<()> {
+        let lines = grid_halfblocks(self.grid.current_cells(), self.grid.rows(), self.grid.cols());
+        let (visible_lines, visible_cols) = self.visible();
+        let cursor_line = self.cursor_row / 2;
+
+        let mut shown = 0;
+        for (i, line) in lines.iter().enumerate().skip(self.view_line).take(visible_lines) {
+            let chars: Vec<char> = line.chars().skip(self.view_col).take(visible_cols).collect();
+            queue!(out, cursor::MoveTo(0, shown as u16))?;
+            shown += 1;
+            // Le curseur est affiché en vidéo inverse sur le caractère qui le contient
+            let cursor = self.cursor_col.checked_sub(self.view_col).filter(|&col| i == cursor_line && col < chars.len());
+            let Some(col) = cursor else {
+                queue!(out, Print(chars.iter().collect::<String>()))?;
+                continue;
+            };
+            let before: String = chars[..col].iter().collect();
+            let after: String = chars[col + 1..].iter().collect();
+            queue!(
+                out,
+                Print(before),
+                SetAttribute(Attribute::Reverse),
+                Print(chars[col]),
+                SetAttribute(Attribute::Reset),
+                Print(after)
+            )?;
+        }
+
+        let status = format!(
+            "Days: {}  FPS: {}  {}  ({}, {})  espace:pause n:pas +/-:vitesse entrée:inverser q:quitter",
+            self.days,
+            self.fps,
+            if self.is_paused { "PAUSE" } else { "     " },
+            self.cursor_row,
+            self.cursor_col,
+        );
+        // La ligne d'état ne doit pas déborder, sans quoi le terminal défilerait à chaque image
+        queue!(
+            out,
+            cursor::MoveTo(0, shown as u16),
+            terminal::Clear(terminal::ClearType::CurrentLine),
+            Print(truncate_status(&status, self.size.0 as usize))
+        )?;
+        out.flush()
+    }
+}
+
+/// Tronque la ligne d'état pour qu'elle tienne sur une ligne de `width` colonnes, la dernière
+/// colonne restant libre pour que le curseur ne passe pas à la ligne suivante.
+fn truncate_status(status: &str, width: usize) -> String {
+    status.chars().take(width.saturating_sub(1)).collect()
+}
+
+/// Convertit l'état des cellules en lignes de texte utilisant des demi-blocs.
+///
+/// Chaque caractère représente deux lignes de la grille : `▀` pour une cellule vivante en haut,
+/// `▄` pour une cellule vivante en bas, `█` lorsque les deux sont vivantes et un espace sinon.
+/// Si le nombre de lignes est impair, la dernière ligne est complétée par des cellules mortes.
+///
+/// # Arguments
+///
+/// * `current_cells` - Vecteur contenant l'état actuel de chaque cellule de la grille.
+/// * `rows` - Nombre de lignes de la grille.
+/// * `cols` - Nombre de colonnes de la grille.
+///
+/// # Exemple
+///
+/// ```
+/// use crate::lifers::grid_halfblocks;
+///
+/// let current_cells = vec![
+///     1, 0, 1,
+///     1, 1, 0,
+///     0, 1, 0,
+/// ];
+///
+/// let lines = grid_halfblocks(&current_cells, 3, 3);
+/// assert_eq!(lines, vec!["█▄▀", " ▀ "]);
+/// ```
+pub fn grid_halfblocks(current_cells: &[u8], rows: usize, cols: usize) -> Vec<String> {
+    let mut lines = Vec::with_capacity(rows.div_ceil(2));
+    for row in (0..rows).step_by(2) {
+        let mut line = String::with_capacity(cols * 3);
+        for col in 0..cols {
+            let top = grid_is_alive_at(current_cells, row, col, rows, cols);
+            let bottom = grid_is_alive_at(current_cells, row + 1, col, rows, cols);
+            line.push(match (top, bottom) {
+                (true, true) => '█',
+                (true, false) => '▀',
+                (false, true) => '▄',
+                (false, false) => ' ',
+            });
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Indique si la cellule est vivante, les cellules hors de la grille étant considérées mortes.
+fn grid_is_alive_at(current_cells: &[u8], row: usize, col: usize, rows: usize, cols: usize) -> bool {
+    row < rows && col < cols && current_cells[crate::grid_index(row, col, cols)] >= 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_halfblocks_odd_rows() {
+        let current_cells = vec![1, 0, 0, 1, 1, 1];
+        let lines = grid_halfblocks(&current_cells, 3, 2);
+        assert_eq!(lines, vec!["▀▄", "▀▀"]);
+    }
+
+    #[test]
+    fn test_tui_cursor_stays_in_grid() {
+        use crate::ConwaysGrid;
+        use crossterm::event::KeyModifiers;
+
+        let mut tui = LifeTui::new(ConwaysGrid::new(2, 2, true));
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        for _ in 0..5 {
+            tui.handle_key(key(KeyCode::Down));
+            tui.handle_key(key(KeyCode::Right));
+        }
+        assert_eq!((tui.cursor_row, tui.cursor_col), (1, 1));
+
+        tui.handle_key(key(KeyCode::Enter));
+        assert!(tui.grid().is_alive(1, 1));
+    }
+
+    #[test]
+    fn test_truncate_status() {
+        assert_eq!(truncate_status("Days: 12  FPS: 30", 10), "Days: 12 ");
+        assert_eq!(truncate_status("Days: 12", 80), "Days: 12");
+        assert_eq!(truncate_status("Days: 12", 0), "");
+    }
+
+    #[test]
+    fn test_view_follows_cursor_and_clips_grid() {
+        use crate::ConwaysGrid;
+
+        // Terminal de 10 colonnes et 4 lignes : 3 lignes de demi-blocs, soit 6 lignes de la grille
+        let mut tui = LifeTui::new(ConwaysGrid::new(20, 30, false));
+        tui.resize((10, 4));
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        for _ in 0..12 {
+            tui.handle_key(key(KeyCode::Down));
+            tui.handle_key(key(KeyCode::Right));
+        }
+        assert_eq!((tui.cursor_row, tui.cursor_col), (12, 12));
+        assert_eq!((tui.view_line, tui.view_col), (4, 3));
+
+        let mut out = Vec::new();
+        tui.draw(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(!text.contains(&" ".repeat(11)));
+
+        // Un terminal agrandi ramène la partie affichée dans la grille
+        tui.resize((200, 100));
+        assert_eq!((tui.view_line, tui.view_col), (0, 0));
+    }
+
+    #[test]
+    fn test_ctrl_c_quits_and_empty_grid_draws() {
+        use crate::ConwaysGrid;
+
+        let mut tui = LifeTui::new(ConwaysGrid::new(3, 0, false));
+        tui.draw(&mut Vec::new()).unwrap();
+        tui.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL));
+        assert!(tui.quit);
+    }
+}
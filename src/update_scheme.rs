@@ -0,0 +1,90 @@
+/// Ordre dans lequel les cellules d'une grille sont mises à jour à chaque génération.
+///
+/// Les schémas aléatoires utilisent le générateur de la grille : une même graine donne donc la
+/// même suite de générations.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum UpdateScheme {
+    /// Toutes les cellules sont calculées à partir de la génération précédente.
+    #[default]
+    Synchronous,
+    /// Les cellules sont mises à jour sur place une par une, dans un ordre aléatoire tiré à chaque génération.
+    RandomSequential,
+    /// Les cellules sont mises à jour sur place une par une, ligne par ligne de haut en bas.
+    FixedSweep,
+    /// Chaque cellule est mise à jour à partir de la génération précédente avec la probabilité
+    /// donnée, et conserve son état sinon.
+    RandomIndependent(f64),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConwaysGrid, Grid, Rule, Topology};
+    use rand::prelude::*;
+
+    fn random_grid(seed: u64) -> ConwaysGrid {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let cells: Vec<u8> = (0..20 * 20).map(|_| rng.gen_range(0..2)).collect();
+        let mut grid = ConwaysGrid::from_vect(cells, 20, 20, true);
+        grid.set_seed(seed);
+        grid
+    }
+
+    #[test]
+    fn test_next_cell_state_matches_synchronous_update() {
+        for rulestring in ["B3/S23", "B2/S34H", "R2,C3,M1,S4..8,B5..7,NN", "B2n3/S23-q"] {
+            let mut grid = random_grid(1);
+            grid.set_rule(Rule::parse(rulestring).unwrap());
+            grid.set_topology(Topology::Mirror);
+            let previous = grid.current_cells().clone();
+            grid.update();
+            for row in 0..20 {
+                for col in 0..20 {
+                    let next = grid.rule().next_cell_state(row, col, &previous, 20, 20, Topology::Mirror);
+                    assert_eq!(next, grid.current_cells()[grid.index(row, col)], "{}", rulestring);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_independent_extremes() {
+        let mut synchronous = random_grid(2);
+        let mut always = random_grid(2);
+        let mut never = random_grid(2);
+        always.set_update_scheme(UpdateScheme::RandomIndependent(1.0));
+        never.set_update_scheme(UpdateScheme::RandomIndependent(0.0));
+        let start = never.current_cells().clone();
+        for _ in 0..5 {
+            synchronous.update();
+            always.update();
+            never.update();
+        }
+        assert_eq!(always.current_cells(), synchronous.current_cells());
+        assert_eq!(never.current_cells(), &start);
+
+        // Une probabilité invalide ne met aucune cellule à jour
+        let mut invalid = random_grid(2);
+        invalid.set_update_scheme(UpdateScheme::RandomIndependent(f64::NAN));
+        assert_eq!(invalid.update_scheme(), UpdateScheme::RandomIndependent(0.0));
+        invalid.update();
+        assert_eq!(invalid.current_cells(), &start);
+        always.set_update_scheme(UpdateScheme::RandomIndependent(3.0));
+        assert_eq!(always.update_scheme(), UpdateScheme::RandomIndependent(1.0));
+    }
+
+    #[test]
+    fn test_random_sequential_is_reproducible() {
+        let run = |seed| {
+            let mut grid = random_grid(3);
+            grid.set_seed(seed);
+            grid.set_update_scheme(UpdateScheme::RandomSequential);
+            for _ in 0..5 {
+                grid.update();
+            }
+            grid.current_cells().clone()
+        };
+        assert_eq!(run(11), run(11));
+        assert_ne!(run(11), run(12));
+    }
+}
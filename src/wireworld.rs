@@ -0,0 +1,311 @@
+use crate::life::*;
+use crate::{grid_resize, Anchor, CellStyle, Topology};
+use ggez::{graphics::Color, graphics::Canvas, Context, GameResult};
+use rand::prelude::*;
+use std::fmt;
+
+/// Structure représentant une grille de l'automate Wireworld.
+///
+/// Chaque cellule est dans l'un des quatre états suivants :
+///
+/// * `EMPTY` (0) - Cellule vide, elle le reste.
+/// * `HEAD` (1) - Tête d'électron, elle devient une queue d'électron.
+/// * `TAIL` (2) - Queue d'électron, elle redevient un conducteur.
+/// * `CONDUCTOR` (3) - Conducteur, il devient une tête d'électron si une ou deux de ses 8 voisines sont des têtes.
+#[derive(Clone)]
+pub struct WireworldGrid {
+    /// Vecteur contenant l'état actuel de chaque cellule de la grille.
+    current_cells: Vec<u8>,
+    /// Vecteur contenant l'état suivant de chaque cellule de la grille.
+    next_cells: Vec<u8>,
+    /// Nombre de lignes de la grille.
+    rows: usize,
+    /// Nombre de colonnes de la grille.
+    cols: usize,
+    /// Topologie de la grille.
+    topology: Topology,
+    /// Couleur de chacun des quatre états, indexée par la valeur de l'état.
+    palette: [Option<Color>; 4],
+    /// Style des cellules dessinées.
+    cell_style: CellStyle,
+}
+
+impl WireworldGrid {
+    /// État d'une cellule vide.
+    pub const EMPTY: u8 = 0;
+    /// État d'une tête d'électron.
+    pub const HEAD: u8 = 1;
+    /// État d'une queue d'électron.
+    pub const TAIL: u8 = 2;
+    /// État d'un conducteur.
+    pub const CONDUCTOR: u8 = 3;
+
+    /// Palette par défaut : fond noir, têtes bleues, queues rouges et conducteurs jaunes.
+    fn default_palette() -> [Option<Color>; 4] {
+        [
+            Some(Color::from_rgb(0, 0, 0)),
+            Some(Color::from_rgb(0, 128, 255)),
+            Some(Color::from_rgb(255, 64, 0)),
+            Some(Color::from_rgb(255, 200, 0)),
+        ]
+    }
+
+    /// Modifie la couleur de chacun des quatre états.
+    pub fn set_palette(&mut self, palette: [Option<Color>; 4]) {
+        self.palette = palette;
+    }
+
+    /// Renvoie la couleur de chacun des quatre états.
+    pub fn palette(&self) -> &[Option<Color>; 4] {
+        &self.palette
+    }
+
+    /// Calcule l'état suivant d'une cellule à partir de son état et du nombre de têtes d'électron voisines.
+    pub fn next_state(cell: u8, heads: usize) -> u8 {
+        match cell {
+            WireworldGrid::HEAD => WireworldGrid::TAIL,
+            WireworldGrid::TAIL => WireworldGrid::CONDUCTOR,
+            WireworldGrid::CONDUCTOR if heads == 1 || heads == 2 => WireworldGrid::HEAD,
+            WireworldGrid::CONDUCTOR => WireworldGrid::CONDUCTOR,
+            _ => WireworldGrid::EMPTY,
+        }
+    }
+
+    /// Compte le nombre de têtes d'électron parmi les 8 voisines d'une cellule.
+    fn count_heads(&self, row: usize, col: usize) -> usize {
+        let mut count = 0;
+        for di in -1..=1isize {
+            for dj in -1..=1isize {
+                if di == 0 && dj == 0 {
+                    continue;
+                }
+                let i = row as isize + di;
+                let j = col as isize + dj;
+                if self.topology.state_at(&self.current_cells, i, j, self.rows, self.cols) == WireworldGrid::HEAD {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+}
+
+// Implémentation d'une méthode pour afficher la grille
+impl fmt::Display for WireworldGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                let symbol = match self.current_cells[grid_index(row, col, self.cols)] {
+                    WireworldGrid::HEAD => 'H',
+                    WireworldGrid::TAIL => 't',
+                    WireworldGrid::CONDUCTOR => '#',
+                    _ => '.',
+                };
+                write!(f, "{}", symbol)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Grid for WireworldGrid {
+    fn display(&self) {
+        println!("{}", self);
+    }
+
+    /// Initialise une nouvelle grille Wireworld avec des cellules vides.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::{Grid, WireworldGrid};
+    ///
+    /// let grid = WireworldGrid::new(3, 3, false);
+    /// assert_eq!(grid.current_cells(), &vec![WireworldGrid::EMPTY; 9]);
+    /// ```
+    fn new(rows: usize, cols: usize, topology: impl Into<Topology>) -> WireworldGrid {
+        WireworldGrid {
+            current_cells: vec![WireworldGrid::EMPTY; rows * cols],
+            next_cells: vec![WireworldGrid::EMPTY; rows * cols],
+            rows,
+            cols,
+            topology: topology.into(),
+            palette: WireworldGrid::default_palette(),
+            cell_style: CellStyle::default(),
+        }
+    }
+
+    /// Initialise une nouvelle grille Wireworld dont chaque cellule est dans un état aléatoire.
+    fn new_random(rows: usize, cols: usize, topology: impl Into<Topology>) -> WireworldGrid {
+        let mut rng: ThreadRng = rand::thread_rng();
+        let current_cells: Vec<u8> = (0..rows * cols).map(|_| rng.gen_range(0..4)).collect();
+        WireworldGrid::from_vect(current_cells, rows, cols, topology)
+    }
+
+    /// Crée une nouvelle grille Wireworld à partir d'un vecteur de cellules.
+    ///
+    /// # Panics
+    ///
+    /// Cette méthode panique si la longueur du vecteur `cels` ne correspond pas à `rows * cols`.
+    fn from_vect(cels: Vec<u8>, rows: usize, cols: usize, topology: impl Into<Topology>) -> Self {
+        assert_eq!(cels.len(), rows * cols);
+        WireworldGrid {
+            current_cells: cels,
+            next_cells: vec![WireworldGrid::EMPTY; rows * cols],
+            rows,
+            cols,
+            topology: topology.into(),
+            palette: WireworldGrid::default_palette(),
+            cell_style: CellStyle::default(),
+        }
+    }
+
+    /// Setter pour la couleur des conducteurs
+    fn set_color_alive(&mut self, color: Option<Color>) {
+        self.palette[WireworldGrid::CONDUCTOR as usize] = color;
+    }
+
+    /// Setter pour la couleur des cellules vides
+    fn set_color_not_alive(&mut self, color: Option<Color>) {
+        self.palette[WireworldGrid::EMPTY as usize] = color;
+    }
+
+    fn set_state_colors(&mut self, palette: &[Option<Color>]) {
+        for (color, &state_color) in self.palette.iter_mut().zip(palette) {
+            *color = state_color;
+        }
+    }
+
+    fn cell_style(&self) -> CellStyle {
+        self.cell_style
+    }
+
+    fn set_cell_style(&mut self, style: CellStyle) {
+        self.cell_style = style;
+    }
+
+    fn rows(&self) -> usize {
+        self.rows
+    }
+
+    fn cols(&self) -> usize {
+        self.cols
+    }
+
+    fn resize(&mut self, rows: usize, cols: usize, anchor: Anchor) {
+        self.current_cells = grid_resize(&self.current_cells, self.rows, self.cols, rows, cols, anchor);
+        self.next_cells = vec![WireworldGrid::EMPTY; rows * cols];
+        self.rows = rows;
+        self.cols = cols;
+    }
+
+    fn is_toricgrid(&self) -> bool {
+        self.topology == Topology::Torus
+    }
+
+    fn current_cells(&self) -> &Vec<u8> {
+        &self.current_cells
+    }
+
+    fn set_cell_state(&mut self, row: usize, col: usize, alive: u8) {
+        grid_set_cell_state(row, col, alive.min(WireworldGrid::CONDUCTOR), &mut self.current_cells, self.cols)
+    }
+
+    /// Inverse une cellule entre l'état vide et l'état conducteur.
+    fn toggle_cell_state(&mut self, row: usize, col: usize) {
+        let index = grid_index(row, col, self.cols);
+        self.current_cells[index] = if self.current_cells[index] == WireworldGrid::EMPTY {
+            WireworldGrid::CONDUCTOR
+        } else {
+            WireworldGrid::EMPTY
+        };
+    }
+
+    /// Fait passer une cellule à l'état suivant : vide, conducteur, tête, queue puis de nouveau vide.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use crate::lifers::{Grid, WireworldGrid};
+    ///
+    /// let mut grid = WireworldGrid::new(1, 1, false);
+    /// grid.cycle_cell_state(0, 0);
+    /// assert_eq!(grid.current_cells()[0], WireworldGrid::CONDUCTOR);
+    /// grid.cycle_cell_state(0, 0);
+    /// assert_eq!(grid.current_cells()[0], WireworldGrid::HEAD);
+    /// ```
+    fn cycle_cell_state(&mut self, row: usize, col: usize) {
+        let index = grid_index(row, col, self.cols);
+        self.current_cells[index] = match self.current_cells[index] {
+            WireworldGrid::EMPTY => WireworldGrid::CONDUCTOR,
+            WireworldGrid::CONDUCTOR => WireworldGrid::HEAD,
+            WireworldGrid::HEAD => WireworldGrid::TAIL,
+            _ => WireworldGrid::EMPTY,
+        };
+    }
+
+    /// Indique si une cellule n'est pas vide.
+    fn is_alive(&self, row: usize, col: usize) -> bool {
+        grid_is_alive(row, col, &self.current_cells, self.cols)
+    }
+
+    fn index(&self, row: usize, col: usize) -> usize {
+        grid_index(row, col, self.cols)
+    }
+
+    /// Compte le nombre de têtes d'électron parmi les voisines d'une cellule.
+    fn count_neighbors(&self, row: usize, col: usize) -> usize {
+        self.count_heads(row, col)
+    }
+
+    /// Met à jour l'état de la grille selon les règles de Wireworld.
+    fn update(&mut self) {
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                let index = grid_index(row, col, self.cols);
+                self.next_cells[index] = WireworldGrid::next_state(self.current_cells[index], self.count_heads(row, col));
+            }
+        }
+        std::mem::swap(&mut self.current_cells, &mut self.next_cells);
+    }
+
+    /// Dessine la grille en utilisant une couleur par état.
+    fn draw(&self, ctx: &mut Context, canvas: &mut Canvas, cell_size: f32) -> GameResult {
+        draw_grid_palette(ctx, canvas, self, cell_size, &self.palette)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_electron_moves_along_wire() {
+        // Un fil horizontal avec un électron (tête puis queue) se déplaçant vers la droite
+        let mut grid = WireworldGrid::from_vect(vec![2, 1, 3, 3, 3], 1, 5, false);
+        grid.update();
+        assert_eq!(grid.current_cells(), &vec![3, 2, 1, 3, 3]);
+        grid.update();
+        assert_eq!(grid.current_cells(), &vec![3, 3, 2, 1, 3]);
+    }
+
+    #[test]
+    fn test_conductor_needs_one_or_two_heads() {
+        assert_eq!(WireworldGrid::next_state(WireworldGrid::CONDUCTOR, 0), WireworldGrid::CONDUCTOR);
+        assert_eq!(WireworldGrid::next_state(WireworldGrid::CONDUCTOR, 2), WireworldGrid::HEAD);
+        assert_eq!(WireworldGrid::next_state(WireworldGrid::CONDUCTOR, 3), WireworldGrid::CONDUCTOR);
+        assert_eq!(WireworldGrid::next_state(WireworldGrid::EMPTY, 2), WireworldGrid::EMPTY);
+    }
+
+    #[test]
+    fn test_cycle_cell_state_visits_all_states() {
+        let mut grid = WireworldGrid::new(1, 1, false);
+        let mut visited = Vec::new();
+        for _ in 0..4 {
+            grid.cycle_cell_state(0, 0);
+            visited.push(grid.current_cells()[0]);
+        }
+        assert_eq!(visited, vec![3, 1, 2, 0]);
+    }
+}